@@ -1,24 +1,58 @@
 use color_eyre::eyre::Result;
 use tracing::info;
 
-/// List all inventory items (stub implementation)
-pub async fn list() -> Result<()> {
-    info!("📦 Listing inventory items...");
-    info!("🚧 This is a placeholder implementation");
-    info!("📋 Future implementation will query the database for inventory items");
-    info!("💡 Use 'wms-cli inventory list' once the inventory system is implemented");
-    
-    // Simulate some inventory items for demonstration
-    let placeholder_items = vec![
-        ("SKU-001", "Widget A", 150),
-        ("SKU-002", "Widget B", 75),
-        ("SKU-003", "Gadget X", 200),
-    ];
-    
-    info!("📦 Sample inventory items:");
-    for (sku, name, quantity) in placeholder_items {
-        info!("  • {} - {} (Qty: {})", sku, name, quantity);
+use crate::config::{AppMode, Backend};
+use crate::output::{Output, Style};
+
+/// List all inventory items
+pub async fn list(out: &Output, backend: &Backend) -> Result<()> {
+    info!("{}", out.render(Style::Package, "Listing inventory items..."));
+    if backend.mode() == AppMode::Mock {
+        info!("{}", out.render(Style::Pending, "Mock mode: showing sample items, not the database"));
+    }
+
+    let items = backend.inventory_items().await?;
+    if items.is_empty() {
+        info!("{}", out.render(Style::Info, "No inventory items found"));
+        return Ok(());
+    }
+
+    info!("{}", out.render(Style::Package, &format!("{} inventory item(s):", items.len())));
+    for item in &items {
+        info!("  • {} - {} (Qty: {})", item.sku, item.name, item.quantity);
     }
-    
+
     Ok(())
-}
\ No newline at end of file
+}
+
+/// List items at or below their reorder threshold
+pub async fn low_stock(out: &Output, backend: &Backend) -> Result<()> {
+    let items = backend.low_stock_items().await?;
+    if items.is_empty() {
+        info!("{}", out.render(Style::Success, "No items are low on stock"));
+        return Ok(());
+    }
+
+    info!("{}", out.render(Style::Package, &format!("{} item(s) low on stock:", items.len())));
+    for item in &items {
+        info!(
+            "  • {} - {} (Qty: {}, reorder at: {})",
+            item.sku, item.name, item.quantity, item.reorder_threshold
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[tokio::test]
+    async fn test_mock_mode_lists_without_database() {
+        let backend = Config::new(AppMode::Mock).connect().await.unwrap();
+        list(&Output::new(false), &backend).await.unwrap();
+        low_stock(&Output::new(false), &backend).await.unwrap();
+    }
+}
@@ -1,3 +1,4 @@
 pub mod system;
 pub mod inventory;
-pub mod order;
\ No newline at end of file
+pub mod order;
+pub mod plan;pub mod worker;
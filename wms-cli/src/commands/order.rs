@@ -1,10 +1,27 @@
 use color_eyre::eyre::Result;
-use tracing::info;
+use std::time::Duration;
+use tracing::{info, warn};
+use wms_db::{initial_order_status, is_transient_error, normalize_order_tag, NewOrderItem, Order, OrderRepository, OrderSort};
 
-/// Create a new order (stub implementation)
-pub async fn create(item: String, quantity: u32) -> Result<()> {
-    info!("📝 Creating new order...");
-    info!("🚧 This is a placeholder implementation");
+use crate::output::{Output, Style};
+
+/// How many times `create` retries after a transient database error
+pub const CREATE_RETRIES: u32 = 3;
+
+/// Pause before the first retry, doubled for each one after
+const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Create a new order
+pub async fn create(
+    out: &Output,
+    repo: &dyn OrderRepository,
+    item: String,
+    sku: Option<String>,
+    quantity: u32,
+    tags: &[String],
+    status: Option<&str>,
+) -> Result<()> {
+    info!("{}", out.render(Style::Note, "Creating new order..."));
     
     // Validate inputs
     if item.trim().is_empty() {
@@ -14,17 +31,363 @@ pub async fn create(item: String, quantity: u32) -> Result<()> {
     if quantity == 0 {
         color_eyre::eyre::bail!("Quantity must be greater than 0");
     }
+
+    let quantity = i32::try_from(quantity)
+        .map_err(|_| color_eyre::eyre::eyre!("Quantity {} is too large", quantity))?;
+    // Checked up front so a bad tag doesn't leave an untagged order behind
+    let tags = tags
+        .iter()
+        .map(|tag| normalize_order_tag(tag))
+        .collect::<Result<Vec<_>>>()?;
+    let status = initial_order_status(status)?;
+    // Without an explicit SKU the item name identifies the line
+    let sku = sku.unwrap_or_else(|| item.clone());
     
-    info!("📦 Order details:");
+    info!("{}", out.render(Style::Package, "Order details:"));
     info!("  • Item: {}", item);
+    info!("  • SKU: {}", sku);
     info!("  • Quantity: {}", quantity);
+    info!("  • Status: {}", status);
+    if !tags.is_empty() {
+        info!("  • Tags: {}", tags.join(", "));
+    }
     
-    // Simulate order creation
-    let order_id = format!("ORD-{:06}", fastrand::u32(100000..999999));
+    let order = create_with_retries(repo, &[NewOrderItem::new(sku, item, quantity)], status.as_str()).await?;
+    for tag in &tags {
+        repo.add_order_tag(&order.id, tag).await?;
+    }
     
-    info!("✅ Order created successfully!");
-    info!("📋 Order ID: {}", order_id);
-    info!("💡 Future implementation will store this order in the database");
+    info!("{}", out.render(Style::Success, "Order created successfully!"));
+    info!("{}", out.render(Style::Info, &format!("Order ID: {}", order.id)));
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Create the order under one idempotency key, retrying transient failures
+///
+/// Every attempt reuses the key, so an attempt whose commit succeeded but
+/// whose response was lost is returned by the retry rather than duplicated.
+async fn create_with_retries(repo: &dyn OrderRepository, items: &[NewOrderItem], status: &str) -> Result<Order> {
+    let key = uuid::Uuid::new_v4().to_string();
+    let mut attempt = 0;
+    loop {
+        match repo.create_order_idempotent(items, Some(status), &key).await {
+            Err(e) if attempt < CREATE_RETRIES && is_transient_error(&e) => {
+                let delay = RETRY_BACKOFF * 2u32.pow(attempt);
+                attempt += 1;
+                warn!("Order creation failed ({}), retrying in {:?} ({}/{})", e, delay, attempt, CREATE_RETRIES);
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Show the fulfillment progress of an order
+pub async fn status(out: &Output, repo: &dyn OrderRepository, id: &str) -> Result<()> {
+    let Some(order) = repo.get_order(id).await? else {
+        color_eyre::eyre::bail!("Order {} not found", id);
+    };
+
+    info!("{}", out.render(Style::Info, &format!("Order ID: {}", order.id)));
+    info!("  • Status: {}", order.status);
+    for item in &order.items {
+        info!(
+            "  • {} - {} (Picked: {}/{})",
+            item.sku, item.item_name, item.picked_quantity, item.quantity
+        );
+    }
+    info!(
+        "{}",
+        out.render(
+            Style::Package,
+            &format!("Fulfillment: {:.1}% complete", order.fulfillment_ratio() * 100.0)
+        )
+    );
+
+    Ok(())
+}
+
+/// Show the prices captured when an order was created
+pub async fn total(out: &Output, repo: &dyn OrderRepository, id: &str) -> Result<()> {
+    let Some(order) = repo.get_order(id).await? else {
+        color_eyre::eyre::bail!("Order {} not found", id);
+    };
+
+    info!("{}", out.render(Style::Info, &format!("Order ID: {}", order.id)));
+    for item in &order.items {
+        info!(
+            "  • {} - {}: {} × {} = {}",
+            item.sku, item.item_name, item.quantity, item.unit_price, item.total_price
+        );
+    }
+    info!("{}", out.render(Style::Package, &format!("Total: {}", order.total_price())));
+
+    Ok(())
+}
+
+/// Print a confirmation receipt for an order
+pub async fn receipt(repo: &dyn OrderRepository, id: &str) -> Result<()> {
+    let Some(order) = repo.get_order(id).await? else {
+        color_eyre::eyre::bail!("Order {} not found", id);
+    };
+
+    // Printed directly so the receipt isn't broken up by log prefixes
+    println!("{}", order.receipt(&order.items));
+    Ok(())
+}
+
+/// Delete an order and its items and tags; refuses to run without `force`
+pub async fn delete(out: &Output, repo: &dyn OrderRepository, id: &str, force: bool) -> Result<()> {
+    if !force {
+        color_eyre::eyre::bail!("Deleting order {} is permanent; pass --force to confirm", id);
+    }
+
+    let removed = repo.delete_order(id).await?;
+    if removed == 0 {
+        color_eyre::eyre::bail!("Order {} not found", id);
+    }
+    info!("{}", out.render(Style::Success, &format!("Deleted order {} ({} row(s) removed)", id, removed)));
+
+    Ok(())
+}
+
+/// List all orders in the requested order
+pub async fn list(out: &Output, repo: &dyn OrderRepository, sort: OrderSort) -> Result<()> {
+    let orders = repo.list_orders_sorted(sort).await?;
+    if orders.is_empty() {
+        info!("{}", out.render(Style::Info, "No orders found"));
+        return Ok(());
+    }
+
+    info!("{}", out.render(Style::Package, &format!("{} order(s), sorted by {}:", orders.len(), sort)));
+    for order in &orders {
+        let units: i64 = order.items.iter().map(|item| item.quantity as i64).sum();
+        info!(
+            "  • {} - {} ({} unit(s), created {})",
+            order.id,
+            order.status,
+            units,
+            out.timestamp(&order.created_at)
+        );
+    }
+
+    Ok(())
+}
+
+/// List unshipped orders past their SLA deadline, most overdue first
+pub async fn sla_breaches(out: &Output, repo: &dyn OrderRepository) -> Result<()> {
+    let orders = repo.breached_orders().await?;
+    if orders.is_empty() {
+        info!("{}", out.render(Style::Success, "No orders past their SLA"));
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now();
+    warn!("{}", out.render(Style::Failure, &format!("{} order(s) past their SLA:", orders.len())));
+    for order in &orders {
+        let Some(deadline) = order.sla_deadline else {
+            continue;
+        };
+        let overdue = now - deadline;
+        info!(
+            "  • {} - {} (due {}, {}h {:02}m overdue)",
+            order.id,
+            order.status,
+            out.timestamp(&deadline),
+            overdue.num_hours(),
+            overdue.num_minutes() % 60
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wms_db::mock::MockOrderRepository;
+    use wms_db::Decimal;
+
+    #[tokio::test]
+    async fn test_create_stores_order_in_repository() {
+        let repo = MockOrderRepository::new();
+        create(&Output::new(false), &repo, "Widget A".to_string(), None, 3, &[], None)
+            .await
+            .unwrap();
+
+        let orders = repo.orders();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].items.len(), 1);
+        assert_eq!(orders[0].items[0].sku, "Widget A");
+        assert_eq!(orders[0].items[0].quantity, 3);
+    }
+
+    #[tokio::test]
+    async fn test_create_uses_explicit_sku() {
+        let repo = MockOrderRepository::new();
+        create(&Output::new(false), &repo, "Widget A".to_string(), Some("SKU-001".to_string()), 1, &[], None)
+            .await
+            .unwrap();
+
+        assert_eq!(repo.orders()[0].items[0].sku, "SKU-001");
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_invalid_input_without_storing() {
+        let repo = MockOrderRepository::new();
+        let out = Output::new(false);
+        assert!(create(&out, &repo, "  ".to_string(), None, 3, &[], None).await.is_err());
+        assert!(create(&out, &repo, "Widget A".to_string(), None, 0, &[], None).await.is_err());
+        assert!(repo.orders().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_missing_order() {
+        let repo = MockOrderRepository::new();
+        assert!(status(&Output::new(false), &repo, "ORD-404404").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_total_sums_priced_lines() {
+        let repo = MockOrderRepository::new()
+            .with_unit_price("SKU-001", Decimal::new(1999, 2))
+            .with_unit_price("SKU-002", Decimal::new(5, 1));
+        let order = repo
+            .create_order(&[
+                NewOrderItem::new("SKU-001", "Widget A", 3),
+                NewOrderItem::new("SKU-002", "Widget B", 4),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(order.total_price(), Decimal::new(6197, 2));
+        total(&Output::new(false), &repo, &order.id).await.unwrap();
+        assert!(total(&Output::new(false), &repo, "ORD-404404").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_receipt_for_stored_order() {
+        let repo = MockOrderRepository::new();
+        let order = repo.create_order(&[NewOrderItem::new("SKU-001", "Widget A", 2)]).await.unwrap();
+
+        receipt(&repo, &order.id).await.unwrap();
+        assert!(receipt(&repo, "ORD-404404").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_sorts_through_repository() {
+        let repo = MockOrderRepository::new();
+        for quantity in [2, 9, 4] {
+            repo.create_order(&[NewOrderItem::new("SKU-001", "Widget A", quantity)]).await.unwrap();
+        }
+
+        for sort in OrderSort::ALL {
+            list(&Output::new(false), &repo, sort).await.unwrap();
+        }
+        let by_quantity = repo.list_orders_sorted(OrderSort::QuantityDesc).await.unwrap();
+        let quantities: Vec<_> = by_quantity.iter().map(|order| order.items[0].quantity).collect();
+        assert_eq!(quantities, vec![9, 4, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_create_applies_repeated_tags() {
+        let repo = MockOrderRepository::new();
+        let tags = ["fragile".to_string(), " gift ".to_string()];
+        create(&Output::new(false), &repo, "Widget A".to_string(), None, 1, &tags, None)
+            .await
+            .unwrap();
+
+        let order_id = &repo.orders()[0].id;
+        assert_eq!(repo.tags(order_id), vec!["fragile", "gift"]);
+
+        // An invalid tag is rejected before any order is stored
+        let bad = ["ok".to_string(), " ".to_string()];
+        assert!(create(&Output::new(false), &repo, "Widget B".to_string(), None, 1, &bad, None).await.is_err());
+        assert_eq!(repo.orders().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_delete_requires_force() {
+        let repo = MockOrderRepository::new();
+        let order = repo
+            .create_order(&[NewOrderItem::new("SKU-001", "Widget A", 1)])
+            .await
+            .unwrap();
+
+        assert!(delete(&Output::new(false), &repo, &order.id, false).await.is_err());
+        assert_eq!(repo.orders().len(), 1);
+
+        delete(&Output::new(false), &repo, &order.id, true).await.unwrap();
+        assert!(repo.orders().is_empty());
+        assert!(delete(&Output::new(false), &repo, &order.id, true).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_mode_orders_stay_in_memory() {
+        use crate::config::{AppMode, Backend, Config};
+
+        let backend = Config::new(AppMode::Mock).connect().await.unwrap();
+        let out = Output::new(false);
+        create(&out, backend.orders(), "Widget A".to_string(), None, 2, &[], None).await.unwrap();
+        list(&out, backend.orders(), OrderSort::default()).await.unwrap();
+
+        let Backend::Mock(repo) = &backend else {
+            panic!("mock mode should not connect to the database");
+        };
+        let order_id = repo.orders()[0].id.clone();
+        status(&out, backend.orders(), &order_id).await.unwrap();
+        backend.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_create_with_initial_status() {
+        let repo = MockOrderRepository::new();
+        let out = Output::new(false);
+        create(&out, &repo, "Widget A".to_string(), None, 1, &[], Some("confirmed")).await.unwrap();
+        assert_eq!(repo.orders()[0].status, wms_db::OrderStatus::Confirmed);
+
+        let err = create(&out, &repo, "Widget A".to_string(), None, 1, &[], Some("on-hold")).await.unwrap_err();
+        assert!(err.to_string().contains("Unknown order status 'on-hold'"));
+        assert_eq!(repo.orders().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_retries_lost_commit_without_duplicating() {
+        // The first attempt commits but its acknowledgement is lost
+        let repo = MockOrderRepository::new().with_lost_acknowledgements(1);
+        create(&Output::new(false), &repo, "Widget A".to_string(), None, 2, &["fragile".to_string()], None)
+            .await
+            .unwrap();
+
+        let orders = repo.orders();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(repo.tags(&orders[0].id), ["fragile"]);
+    }
+
+    #[tokio::test]
+    async fn test_create_gives_up_after_retries() {
+        let repo = MockOrderRepository::new().with_lost_acknowledgements(CREATE_RETRIES + 1);
+        let err = create(&Output::new(false), &repo, "Widget A".to_string(), None, 2, &[], None).await.unwrap_err();
+        assert!(is_transient_error(&err));
+        assert_eq!(repo.orders().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sla_breaches_lists_only_overdue_unshipped_orders() {
+        let repo = MockOrderRepository::new();
+        let items = [NewOrderItem::new("SKU-001", "Widget A", 1)];
+        let late = repo.create_order(&items).await.unwrap();
+        let on_time = repo.create_order(&items).await.unwrap();
+        let shipped = repo.create_order_with_status(&items, Some("shipped")).await.unwrap();
+
+        let now = chrono::Utc::now();
+        repo.set_sla_deadline(&late.id, Some(now - chrono::Duration::hours(2))).await.unwrap();
+        repo.set_sla_deadline(&on_time.id, Some(now + chrono::Duration::hours(2))).await.unwrap();
+        repo.set_sla_deadline(&shipped.id, Some(now - chrono::Duration::hours(2))).await.unwrap();
+
+        sla_breaches(&Output::new(false), &repo).await.unwrap();
+        let breached: Vec<_> = repo.breached_orders().await.unwrap().into_iter().map(|order| order.id).collect();
+        assert_eq!(breached, [late.id]);
+    }
+}
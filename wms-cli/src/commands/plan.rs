@@ -0,0 +1,831 @@
+use clap::{Args, Subcommand, ValueEnum};
+use color_eyre::eyre::{Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+use wms_db::WorkerRecord;
+use std::collections::HashMap;
+use wms_planner::planner::greedy::GreedyBatchPlanner;
+use wms_planner::planner::splitting::SplittingPlanner;
+use wms_planner::planner::TimeCostEstimator;
+use wms_planner::{
+    estimate_makespan, gini_coefficient, input_problems, unknown_skills, validate_input, Assignment, GreedyPlanner,
+    InputError, Location, MinTravelPlanner, PlanSummary, PlannerObserver, RejectionReason, Task, TaskId, TaskPlanner,
+    Worker, WorkerId,
+};
+
+use crate::config::Config;
+use crate::map::{render_map, terminal_width};
+use crate::output::{Output, Style};
+
+/// Planning algorithm selectable from the command line
+///
+/// There is no optimal (Hungarian) assignment; `min-travel` is the closest
+/// substitute when total distance matters most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Algorithm {
+    /// One task per worker, nearest worker first
+    Greedy,
+    /// Several tasks per worker, up to --max-tasks-per-worker
+    Batch,
+    /// Routes of up to --max-tasks-per-worker tasks minimizing total distance; ignores --cost-model
+    MinTravel,
+    /// Splittable tasks shared across workers up to each worker's `max_tasks`
+    Split,
+}
+
+/// How candidate assignments are costed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CostModel {
+    /// Distance to the task, adjusted for load and priority
+    Distance,
+    /// Minutes of travel and work, honoring each worker's travel speed
+    Time,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct PlanArgs {
+    /// Scenario JSON file containing `tasks` and `workers`
+    #[arg(short, long)]
+    pub input: PathBuf,
+    /// Planning algorithm to run
+    #[arg(short, long, value_enum, default_value_t = Algorithm::Greedy)]
+    pub algorithm: Algorithm,
+    /// Cost model used to compare workers
+    #[arg(long, value_enum, default_value_t = CostModel::Distance)]
+    pub cost_model: CostModel,
+    /// Maximum tasks per worker for the batch and min-travel algorithms
+    #[arg(long, default_value_t = 3)]
+    pub max_tasks_per_worker: usize,
+    /// Write the resulting plan as JSON to this file
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+    /// Print a cost summary without saving the plan
+    #[arg(long)]
+    pub preview: bool,
+    /// Plan against workers stored in the database instead of the scenario's workers
+    #[arg(long)]
+    pub from_db: bool,
+    /// Render an ASCII map of workers and tasks after planning
+    #[arg(long)]
+    pub map: bool,
+}
+
+/// `plan` with its arguments, or one of its subcommands
+#[derive(Debug, Clone, Args)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct PlanCommand {
+    #[command(subcommand)]
+    pub command: Option<PlanCommands>,
+    #[command(flatten)]
+    pub args: Option<PlanArgs>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum PlanCommands {
+    /// Run a planner over a scenario and report costs, makespan, fairness and unassigned tasks
+    Analyze(AnalyzeArgs),
+    /// Check a scenario for invalid input without planning, exiting nonzero if any is found
+    Validate(ValidateArgs),
+    /// Run several planners over a scenario and print their results side by side
+    Compare(CompareArgs),
+    /// Print the JSON Schema of tasks, workers and assignments
+    Schema(SchemaArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct AnalyzeArgs {
+    /// Scenario JSON file containing `tasks` and `workers`
+    #[arg(short, long)]
+    pub input: PathBuf,
+    /// Planning algorithm to run
+    #[arg(short, long, value_enum, default_value_t = Algorithm::Greedy)]
+    pub algorithm: Algorithm,
+    /// Maximum tasks per worker for the batch and min-travel algorithms
+    #[arg(long, default_value_t = 3)]
+    pub max_tasks_per_worker: usize,
+    /// Write the report as JSON to this file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ValidateArgs {
+    /// Scenario JSON file containing `tasks` and `workers`
+    #[arg(short, long)]
+    pub input: PathBuf,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct SchemaArgs {
+    /// Write the schema to this file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct CompareArgs {
+    /// Scenario JSON file containing `tasks` and `workers`
+    #[arg(short, long)]
+    pub input: PathBuf,
+    /// Comma-separated planning algorithms to compare
+    #[arg(short, long, value_enum, value_delimiter = ',', default_values_t = [Algorithm::Greedy, Algorithm::Batch])]
+    pub algorithms: Vec<Algorithm>,
+    /// Cost model used to compare workers
+    #[arg(long, value_enum, default_value_t = CostModel::Distance)]
+    pub cost_model: CostModel,
+    /// Maximum tasks per worker for the batch and min-travel algorithms
+    #[arg(long, default_value_t = 3)]
+    pub max_tasks_per_worker: usize,
+}
+
+/// Tasks and workers to plan over
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    pub tasks: Vec<Task>,
+    #[serde(default)]
+    pub workers: Vec<Worker>,
+}
+
+/// Load a scenario from a JSON file
+pub fn load_scenario(path: &Path) -> Result<Scenario> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read scenario file {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid scenario file {}", path.display()))
+}
+
+/// Whether a plan file is gzip-compressed, judged by a `.gz` extension
+fn is_gzipped(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("gz"))
+}
+
+/// Save plan assignments as JSON, gzip-compressed if the path ends in `.gz`
+pub fn save_plan(path: &Path, assignments: &[Assignment]) -> Result<()> {
+    let json = serde_json::to_string_pretty(assignments)?;
+    let contents = if is_gzipped(path) {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(json.as_bytes())?;
+        encoder.finish().context("Failed to compress plan")?
+    } else {
+        json.into_bytes()
+    };
+    std::fs::write(path, contents).with_context(|| format!("Failed to write plan file {}", path.display()))
+}
+
+/// Load plan assignments saved by [`save_plan`], decompressing `.gz` files
+#[allow(dead_code)] // no command reads plans back yet
+pub fn load_plan(path: &Path) -> Result<Vec<Assignment>> {
+    let bytes = std::fs::read(path).with_context(|| format!("Failed to read plan file {}", path.display()))?;
+    let json = if is_gzipped(path) {
+        let mut json = String::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_string(&mut json)
+            .with_context(|| format!("Failed to decompress plan file {}", path.display()))?;
+        json
+    } else {
+        String::from_utf8(bytes).with_context(|| format!("Plan file {} is not UTF-8", path.display()))?
+    };
+    serde_json::from_str(&json).with_context(|| format!("Invalid plan file {}", path.display()))
+}
+
+/// Convert persisted workers into planner workers
+///
+/// Workers without a recorded location are skipped with a warning rather than
+/// being placed at the origin.
+pub fn located_workers(records: Vec<WorkerRecord>) -> Vec<Worker> {
+    records
+        .into_iter()
+        .filter_map(|record| {
+            let Some((x, y)) = record.location else {
+                warn!("Skipping worker {} ({}): no recorded location", record.id, record.name);
+                return None;
+            };
+            let mut worker = Worker::new(record.id as u32, Location::new(x, y), record.is_available)
+                .with_load(record.current_load)
+                .with_max_tasks(record.max_tasks.max(0) as usize);
+            worker.travel_speed = record.travel_speed;
+            Some(worker)
+        })
+        .collect()
+}
+
+/// Run the selected planner over a scenario, rejecting invalid input
+pub fn run_planner(
+    algorithm: Algorithm,
+    cost_model: CostModel,
+    max_tasks_per_worker: usize,
+    scenario: &Scenario,
+) -> Result<Vec<Assignment>> {
+    let (tasks, workers) = (&scenario.tasks, &scenario.workers);
+    let assignments = match (algorithm, cost_model) {
+        (Algorithm::Greedy, CostModel::Distance) => GreedyPlanner::new().try_plan(tasks, workers),
+        (Algorithm::Greedy, CostModel::Time) => {
+            GreedyPlanner::with_cost_estimator(TimeCostEstimator::default()).try_plan(tasks, workers)
+        }
+        (Algorithm::Batch, CostModel::Distance) => {
+            GreedyBatchPlanner::new().try_plan_batch(tasks, workers, max_tasks_per_worker)
+        }
+        (Algorithm::Batch, CostModel::Time) => GreedyBatchPlanner::with_cost_estimator(TimeCostEstimator::default())
+            .try_plan_batch(tasks, workers, max_tasks_per_worker),
+        (Algorithm::MinTravel, _) => {
+            validate_input(tasks, workers).map(|()| MinTravelPlanner::new(max_tasks_per_worker).plan(tasks, workers))
+        }
+        (Algorithm::Split, CostModel::Distance) => {
+            validate_input(tasks, workers).map(|()| SplittingPlanner::new().plan(tasks, workers))
+        }
+        (Algorithm::Split, CostModel::Time) => validate_input(tasks, workers)
+            .map(|()| SplittingPlanner::with_cost_estimator(TimeCostEstimator::default()).plan(tasks, workers)),
+    };
+    assignments.context("Invalid scenario")
+}
+
+/// Plan a scenario, then print or save the result
+///
+/// Only `--from-db` connects, through the backend for `config`'s mode.
+pub async fn run(out: &Output, config: &Config, args: &PlanArgs) -> Result<()> {
+    let mut scenario = load_scenario(&args.input)?;
+    if args.from_db {
+        let backend = config.connect().await?;
+        let records = async { backend.database("Loading workers with --from-db")?.list_workers().await }.await;
+        backend.close().await;
+        scenario.workers = located_workers(records?);
+    }
+
+    info!(
+        "{}",
+        out.render(
+            Style::Note,
+            &format!(
+                "Planning {} task(s) across {} worker(s) with {:?} planner...",
+                scenario.tasks.len(),
+                scenario.workers.len(),
+                args.algorithm
+            )
+        )
+    );
+
+    let assignments = run_planner(args.algorithm, args.cost_model, args.max_tasks_per_worker, &scenario)?;
+
+    if args.map {
+        // Printed directly so the grid isn't broken up by log prefixes
+        println!(
+            "{}",
+            render_map(&scenario.tasks, &scenario.workers, &assignments, terminal_width())
+        );
+    }
+
+    if args.preview {
+        print_summary(out, &PlanSummary::new(&scenario.tasks, &assignments));
+        info!("{}", out.render(Style::Hint, "Preview only: the plan was not saved"));
+        return Ok(());
+    }
+
+    for assignment in &assignments {
+        info!(
+            "  • Task {} → Worker {} (cost: {:.2})",
+            assignment.task_id, assignment.worker_id, assignment.estimated_cost
+        );
+    }
+
+    if let Some(path) = &args.output {
+        save_plan(path, &assignments)?;
+        info!("{}", out.render(Style::Success, &format!("Plan saved to {}", path.display())));
+    }
+
+    Ok(())
+}
+
+/// What-if report for one planner run over a scenario
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanReport {
+    pub algorithm: Algorithm,
+    pub assignments: Vec<Assignment>,
+    pub unassigned: Vec<UnassignedTask>,
+    pub total_cost: f64,
+    pub average_cost: f64,
+    pub max_cost: f64,
+    /// Minutes until the busiest worker finishes, from `estimate_makespan` at default speed and durations
+    pub makespan_minutes: f64,
+    /// Gini coefficient of total cost over the workers able to take tasks, idle ones counting as 0
+    pub fairness_gini: f64,
+}
+
+/// A task the planner left unassigned, with why each worker was rejected
+#[derive(Debug, Clone, Serialize)]
+pub struct UnassignedTask {
+    pub task_id: TaskId,
+    pub reasons: Vec<WorkerRejection>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerRejection {
+    pub worker_id: WorkerId,
+    pub reason: RejectionReason,
+}
+
+/// Observer keeping every rejection, grouped by task
+#[derive(Debug, Default)]
+struct RejectionCollector {
+    rejections: HashMap<TaskId, Vec<WorkerRejection>>,
+}
+
+impl PlannerObserver for RejectionCollector {
+    fn on_candidate_rejected(&mut self, task_id: TaskId, worker_id: WorkerId, reason: RejectionReason) {
+        self.rejections
+            .entry(task_id)
+            .or_default()
+            .push(WorkerRejection { worker_id, reason });
+    }
+}
+
+/// Minutes from now until the busiest worker is done, at 1 unit per minute and 30 minutes per task by default
+fn makespan_minutes(assignments: &[Assignment], tasks: &[Task], workers: &[Worker]) -> f64 {
+    estimate_makespan(assignments, tasks, workers, chrono::Utc::now(), 1.0, 30.0)
+}
+
+/// Plan a scenario with `algorithm` and compute its report
+pub fn analyze_scenario(algorithm: Algorithm, max_tasks_per_worker: usize, scenario: &Scenario) -> Result<PlanReport> {
+    let (tasks, workers) = (&scenario.tasks, &scenario.workers);
+    validate_input(tasks, workers).context("Invalid scenario")?;
+
+    let mut collector = RejectionCollector::default();
+    let assignments = match algorithm {
+        Algorithm::Greedy => GreedyPlanner::new().plan_with_observer(tasks, workers, &mut collector),
+        Algorithm::Batch => {
+            GreedyBatchPlanner::new().plan_batch_with_observer(tasks, workers, max_tasks_per_worker, &mut collector)
+        }
+        // These report no rejections, so their unassigned tasks come without reasons
+        Algorithm::MinTravel | Algorithm::Split => {
+            run_planner(algorithm, CostModel::Distance, max_tasks_per_worker, scenario)?
+        }
+    };
+
+    let summary = PlanSummary::new(tasks, &assignments);
+    let unassigned = summary
+        .unassigned_tasks
+        .iter()
+        .map(|&task_id| UnassignedTask {
+            task_id,
+            reasons: collector.rejections.remove(&task_id).unwrap_or_default(),
+        })
+        .collect();
+    let worker_costs: Vec<f64> = workers
+        .iter()
+        .filter(|worker| worker.can_accept_task())
+        .map(|worker| summary.per_worker.get(&worker.id).map_or(0.0, |w| w.total_cost))
+        .collect();
+
+    Ok(PlanReport {
+        algorithm,
+        average_cost: if assignments.is_empty() { 0.0 } else { summary.total_cost / assignments.len() as f64 },
+        max_cost: assignments.iter().map(|a| a.estimated_cost).fold(0.0, f64::max),
+        makespan_minutes: makespan_minutes(&assignments, tasks, workers),
+        fairness_gini: gini_coefficient(&worker_costs),
+        total_cost: summary.total_cost,
+        unassigned,
+        assignments,
+    })
+}
+
+/// Analyze a scenario, printing the JSON report or writing it to `--output`
+pub fn analyze(out: &Output, args: &AnalyzeArgs) -> Result<()> {
+    let scenario = load_scenario(&args.input)?;
+    let report = analyze_scenario(args.algorithm, args.max_tasks_per_worker, &scenario)?;
+    let json = serde_json::to_string_pretty(&report)?;
+
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, json).with_context(|| format!("Failed to write report file {}", path.display()))?;
+            info!("{}", out.render(Style::Success, &format!("Report saved to {}", path.display())));
+        }
+        // Printed directly so the JSON can be piped
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+/// Everything wrong with a scenario: what planners reject, then skills no worker has
+pub fn scenario_problems(scenario: &Scenario) -> Vec<InputError> {
+    let mut problems = input_problems(&scenario.tasks, &scenario.workers);
+    problems.extend(unknown_skills(&scenario.tasks, &scenario.workers));
+    problems
+}
+
+/// Check a scenario file, printing each problem and failing if there are any
+pub fn validate(out: &Output, args: &ValidateArgs) -> Result<()> {
+    let scenario = load_scenario(&args.input)?;
+    let problems = scenario_problems(&scenario);
+    if problems.is_empty() {
+        info!(
+            "{}",
+            out.render(
+                Style::Success,
+                &format!(
+                    "Scenario is valid: {} task(s), {} worker(s)",
+                    scenario.tasks.len(),
+                    scenario.workers.len()
+                )
+            )
+        );
+        return Ok(());
+    }
+
+    warn!("{}", out.render(Style::Failure, &format!("{} problem(s) in {}:", problems.len(), args.input.display())));
+    for problem in &problems {
+        warn!("  • {}", problem);
+    }
+    color_eyre::eyre::bail!("Scenario {} is invalid", args.input.display())
+}
+
+/// Headline results of one algorithm in `plan compare`
+#[derive(Debug, Clone)]
+pub struct AlgorithmResult {
+    pub algorithm: Algorithm,
+    pub total_cost: f64,
+    /// Minutes until the busiest worker finishes, from `estimate_makespan` at default speed and durations
+    pub makespan_minutes: f64,
+    pub unassigned: usize,
+    /// Time spent planning, excluding loading and scoring
+    pub runtime: Duration,
+}
+
+/// Run each of `algorithms` over a scenario, in the order given
+pub fn compare_algorithms(
+    algorithms: &[Algorithm],
+    cost_model: CostModel,
+    max_tasks_per_worker: usize,
+    scenario: &Scenario,
+) -> Result<Vec<AlgorithmResult>> {
+    algorithms
+        .iter()
+        .map(|&algorithm| {
+            let started = Instant::now();
+            let assignments = run_planner(algorithm, cost_model, max_tasks_per_worker, scenario)?;
+            let runtime = started.elapsed();
+            let summary = PlanSummary::new(&scenario.tasks, &assignments);
+            Ok(AlgorithmResult {
+                algorithm,
+                total_cost: summary.total_cost,
+                makespan_minutes: makespan_minutes(&assignments, &scenario.tasks, &scenario.workers),
+                unassigned: summary.unassigned_count(),
+                runtime,
+            })
+        })
+        .collect()
+}
+
+/// Lay out comparison results as a plain-text table, one row per algorithm
+pub fn render_comparison(results: &[AlgorithmResult]) -> String {
+    let mut table = format!(
+        "{:<10} {:>12} {:>15} {:>11} {:>13}",
+        "Algorithm", "Total cost", "Makespan (min)", "Unassigned", "Runtime (ms)"
+    );
+    for result in results {
+        let name = result.algorithm.to_possible_value().map(|value| value.get_name().to_string()).unwrap_or_default();
+        table.push_str(&format!(
+            "\n{:<10} {:>12.2} {:>15.2} {:>11} {:>13.3}",
+            name,
+            result.total_cost,
+            result.makespan_minutes,
+            result.unassigned,
+            result.runtime.as_secs_f64() * 1000.0
+        ));
+    }
+    table
+}
+
+/// Compare planners over a scenario and print the table
+pub fn compare(out: &Output, args: &CompareArgs) -> Result<()> {
+    let scenario = load_scenario(&args.input)?;
+    info!(
+        "{}",
+        out.render(
+            Style::Note,
+            &format!(
+                "Comparing {} algorithm(s) on {} task(s) across {} worker(s)...",
+                args.algorithms.len(),
+                scenario.tasks.len(),
+                scenario.workers.len()
+            )
+        )
+    );
+    let results = compare_algorithms(&args.algorithms, args.cost_model, args.max_tasks_per_worker, &scenario)?;
+    // Printed directly so the columns aren't broken up by log prefixes
+    println!("{}", render_comparison(&results));
+    Ok(())
+}
+
+/// Print the wire-format JSON Schema or write it to `--output`
+pub fn schema(out: &Output, args: &SchemaArgs) -> Result<()> {
+    let json = serde_json::to_string_pretty(&wms_planner::schema())?;
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, json).with_context(|| format!("Failed to write schema file {}", path.display()))?;
+            info!("{}", out.render(Style::Success, &format!("Schema saved to {}", path.display())));
+        }
+        // Printed directly so the JSON can be piped
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+fn print_summary(out: &Output, summary: &PlanSummary) {
+    info!("{}", out.render(Style::Info, "Plan preview:"));
+    info!("  • Assignments: {}", summary.assignment_count);
+    info!("  • Unassigned tasks: {}", summary.unassigned_count());
+    info!("  • Total cost: {:.2}", summary.total_cost);
+    for (worker_id, worker) in &summary.per_worker {
+        info!(
+            "  • Worker {}: {} task(s), cost {:.2}",
+            worker_id, worker.assignment_count, worker.total_cost
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppMode;
+    use wms_db::Database;
+    use wms_planner::Priority;
+
+    fn write_scenario(dir: &Path) -> PathBuf {
+        let scenario = Scenario {
+            tasks: vec![
+                Task::new(1, Location::new(0.0, 0.0), Priority::High),
+                Task::new(2, Location::new(10.0, 10.0), Priority::Medium),
+            ],
+            workers: vec![
+                Worker::new(1, Location::new(1.0, 1.0), true),
+                Worker::new(2, Location::new(11.0, 11.0), true),
+            ],
+        };
+        let path = dir.join("scenario.json");
+        std::fs::write(&path, serde_json::to_string(&scenario).unwrap()).unwrap();
+        path
+    }
+
+    fn args(input: PathBuf, output: PathBuf, preview: bool) -> PlanArgs {
+        PlanArgs {
+            input,
+            algorithm: Algorithm::Greedy,
+            cost_model: CostModel::Distance,
+            max_tasks_per_worker: 3,
+            output: Some(output),
+            preview,
+            from_db: false,
+            map: false,
+        }
+    }
+
+    #[test]
+    fn test_located_workers_skips_unlocated() {
+        let located = WorkerRecord {
+            id: 1,
+            name: "Located".to_string(),
+            is_available: true,
+            current_load: 0.5,
+            max_tasks: 2,
+            location: Some((3.0, 4.0)),
+            travel_speed: Some(2.5),
+        };
+        let unlocated = WorkerRecord {
+            id: 2,
+            name: "Unlocated".to_string(),
+            location: None,
+            ..located.clone()
+        };
+
+        let workers = located_workers(vec![located, unlocated]);
+        assert_eq!(workers.len(), 1);
+        assert_eq!(workers[0].id, 1);
+        assert_eq!(workers[0].location, Location::new(3.0, 4.0));
+        assert_eq!(workers[0].max_tasks, 2);
+        assert_eq!(workers[0].current_load, 0.5);
+        assert_eq!(workers[0].travel_speed, Some(2.5));
+    }
+
+    #[tokio::test]
+    async fn test_preview_never_saves_plan() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = write_scenario(dir.path());
+        let output = dir.path().join("plan.json");
+
+        run(&Output::new(false), &Config::default(), &args(input, output.clone(), true)).await.unwrap();
+        assert!(!output.exists());
+    }
+
+    #[tokio::test]
+    async fn test_plan_saves_assignments() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = write_scenario(dir.path());
+        let output = dir.path().join("plan.json");
+
+        run(&Output::new(false), &Config::default(), &args(input, output.clone(), false)).await.unwrap();
+        assert_eq!(load_plan(&output).unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_from_db_refused_in_mock_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = write_scenario(dir.path());
+        let output = dir.path().join("plan.json");
+        let mut plan_args = args(input, output.clone(), false);
+        plan_args.from_db = true;
+
+        // Refused before any connection attempt, whatever DATABASE_URL says
+        let err = run(&Output::new(false), &Config::new(AppMode::Mock), &plan_args).await.unwrap_err();
+        assert!(err.to_string().contains("not available in mock mode"), "{}", err);
+        assert!(!output.exists());
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires database connection
+    async fn test_from_db_honors_worker_travel_speed() {
+        if std::env::var("DATABASE_URL").is_err() {
+            eprintln!("Skipping integration test: DATABASE_URL not set");
+            return;
+        }
+
+        let db = Database::from_env().await.expect("Failed to connect to database");
+        db.migrate().await.expect("Migrations failed");
+        let walker = WorkerRecord {
+            id: 7031,
+            name: "Walker".to_string(),
+            is_available: true,
+            current_load: 0.0,
+            max_tasks: 1,
+            location: Some((5003.0, 5004.0)),
+            travel_speed: None,
+        };
+        // Three times as far, but ten times as fast
+        let driver = WorkerRecord {
+            id: 7032,
+            name: "Driver".to_string(),
+            location: Some((5009.0, 5012.0)),
+            travel_speed: Some(10.0),
+            ..walker.clone()
+        };
+        db.upsert_worker(&walker).await.unwrap();
+        db.upsert_worker(&driver).await.unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("tasks.json");
+        let scenario = Scenario {
+            tasks: vec![Task::new(1, Location::new(5000.0, 5000.0), Priority::Medium).with_duration(10.0)],
+            workers: Vec::new(),
+        };
+        std::fs::write(&input, serde_json::to_string(&scenario).unwrap()).unwrap();
+        let output = dir.path().join("plan.json");
+        let mut plan_args = args(input, output.clone(), false);
+        plan_args.from_db = true;
+
+        run(&Output::new(false), &Config::default(), &plan_args).await.unwrap();
+        assert_eq!(load_plan(&output).unwrap()[0].worker_id, 7031, "distance costs ignore speed");
+
+        plan_args.cost_model = CostModel::Time;
+        run(&Output::new(false), &Config::default(), &plan_args).await.unwrap();
+        assert_eq!(load_plan(&output).unwrap()[0].worker_id, 7032);
+
+        db.set_worker_available(7031, false).await.unwrap();
+        db.set_worker_available(7032, false).await.unwrap();
+        db.close().await;
+    }
+
+    #[test]
+    fn test_duplicate_task_ids_rejected() {
+        let scenario = Scenario {
+            tasks: vec![
+                Task::new(1, Location::new(0.0, 0.0), Priority::High),
+                Task::new(1, Location::new(5.0, 5.0), Priority::Low),
+            ],
+            workers: vec![Worker::new(1, Location::new(1.0, 1.0), true)],
+        };
+        let err = run_planner(Algorithm::Batch, CostModel::Distance, 3, &scenario).unwrap_err();
+        assert!(format!("{:#}", err).contains("Task ID 1 appears more than once"));
+    }
+
+    #[test]
+    fn test_analyze_reports_fixture_metrics() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("report.json");
+        let args = AnalyzeArgs {
+            input: Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/analyze_scenario.json"),
+            algorithm: Algorithm::Greedy,
+            max_tasks_per_worker: 3,
+            output: Some(output.clone()),
+        };
+        analyze(&Output::new(false), &args).unwrap();
+
+        let report: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        let close = |key: &str, expected: f64| {
+            let actual = report[key].as_f64().unwrap();
+            assert!((actual - expected).abs() < 1e-9, "{}: {} != {}", key, actual, expected);
+        };
+        assert_eq!(report["algorithm"], "greedy");
+        assert_eq!(report["assignments"].as_array().unwrap().len(), 2);
+        close("total_cost", 6.5);
+        close("average_cost", 3.25);
+        close("max_cost", 3.5);
+        // Worker 1: 5 travel + 10 work
+        close("makespan_minutes", 15.0);
+        // Worker costs 3.5 and 3.0; the unavailable worker is left out
+        close("fairness_gini", 1.0 / 26.0);
+
+        // Nobody has a forklift for task 1; task 4 finds every worker taken
+        let unassigned = report["unassigned"].as_array().unwrap();
+        assert_eq!(unassigned.len(), 2);
+        assert_eq!(unassigned[0]["task_id"], 1);
+        assert_eq!(unassigned[0]["reasons"][0], serde_json::json!({"worker_id": 1, "reason": "missing_skill"}));
+        assert_eq!(unassigned[0]["reasons"][2]["reason"], "worker_unavailable");
+        assert_eq!(unassigned[1]["task_id"], 4);
+        assert_eq!(unassigned[1]["reasons"][0]["reason"], "at_capacity");
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem_in_fixture() {
+        let input = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/invalid_scenario.json");
+        let problems: Vec<String> = scenario_problems(&load_scenario(&input).unwrap())
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            problems,
+            [
+                "Task ID 2 appears more than once",
+                "Tasks depend on each other in a cycle: 3 -> 4 -> 3",
+                "Worker ID 1 appears more than once",
+                "Task 5 requires skill 'forklfit', which no worker has",
+            ]
+        );
+
+        let err = validate(&Output::new(false), &ValidateArgs { input }).unwrap_err();
+        assert!(err.to_string().contains("is invalid"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let valid = ValidateArgs { input: write_scenario(dir.path()) };
+        validate(&Output::new(false), &valid).unwrap();
+    }
+
+    #[test]
+    fn test_compare_lists_every_requested_algorithm() {
+        let args = CompareArgs {
+            input: Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/analyze_scenario.json"),
+            algorithms: vec![Algorithm::Greedy, Algorithm::Batch, Algorithm::MinTravel, Algorithm::Split],
+            cost_model: CostModel::Distance,
+            max_tasks_per_worker: 3,
+        };
+        let results =
+            compare_algorithms(&args.algorithms, args.cost_model, args.max_tasks_per_worker, &load_scenario(&args.input).unwrap())
+                .unwrap();
+
+        let table = render_comparison(&results);
+        let rows: Vec<&str> = table.lines().collect();
+        assert_eq!(rows.len(), 5, "{}", table);
+        assert!(rows[0].starts_with("Algorithm"));
+        assert!(rows[1].starts_with("greedy"));
+        assert!(rows[2].starts_with("batch"));
+        assert!(rows[3].starts_with("min-travel "));
+        assert!(rows[4].starts_with("split"));
+        // Nobody has a forklift for task 1, whichever algorithm runs
+        assert!(results.iter().all(|result| result.unassigned >= 1));
+        compare(&Output::new(false), &args).unwrap();
+    }
+
+    #[test]
+    fn test_schema_written_to_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("schema.json");
+        schema(&Output::new(false), &SchemaArgs { output: Some(output.clone()) }).unwrap();
+
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        for definition in ["Task", "Worker", "Assignment"] {
+            assert!(written["$defs"][definition]["properties"].is_object(), "missing {}", definition);
+        }
+    }
+
+    #[test]
+    fn test_gzipped_plan_round_trips_and_is_smaller() {
+        let dir = tempfile::tempdir().unwrap();
+        let assignments: Vec<Assignment> = (1..=200)
+            .map(|id| Assignment::new(id, id % 7 + 1, id as f64 * 1.5).with_margin(0.25))
+            .collect();
+
+        let plain = dir.path().join("plan.json");
+        let compressed = dir.path().join("plan.json.gz");
+        save_plan(&plain, &assignments).unwrap();
+        save_plan(&compressed, &assignments).unwrap();
+
+        assert_eq!(load_plan(&compressed).unwrap(), assignments);
+        assert_eq!(load_plan(&plain).unwrap(), assignments);
+        let size = |path: &Path| std::fs::metadata(path).unwrap().len();
+        assert!(size(&compressed) < size(&plain));
+    }
+}
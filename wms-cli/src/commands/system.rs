@@ -1,37 +1,86 @@
 use color_eyre::eyre::Result;
 use tracing::{info, error};
-use wms_db::Database;
+use wms_db::SeedOutcome;
+
+use crate::config::Config;
+use crate::output::{Output, Style};
 
 /// Check system health including database connectivity
-pub async fn health() -> Result<()> {
+pub async fn health(out: &Output, config: &Config) -> Result<()> {
     info!("Running system health check...");
-    
+
     // Initialize database connection
-    match Database::from_env().await {
-        Ok(db) => {
-            info!("✅ Database connection established");
-            
-            // Run database health check
-            match db.health_check().await {
-                Ok(()) => {
-                    info!("✅ Database health check passed");
-                    info!("🎉 System health check completed successfully");
-                }
-                Err(e) => {
-                    error!("❌ Database health check failed: {}", e);
-                    return Err(e.into());
-                }
-            }
-            
-            // Close database connection gracefully
-            db.close().await;
-        }
+    let backend = match config.connect().await {
+        Ok(backend) => backend,
         Err(e) => {
-            error!("❌ Failed to establish database connection: {}", e);
-            error!("💡 Make sure your .env file is configured with DATABASE_URL");
-            return Err(e.into());
+            error!("{}", out.render(Style::Failure, &format!("Failed to establish database connection: {}", e)));
+            error!("{}", out.render(Style::Hint, "Make sure your .env file is configured with DATABASE_URL"));
+            return Err(e);
+        }
+    };
+    let Some(db) = backend.live_database() else {
+        info!("{}", out.render(Style::Info, "Mock mode: no database connection to check"));
+        return Ok(());
+    };
+    info!("{}", out.render(Style::Success, "Database connection established"));
+
+    // Run database health check
+    let result = db.health_check().await;
+    match &result {
+        Ok(()) => {
+            info!("{}", out.render(Style::Success, "Database health check passed"));
+            info!("{}", out.render(Style::Celebrate, "System health check completed successfully"));
+        }
+        Err(e) => error!("{}", out.render(Style::Failure, &format!("Database health check failed: {}", e))),
+    }
+
+    // Close database connection gracefully
+    backend.close().await;
+    result
+}
+
+/// Show where the database pool points and how much of it is in use
+pub async fn pool(out: &Output, config: &Config) -> Result<()> {
+    let backend = config.connect().await?;
+    let Some(db) = backend.live_database() else {
+        info!("{}", out.render(Style::Info, "Mock mode: no database connection pool"));
+        return Ok(());
+    };
+    let pool = db.connection_info();
+    backend.close().await;
+
+    info!("{}", out.render(Style::Info, &format!("Database: {}", pool.masked_url)));
+    info!(
+        "{}",
+        out.render(
+            Style::Info,
+            &format!(
+                "Pool: {} open ({} idle), min {}, max {}",
+                pool.size, pool.idle, pool.min_connections, pool.max_connections
+            )
+        )
+    );
+    Ok(())
+}
+
+/// Load demo data for onboarding and demos
+pub async fn seed(out: &Output, config: &Config, reset: bool) -> Result<()> {
+    let backend = config.connect().await?;
+    let result = async {
+        let db = backend.database("Seeding demo data")?;
+        info!("{}", out.render(Style::Note, "Seeding demo data..."));
+        db.migrate().await?;
+        db.seed_demo_data(reset).await
+    }
+    .await;
+    backend.close().await;
+
+    match result? {
+        SeedOutcome::Seeded => info!("{}", out.render(Style::Success, "Demo data seeded")),
+        SeedOutcome::AlreadySeeded => {
+            info!("{}", out.render(Style::Info, "Demo data already present, nothing to do"));
+            info!("{}", out.render(Style::Hint, "Use --reset to replace it"));
         }
     }
-    
     Ok(())
-}
\ No newline at end of file
+}
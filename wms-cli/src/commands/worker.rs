@@ -0,0 +1,17 @@
+use color_eyre::eyre::Result;
+use tracing::info;
+
+use crate::config::Config;
+use crate::output::{Output, Style};
+
+/// Mark a worker as available or unavailable for new tasks
+pub async fn set_available(out: &Output, config: &Config, id: i32, available: bool) -> Result<()> {
+    let backend = config.connect().await?;
+    let result = async { backend.database("Changing worker availability")?.set_worker_available(id, available).await }.await;
+    backend.close().await;
+    result?;
+
+    let state = if available { "available" } else { "unavailable" };
+    info!("{}", out.render(Style::Success, &format!("Worker {} marked {}", id, state)));
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use color_eyre::eyre::{eyre, Result};
+use std::fmt;
+use std::str::FromStr;
+use wms_db::mock::MockOrderRepository;
+use wms_db::{Database, Decimal, InventoryItem, OrderRepository};
+
+/// Whether commands talk to real services or to in-memory stand-ins
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AppMode {
+    /// Sample data and an in-memory order store; nothing is persisted
+    Mock,
+    /// The Postgres database configured through `DATABASE_URL`
+    #[default]
+    Live,
+}
+
+impl AppMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AppMode::Mock => "mock",
+            AppMode::Live => "live",
+        }
+    }
+}
+
+impl fmt::Display for AppMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AppMode {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "mock" => Ok(AppMode::Mock),
+            "live" => Ok(AppMode::Live),
+            other => Err(eyre!("Unknown mode '{}'; expected 'mock' or 'live'", other)),
+        }
+    }
+}
+
+/// Settings shared by every command
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Config {
+    pub mode: AppMode,
+}
+
+impl Config {
+    pub fn new(mode: AppMode) -> Self {
+        Self { mode }
+    }
+
+    /// Pick the mode from `--mock`, falling back to `WMS_MODE`, then live
+    pub fn resolve(mock_flag: bool, env_mode: Option<&str>) -> Result<Self> {
+        if mock_flag {
+            return Ok(Self::new(AppMode::Mock));
+        }
+        match env_mode.filter(|v| !v.is_empty()) {
+            Some(mode) => Ok(Self::new(mode.parse()?)),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Open the services for this mode
+    pub async fn connect(&self) -> Result<Backend> {
+        match self.mode {
+            AppMode::Mock => Ok(Backend::Mock(MockOrderRepository::new())),
+            AppMode::Live => Ok(Backend::Live(Database::from_env().await?)),
+        }
+    }
+}
+
+/// Services a command runs against, chosen once from the [`AppMode`]
+#[derive(Debug)]
+pub enum Backend {
+    Mock(MockOrderRepository),
+    Live(Database),
+}
+
+impl Backend {
+    pub fn mode(&self) -> AppMode {
+        match self {
+            Backend::Mock(_) => AppMode::Mock,
+            Backend::Live(_) => AppMode::Live,
+        }
+    }
+
+    /// The database, `None` in mock mode
+    ///
+    /// Commands get a database handle only from here or [`database`](Self::database),
+    /// so mock mode never reaches Postgres.
+    pub fn live_database(&self) -> Option<&Database> {
+        match self {
+            Backend::Mock(_) => None,
+            Backend::Live(db) => Some(db),
+        }
+    }
+
+    /// The database needed for `action`, refused in mock mode
+    pub fn database(&self, action: &str) -> Result<&Database> {
+        self.live_database()
+            .ok_or_else(|| eyre!("{} needs the database and is not available in mock mode", action))
+    }
+
+    pub fn orders(&self) -> &dyn OrderRepository {
+        match self {
+            Backend::Mock(repo) => repo,
+            Backend::Live(db) => db,
+        }
+    }
+
+    /// All inventory items ordered by SKU
+    pub async fn inventory_items(&self) -> Result<Vec<InventoryItem>> {
+        match self {
+            Backend::Mock(_) => Ok(sample_inventory()),
+            Backend::Live(db) => db.list_inventory_items().await,
+        }
+    }
+
+    /// Items at or below their reorder threshold, ordered by SKU
+    pub async fn low_stock_items(&self) -> Result<Vec<InventoryItem>> {
+        match self {
+            Backend::Mock(_) => Ok(sample_inventory().into_iter().filter(InventoryItem::is_low_stock).collect()),
+            Backend::Live(db) => db.low_stock_items().await,
+        }
+    }
+
+    pub async fn close(self) {
+        if let Backend::Live(db) = self {
+            db.close().await;
+        }
+    }
+}
+
+/// Inventory shown in mock mode
+fn sample_inventory() -> Vec<InventoryItem> {
+    vec![
+        InventoryItem::new("SKU-001", "Widget A", "Widgets", 150)
+            .with_unit_price(Decimal::new(1999, 2))
+            .with_reorder_threshold(50),
+        InventoryItem::new("SKU-002", "Widget B", "Widgets", 75)
+            .with_unit_price(Decimal::new(2499, 2))
+            .with_reorder_threshold(100),
+        InventoryItem::new("SKU-003", "Gadget X", "Gadgets", 200)
+            .with_unit_price(Decimal::new(4950, 2))
+            .with_reorder_threshold(25),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_flag_overrides_env() {
+        assert_eq!(Config::resolve(false, None).unwrap().mode, AppMode::Live);
+        assert_eq!(Config::resolve(false, Some("")).unwrap().mode, AppMode::Live);
+        assert_eq!(Config::resolve(false, Some("Mock")).unwrap().mode, AppMode::Mock);
+        assert_eq!(Config::resolve(true, Some("live")).unwrap().mode, AppMode::Mock);
+        assert!(Config::resolve(false, Some("staging")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_mode_connects_without_database() {
+        let backend = Config::new(AppMode::Mock).connect().await.unwrap();
+        assert_eq!(backend.mode(), AppMode::Mock);
+        assert_eq!(backend.inventory_items().await.unwrap().len(), 3);
+
+        let low: Vec<_> = backend.low_stock_items().await.unwrap().into_iter().map(|item| item.sku).collect();
+        assert_eq!(low, ["SKU-002"]);
+
+        assert!(backend.live_database().is_none());
+        let err = backend.database("Seeding").unwrap_err();
+        assert_eq!(err.to_string(), "Seeding needs the database and is not available in mock mode");
+        backend.close().await;
+    }
+}
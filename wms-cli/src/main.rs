@@ -1,11 +1,16 @@
 use color_eyre::eyre::Result;
-use clap::{Parser, Subcommand};
-use color_eyre::eyre;
-use tracing::{info, error};
+use clap::{ArgAction, Parser, Subcommand};
+use tracing::info;
 
 mod commands;
+mod config;
+mod map;
+mod output;
 
-use commands::{system, inventory, order};
+use commands::{system, inventory, order, plan, worker};
+use config::Config;
+use output::{resolve_timezone, Output};
+use wms_db::OrderSort;
 
 #[derive(Parser)]
 #[command(name = "wms-cli")]
@@ -13,6 +18,18 @@ use commands::{system, inventory, order};
 #[command(version = "0.1.0")]
 #[command(author = "WMS Development Team")]
 struct Cli {
+    /// Disable colored and emoji output (also honors NO_COLOR)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Time zone for displayed timestamps, e.g. Europe/Berlin (also honors WMS_TZ; defaults to UTC)
+    #[arg(long, global = true)]
+    timezone: Option<String>,
+
+    /// Use sample data and an in-memory order store instead of the database (also honors WMS_MODE=mock)
+    #[arg(long, global = true)]
+    mock: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -34,18 +51,35 @@ enum Commands {
         #[command(subcommand)]
         order_command: OrderCommands,
     },
+    /// Worker management commands
+    Worker {
+        #[command(subcommand)]
+        worker_command: WorkerCommands,
+    },
+    /// Plan task assignments for a scenario
+    Plan(plan::PlanCommand),
 }
 
 #[derive(Subcommand)]
 enum SystemCommands {
     /// Check system health including database connectivity
     Health,
+    /// Show the database URL (credentials masked) and connection pool usage
+    Pool,
+    /// Insert demo inventory, workers, tasks and orders (skipped if already present)
+    Seed {
+        /// Delete existing demo data first
+        #[arg(long)]
+        reset: bool,
+    },
 }
 
 #[derive(Subcommand)]
 enum InventoryCommands {
     /// List all inventory items
     List,
+    /// List items at or below their reorder threshold
+    LowStock,
 }
 
 #[derive(Subcommand)]
@@ -55,57 +89,160 @@ enum OrderCommands {
         /// Name of the item to order
         #[arg(short, long)]
         item: String,
+        /// SKU of the item (defaults to the item name)
+        #[arg(long)]
+        sku: Option<String>,
         /// Quantity to order
         #[arg(short, long)]
         quantity: u32,
+        /// Tag to attach to the order; repeat for several tags
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Initial status: pending, confirmed, shipped, delivered or cancelled (defaults to pending)
+        #[arg(long)]
+        status: Option<String>,
+    },
+    /// Show how much of an order has been picked
+    Status {
+        /// ID of the order
+        #[arg(long)]
+        id: String,
+    },
+    /// Show the price of each line and the order total
+    Total {
+        /// ID of the order
+        #[arg(long)]
+        id: String,
+    },
+    /// Print a confirmation receipt with the order's lines and total
+    Receipt {
+        /// ID of the order
+        #[arg(long)]
+        id: String,
+    },
+    /// Delete an order with its items and tags
+    Delete {
+        /// ID of the order
+        #[arg(long)]
+        id: String,
+        /// Confirm the deletion; nothing is deleted without it
+        #[arg(long)]
+        force: bool,
+    },
+    /// List unshipped orders past their SLA deadline
+    SlaBreaches,
+    /// List all orders
+    List {
+        /// Sort order: created-at-desc, created-at-asc, quantity-desc or status-asc
+        #[arg(long, default_value_t = OrderSort::default())]
+        sort: OrderSort,
+    },
+}
+
+#[derive(Subcommand)]
+enum WorkerCommands {
+    /// Mark a worker as available or unavailable for new tasks
+    SetAvailable {
+        /// ID of the worker
+        #[arg(long)]
+        id: i32,
+        /// Whether the worker can take tasks: true or false
+        #[arg(long, action = ArgAction::Set)]
+        available: bool,
     },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut out = Output::detect(cli.no_color);
+
     // Initialize color-eyre for better error reporting
-    color_eyre::install()?;
+    if out.is_color() {
+        color_eyre::install()?;
+    } else {
+        color_eyre::config::HookBuilder::new()
+            .theme(color_eyre::config::Theme::new())
+            .install()?;
+    }
 
     // Initialize logging
-    init_logging();
+    init_logging(&out);
 
     // Load environment variables
     dotenv::dotenv().ok();
 
-    let cli = Cli::parse();
+    // Stored timestamps are UTC; only their display follows the configured zone
+    let wms_tz = std::env::var("WMS_TZ").ok();
+    out = out.with_timezone(resolve_timezone(cli.timezone.as_deref(), wms_tz.as_deref())?);
+
+    // Every command takes its mock/live choice from here
+    let wms_mode = std::env::var("WMS_MODE").ok();
+    let config = Config::resolve(cli.mock, wms_mode.as_deref())?;
 
     match &cli.command {
         Commands::System { system_command } => {
             match system_command {
-                SystemCommands::Health => system::health().await,
+                SystemCommands::Health => system::health(&out, &config).await,
+                SystemCommands::Pool => system::pool(&out, &config).await,
+                SystemCommands::Seed { reset } => system::seed(&out, &config, *reset).await,
             }
         }
         Commands::Inventory { inventory_command } => {
-            match inventory_command {
-                InventoryCommands::List => inventory::list().await,
-            }
+            let backend = config.connect().await?;
+            let result = match inventory_command {
+                InventoryCommands::List => inventory::list(&out, &backend).await,
+                InventoryCommands::LowStock => inventory::low_stock(&out, &backend).await,
+            };
+            backend.close().await;
+            result
         }
         Commands::Order { order_command } => {
-            match order_command {
-                OrderCommands::Create { item, quantity } => {
-                    order::create(item.clone(), *quantity).await
+            let backend = config.connect().await?;
+            let repo = backend.orders();
+            let result = match order_command {
+                OrderCommands::Create { item, sku, quantity, tags, status } => {
+                    order::create(&out, repo, item.clone(), sku.clone(), *quantity, tags, status.as_deref()).await
+                }
+                OrderCommands::Status { id } => order::status(&out, repo, id).await,
+                OrderCommands::Total { id } => order::total(&out, repo, id).await,
+                OrderCommands::Receipt { id } => order::receipt(repo, id).await,
+                OrderCommands::Delete { id, force } => order::delete(&out, repo, id, *force).await,
+                OrderCommands::List { sort } => order::list(&out, repo, *sort).await,
+                OrderCommands::SlaBreaches => order::sla_breaches(&out, repo).await,
+            };
+            backend.close().await;
+            result
+        }
+        Commands::Worker { worker_command } => {
+            match worker_command {
+                WorkerCommands::SetAvailable { id, available } => {
+                    worker::set_available(&out, &config, *id, *available).await
                 }
             }
         }
+        Commands::Plan(command) => match (&command.command, &command.args) {
+            (Some(plan::PlanCommands::Analyze(args)), _) => plan::analyze(&out, args),
+            (Some(plan::PlanCommands::Validate(args)), _) => plan::validate(&out, args),
+            (Some(plan::PlanCommands::Compare(args)), _) => plan::compare(&out, args),
+            (Some(plan::PlanCommands::Schema(args)), _) => plan::schema(&out, args),
+            (None, Some(args)) => plan::run(&out, &config, args).await,
+            (None, None) => color_eyre::eyre::bail!("Pass --input with a scenario file, or a subcommand such as `plan analyze`"),
+        },
     }
 }
 
-fn init_logging() {
+fn init_logging(out: &Output) {
     // Set default log level if not specified
     if std::env::var("RUST_LOG").is_err() {
         std::env::set_var("RUST_LOG", "wms_cli=info,wms_db=info");
     }
 
-    // Initialize tracing subscriber with colored output
+    // Initialize tracing subscriber, colored only when output decoration is enabled
     tracing_subscriber::fmt()
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
         .with_target(false)
-        .with_ansi(true)
+        .with_ansi(out.is_color())
         .init();
 
     info!("WMS CLI initialized");
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use wms_planner::{Assignment, Location, Task, Worker, WorkerId};
+
+/// Characters of terminal width taken by each grid cell
+const CELL_WIDTH: usize = 3;
+
+/// Symbols used to link a worker with the tasks it serves
+const GROUP_SYMBOLS: &[u8] = b"123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Width used when the terminal width cannot be determined
+pub const DEFAULT_MAP_WIDTH: usize = 80;
+
+/// Terminal width from `COLUMNS`, falling back to `DEFAULT_MAP_WIDTH`
+pub fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAP_WIDTH)
+}
+
+/// Render workers and tasks on an ASCII grid scaled to `width` characters
+///
+/// Workers are drawn as `W` and tasks as `T`, each followed by a group symbol
+/// shared by a worker and the tasks assigned to it. Unassigned tasks use `?`.
+/// When markers fall in the same cell, workers win over tasks and the later
+/// task wins over earlier ones.
+pub fn render_map(tasks: &[Task], workers: &[Worker], assignments: &[Assignment], width: usize) -> String {
+    let symbols: HashMap<WorkerId, char> = workers
+        .iter()
+        .enumerate()
+        .map(|(i, w)| (w.id, GROUP_SYMBOLS.get(i).map_or('*', |&b| b as char)))
+        .collect();
+    let task_workers: HashMap<_, _> = assignments.iter().map(|a| (a.task_id, a.worker_id)).collect();
+
+    let cols = (width.saturating_sub(2) / CELL_WIDTH).max(2);
+    let rows = (cols / 2).clamp(5, 20);
+    let bounds = Bounds::new(
+        tasks
+            .iter()
+            .map(|t| &t.location)
+            .chain(workers.iter().map(|w| &w.location)),
+    );
+
+    let mut grid = vec![vec![None::<(char, char)>; cols]; rows];
+    for task in tasks {
+        let symbol = task_workers
+            .get(&task.id)
+            .and_then(|worker_id| symbols.get(worker_id))
+            .copied()
+            .unwrap_or('?');
+        let (row, col) = bounds.cell(&task.location, rows, cols);
+        if !matches!(grid[row][col], Some(('W', _))) {
+            grid[row][col] = Some(('T', symbol));
+        }
+    }
+    for worker in workers {
+        let (row, col) = bounds.cell(&worker.location, rows, cols);
+        grid[row][col] = Some(('W', symbols[&worker.id]));
+    }
+
+    let border = format!("+{}+", "-".repeat(cols * CELL_WIDTH));
+    let mut lines = vec![border.clone()];
+    for row in &grid {
+        let cells: String = row
+            .iter()
+            .map(|cell| match cell {
+                Some((kind, symbol)) => format!("{}{} ", kind, symbol),
+                None => " . ".to_string(),
+            })
+            .collect();
+        lines.push(format!("|{}|", cells));
+    }
+    lines.push(border);
+
+    for worker in workers {
+        let served: Vec<String> = assignments
+            .iter()
+            .filter(|a| a.worker_id == worker.id)
+            .map(|a| a.task_id.to_string())
+            .collect();
+        let served = if served.is_empty() { "-".to_string() } else { served.join(", ") };
+        lines.push(format!("{} = worker {} -> task(s) {}", symbols[&worker.id], worker.id, served));
+    }
+    if tasks.iter().any(|t| !task_workers.contains_key(&t.id)) {
+        lines.push("? = unassigned".to_string());
+    }
+
+    lines.join("\n")
+}
+
+/// Bounding box of all plotted locations
+struct Bounds {
+    min_x: f64,
+    max_x: f64,
+    min_y: f64,
+    max_y: f64,
+}
+
+impl Bounds {
+    fn new<'a>(locations: impl Iterator<Item = &'a Location>) -> Self {
+        let mut bounds = Self {
+            min_x: f64::INFINITY,
+            max_x: f64::NEG_INFINITY,
+            min_y: f64::INFINITY,
+            max_y: f64::NEG_INFINITY,
+        };
+        for loc in locations {
+            bounds.min_x = bounds.min_x.min(loc.x);
+            bounds.max_x = bounds.max_x.max(loc.x);
+            bounds.min_y = bounds.min_y.min(loc.y);
+            bounds.max_y = bounds.max_y.max(loc.y);
+        }
+        bounds
+    }
+
+    /// Grid cell for a location, with larger `y` values towards the top
+    fn cell(&self, loc: &Location, rows: usize, cols: usize) -> (usize, usize) {
+        let col = scale(loc.x, self.min_x, self.max_x, cols);
+        let row = rows - 1 - scale(loc.y, self.min_y, self.max_y, rows);
+        (row, col)
+    }
+}
+
+/// Map `value` from `[min, max]` onto `0..steps`
+fn scale(value: f64, min: f64, max: f64, steps: usize) -> usize {
+    let span = max - min;
+    if span <= 0.0 {
+        return 0;
+    }
+    (((value - min) / span) * (steps - 1) as f64).round() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wms_planner::Priority;
+
+    #[test]
+    fn test_map_contains_worker_and_task_markers() {
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High),
+            Task::new(2, Location::new(50.0, 20.0), Priority::Medium),
+            Task::new(3, Location::new(100.0, 40.0), Priority::Low),
+        ];
+        let workers = vec![
+            Worker::new(7, Location::new(10.0, 0.0), true),
+            Worker::new(8, Location::new(90.0, 40.0), true),
+        ];
+        let assignments = vec![Assignment::new(1, 7, 10.0), Assignment::new(3, 8, 10.0)];
+
+        let map = render_map(&tasks, &workers, &assignments, 80);
+        assert_eq!(map.matches('W').count(), 2);
+        assert_eq!(map.matches('T').count(), 3);
+        assert!(map.contains("W1") && map.contains("T1"));
+        assert!(map.contains("W2") && map.contains("T2"));
+        assert!(map.contains("T?"));
+        assert!(map.lines().all(|line| line.chars().count() <= 80));
+    }
+
+    #[test]
+    fn test_single_point_does_not_panic() {
+        let tasks = vec![Task::new(1, Location::new(5.0, 5.0), Priority::High)];
+        let map = render_map(&tasks, &[], &[], 10);
+        assert_eq!(map.matches('T').count(), 1);
+    }
+}
@@ -0,0 +1,166 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use color_eyre::eyre::{eyre, Result};
+use std::io::IsTerminal;
+
+/// Kind of message being rendered, which decides its emoji and color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Success,
+    Failure,
+    Info,
+    Hint,
+    Pending,
+    Package,
+    Note,
+    Celebrate,
+}
+
+impl Style {
+    fn emoji(&self) -> &'static str {
+        match self {
+            Style::Success => "✅",
+            Style::Failure => "❌",
+            Style::Info => "📋",
+            Style::Hint => "💡",
+            Style::Pending => "🚧",
+            Style::Package => "📦",
+            Style::Note => "📝",
+            Style::Celebrate => "🎉",
+        }
+    }
+
+    fn ansi_code(&self) -> &'static str {
+        match self {
+            Style::Success => "32",
+            Style::Failure => "31",
+            Style::Info => "36",
+            Style::Hint => "33",
+            Style::Pending => "35",
+            Style::Package | Style::Note => "34",
+            Style::Celebrate => "32",
+        }
+    }
+}
+
+/// Shared output helper deciding whether decorative formatting is enabled
+/// and which time zone timestamps are shown in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Output {
+    color: bool,
+    timezone: Tz,
+}
+
+impl Output {
+    /// Create an output helper with decoration explicitly enabled or disabled
+    pub fn new(color: bool) -> Self {
+        Self { color, timezone: Tz::UTC }
+    }
+
+    /// Render timestamps in `timezone` instead of UTC
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Detect decoration from the `--no-color` flag, `NO_COLOR` and whether stdout is a tty
+    pub fn detect(no_color_flag: bool) -> Self {
+        let no_color_env = std::env::var_os("NO_COLOR").map(|v| v.to_string_lossy().into_owned());
+        Self::new(should_colorize(
+            no_color_flag,
+            no_color_env.as_deref(),
+            std::io::stdout().is_terminal(),
+        ))
+    }
+
+    /// Whether emoji and ANSI codes are emitted
+    pub fn is_color(&self) -> bool {
+        self.color
+    }
+
+    /// Format a stored UTC timestamp in the configured time zone
+    pub fn timestamp(&self, at: &DateTime<Utc>) -> String {
+        at.with_timezone(&self.timezone).format("%Y-%m-%d %H:%M:%S %Z").to_string()
+    }
+
+    /// Render a message in the given style
+    pub fn render(&self, style: Style, message: &str) -> String {
+        if self.color {
+            format!("\x1b[{}m{} {}\x1b[0m", style.ansi_code(), style.emoji(), message)
+        } else {
+            message.to_string()
+        }
+    }
+}
+
+/// Decide whether to colorize output
+///
+/// Follows https://no-color.org: any non-empty `NO_COLOR` value disables color.
+pub fn should_colorize(no_color_flag: bool, no_color_env: Option<&str>, is_tty: bool) -> bool {
+    if no_color_flag {
+        return false;
+    }
+    if no_color_env.is_some_and(|v| !v.is_empty()) {
+        return false;
+    }
+    is_tty
+}
+
+/// Pick the display time zone from `--timezone`, falling back to `WMS_TZ`, then UTC
+///
+/// Names are IANA zone names such as `Europe/Berlin`.
+pub fn resolve_timezone(flag: Option<&str>, env: Option<&str>) -> Result<Tz> {
+    match flag.or(env.filter(|v| !v.is_empty())) {
+        Some(name) => name
+            .parse()
+            .map_err(|_| eyre!("Unknown time zone '{}'; expected an IANA name such as Europe/Berlin", name)),
+        None => Ok(Tz::UTC),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_color_env_suppresses_ansi() {
+        let output = Output::new(should_colorize(false, Some("1"), true));
+        let rendered = output.render(Style::Success, "Database health check passed");
+        assert!(!rendered.contains('\x1b'));
+        assert_eq!(rendered, "Database health check passed");
+    }
+
+    #[test]
+    fn test_color_enabled_on_tty() {
+        let output = Output::new(should_colorize(false, None, true));
+        let rendered = output.render(Style::Failure, "boom");
+        assert!(rendered.contains("\x1b[31m"));
+        assert!(rendered.contains("❌"));
+    }
+
+    #[test]
+    fn test_flag_and_non_tty_disable_color() {
+        assert!(!should_colorize(true, None, true));
+        assert!(!should_colorize(false, None, false));
+        // An empty NO_COLOR is treated as unset
+        assert!(should_colorize(false, Some(""), true));
+    }
+
+    #[test]
+    fn test_timestamp_converted_to_named_zone() {
+        let at = DateTime::parse_from_rfc3339("2024-01-15T12:30:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(Output::new(false).timestamp(&at), "2024-01-15 12:30:00 UTC");
+
+        let new_york = resolve_timezone(Some("America/New_York"), None).unwrap();
+        assert_eq!(Output::new(false).with_timezone(new_york).timestamp(&at), "2024-01-15 07:30:00 EST");
+    }
+
+    #[test]
+    fn test_timezone_flag_overrides_env() {
+        assert_eq!(resolve_timezone(None, None).unwrap(), Tz::UTC);
+        assert_eq!(resolve_timezone(None, Some("")).unwrap(), Tz::UTC);
+        assert_eq!(resolve_timezone(None, Some("Asia/Tokyo")).unwrap(), Tz::Asia__Tokyo);
+        assert_eq!(resolve_timezone(Some("Europe/Berlin"), Some("Asia/Tokyo")).unwrap(), Tz::Europe__Berlin);
+        assert!(resolve_timezone(Some("Mars/Olympus"), None).is_err());
+    }
+}
@@ -0,0 +1,6 @@
+-- Per-day counters for date-prefixed order IDs
+
+CREATE TABLE IF NOT EXISTS order_id_sequences (
+    day DATE PRIMARY KEY,
+    last_value INTEGER NOT NULL
+);
@@ -0,0 +1,12 @@
+-- Workers and their last known positions
+
+CREATE TABLE IF NOT EXISTS workers (
+    id INTEGER PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    is_available BOOLEAN NOT NULL DEFAULT TRUE,
+    current_load DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (current_load >= 0 AND current_load <= 1),
+    max_tasks INTEGER NOT NULL DEFAULT 1 CHECK (max_tasks > 0),
+    location_x DOUBLE PRECISION,
+    location_y DOUBLE PRECISION,
+    CHECK ((location_x IS NULL) = (location_y IS NULL))
+);
@@ -0,0 +1,23 @@
+-- Inventory items constrained to a known set of categories
+
+CREATE TABLE IF NOT EXISTS categories (
+    name VARCHAR(50) PRIMARY KEY
+);
+
+INSERT INTO categories (name) VALUES
+    ('Electronics'),
+    ('Apparel'),
+    ('Food'),
+    ('Hardware'),
+    ('Furniture')
+ON CONFLICT DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS inventory_items (
+    sku VARCHAR(64) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    category VARCHAR(50) NOT NULL REFERENCES categories(name),
+    quantity INTEGER NOT NULL DEFAULT 0 CHECK (quantity >= 0),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_inventory_items_category ON inventory_items(category);
@@ -0,0 +1,8 @@
+-- Unit prices on inventory, and prices captured on order lines at creation time
+
+ALTER TABLE inventory_items
+    ADD COLUMN IF NOT EXISTS unit_price NUMERIC NOT NULL DEFAULT 0 CHECK (unit_price >= 0);
+
+ALTER TABLE order_items
+    ADD COLUMN IF NOT EXISTS unit_price NUMERIC NOT NULL DEFAULT 0 CHECK (unit_price >= 0),
+    ADD COLUMN IF NOT EXISTS total_price NUMERIC NOT NULL DEFAULT 0 CHECK (total_price >= 0);
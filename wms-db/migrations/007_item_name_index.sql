@@ -0,0 +1,3 @@
+-- Case-insensitive item name lookups
+
+CREATE INDEX IF NOT EXISTS idx_inventory_items_name_normalized ON inventory_items (LOWER(TRIM(name)));
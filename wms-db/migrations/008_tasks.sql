@@ -0,0 +1,10 @@
+-- Warehouse tasks available for planning
+
+CREATE TABLE IF NOT EXISTS tasks (
+    id INTEGER PRIMARY KEY,
+    location_x DOUBLE PRECISION NOT NULL,
+    location_y DOUBLE PRECISION NOT NULL,
+    priority VARCHAR(16) NOT NULL DEFAULT 'medium'
+        CHECK (priority IN ('low', 'medium', 'high', 'critical')),
+    estimated_duration DOUBLE PRECISION CHECK (estimated_duration > 0)
+);
@@ -0,0 +1,6 @@
+-- Stock level at or below which an item should be reordered
+
+ALTER TABLE inventory_items
+    ADD COLUMN IF NOT EXISTS reorder_threshold INTEGER NOT NULL DEFAULT 0 CHECK (reorder_threshold >= 0);
+
+CREATE INDEX IF NOT EXISTS idx_inventory_items_low_stock ON inventory_items (sku) WHERE quantity <= reorder_threshold;
@@ -0,0 +1,9 @@
+-- Free-form labels on orders, e.g. "fragile" or "gift"
+
+CREATE TABLE IF NOT EXISTS order_tags (
+    order_id VARCHAR(32) NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    tag VARCHAR(64) NOT NULL CHECK (tag <> ''),
+    PRIMARY KEY (order_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_order_tags_tag ON order_tags(tag);
@@ -0,0 +1,13 @@
+-- Actual durations of completed assignments, for learning per-worker estimates
+
+CREATE TABLE IF NOT EXISTS assignment_completions (
+    id SERIAL PRIMARY KEY,
+    task_id INTEGER NOT NULL,
+    worker_id INTEGER NOT NULL,
+    skill VARCHAR(64),
+    estimated_duration DOUBLE PRECISION NOT NULL CHECK (estimated_duration > 0),
+    actual_duration DOUBLE PRECISION NOT NULL CHECK (actual_duration > 0),
+    completed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_assignment_completions_worker ON assignment_completions(worker_id, skill);
@@ -0,0 +1,3 @@
+-- Client-chosen keys so a retried order creation never inserts twice
+
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS idempotency_key VARCHAR(64) UNIQUE;
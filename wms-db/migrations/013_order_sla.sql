@@ -0,0 +1,5 @@
+-- Deadline by which an order must ship, if it has an SLA
+
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS sla_deadline TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX IF NOT EXISTS idx_orders_sla_deadline ON orders(sla_deadline) WHERE sla_deadline IS NOT NULL;
@@ -0,0 +1,3 @@
+-- Short-lived claims on workers, so concurrent planners don't double-book them
+
+ALTER TABLE workers ADD COLUMN IF NOT EXISTS reserved_until TIMESTAMP WITH TIME ZONE;
@@ -0,0 +1,3 @@
+-- Per-worker travel speed in units per minute, NULL for the planner's default
+
+ALTER TABLE workers ADD COLUMN IF NOT EXISTS travel_speed DOUBLE PRECISION CHECK (travel_speed > 0);
@@ -0,0 +1,10 @@
+-- Units of measure: items stocked in one unit and also sold by the case or pallet
+
+ALTER TABLE inventory_items
+    ADD COLUMN IF NOT EXISTS unit VARCHAR(16) NOT NULL DEFAULT 'each' CHECK (unit IN ('each', 'case', 'pallet')),
+    ADD COLUMN IF NOT EXISTS case_quantity INTEGER CHECK (case_quantity > 0),
+    ADD COLUMN IF NOT EXISTS pallet_quantity INTEGER CHECK (pallet_quantity > 0);
+
+ALTER TABLE order_items
+    ADD COLUMN IF NOT EXISTS unit VARCHAR(16) NOT NULL DEFAULT 'each' CHECK (unit IN ('each', 'case', 'pallet')),
+    ADD COLUMN IF NOT EXISTS conversion_factor INTEGER NOT NULL DEFAULT 1 CHECK (conversion_factor > 0);
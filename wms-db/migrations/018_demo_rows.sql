@@ -0,0 +1,7 @@
+-- Mark rows inserted by the demo seed, so a reset deletes only those
+--
+-- Rows seeded before this column existed are left unflagged rather than
+-- guessed at; remove them by hand if they are no longer wanted.
+
+ALTER TABLE workers ADD COLUMN IF NOT EXISTS is_demo BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS is_demo BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,70 @@
+use color_eyre::eyre::{Context, Result};
+
+use crate::models::{CompletedAssignment, CompletionRecord};
+use crate::Database;
+
+impl Database {
+    /// Store how long a finished assignment actually took, in minutes
+    pub async fn record_completion(&self, assignment: &CompletedAssignment, actual_duration: f64) -> Result<()> {
+        self.timed("record_completion", async {
+            if !(actual_duration.is_finite() && actual_duration > 0.0) {
+                color_eyre::eyre::bail!("Actual duration must be a positive number of minutes, got {}", actual_duration);
+            }
+            if !(assignment.estimated_duration.is_finite() && assignment.estimated_duration > 0.0) {
+                color_eyre::eyre::bail!(
+                    "Estimated duration must be a positive number of minutes, got {}",
+                    assignment.estimated_duration
+                );
+            }
+
+            sqlx::query(
+                "INSERT INTO assignment_completions (task_id, worker_id, skill, estimated_duration, actual_duration) \
+                 VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(assignment.task_id)
+            .bind(assignment.worker_id)
+            .bind(&assignment.skill)
+            .bind(assignment.estimated_duration)
+            .bind(actual_duration)
+            .execute(&mut *self.acquire().await?)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to record completion of task {} by worker {}",
+                    assignment.task_id, assignment.worker_id
+                )
+            })?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Completions recorded for a worker, oldest first
+    pub async fn list_completions(&self, worker_id: i32) -> Result<Vec<CompletionRecord>> {
+        self.timed("list_completions", async {
+            sqlx::query_as::<_, CompletionRecord>(
+                "SELECT task_id, worker_id, skill, estimated_duration, actual_duration, completed_at \
+                 FROM assignment_completions WHERE worker_id = $1 ORDER BY completed_at, id",
+            )
+            .bind(worker_id)
+            .fetch_all(&mut *self.acquire().await?)
+            .await
+            .context("Failed to list completions")
+        })
+        .await
+    }
+
+    /// Every recorded completion, oldest first, for loading into a learned estimator
+    pub async fn completion_history(&self) -> Result<Vec<CompletionRecord>> {
+        self.timed("completion_history", async {
+            sqlx::query_as::<_, CompletionRecord>(
+                "SELECT task_id, worker_id, skill, estimated_duration, actual_duration, completed_at \
+                 FROM assignment_completions ORDER BY completed_at, id",
+            )
+            .fetch_all(&mut *self.acquire().await?)
+            .await
+            .context("Failed to load completion history")
+        })
+        .await
+    }
+}
@@ -0,0 +1,92 @@
+use thiserror::Error;
+
+use crate::models::{OrderStatus, UnitOfMeasure};
+
+/// Postgres SQLSTATE for foreign key violations
+pub(crate) const FOREIGN_KEY_VIOLATION: &str = "23503";
+
+/// Typed errors returned by database operations
+///
+/// Methods still return `color_eyre::eyre::Result`; callers that need to react
+/// to a specific failure can `downcast_ref::<WmsDbError>()` the report.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WmsDbError {
+    #[error("SKU '{sku}' appears more than once in the order")]
+    DuplicateLineItem { sku: String },
+
+    #[error("Unknown category '{category}' (allowed: {})", allowed.join(", "))]
+    UnknownCategory { category: String, allowed: Vec<String> },
+
+    #[error("Backdated order creation is disabled (set DB_ALLOW_BACKDATE=true for backfills)")]
+    BackdateNotAllowed,
+
+    #[error("Order {order_id} cannot move from {from} to {to}")]
+    IllegalStatusTransition { order_id: String, from: OrderStatus, to: OrderStatus },
+
+    #[error("Worker {worker_id} not found")]
+    WorkerNotFound { worker_id: i32 },
+
+    #[error("Inventory item '{sku}' not found")]
+    InventoryItemNotFound { sku: String },
+
+    #[error("Cannot reserve {requested} of '{sku}': only {available} available")]
+    InsufficientStock { sku: String, requested: i32, available: i32 },
+
+    #[error("Item '{sku}' is not sold by the {unit}")]
+    UnitNotSellable { sku: String, unit: UnitOfMeasure },
+
+    #[error("Database overloaded: {max_pending_acquires} callers already waiting for a connection")]
+    Overloaded { max_pending_acquires: usize },
+
+    #[error("No database connection became available after {attempts} attempt(s)")]
+    PoolExhausted { attempts: u32 },
+}
+
+/// A migration run that stopped at a failing migration
+///
+/// Each migration commits in its own transaction, so every version up to
+/// `last_applied` is in place and nothing from `failed_version` on has been
+/// applied. Fix the failing migration and run `migrate()` again to resume.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Migration {failed_version} failed ({}): {reason}", describe_applied(.last_applied))]
+pub struct MigrationError {
+    /// Highest version that is applied, `None` if none are
+    pub last_applied: Option<i64>,
+    pub failed_version: i64,
+    /// Why the migration failed, as reported by the database
+    pub reason: String,
+}
+
+fn describe_applied(last_applied: &Option<i64>) -> String {
+    match last_applied {
+        Some(version) => format!("migrations up to {} are applied", version),
+        None => "no migrations are applied".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_category_lists_allowed() {
+        let err = WmsDbError::UnknownCategory {
+            category: "electronics".to_string(),
+            allowed: vec!["Apparel".to_string(), "Electronics".to_string()],
+        };
+        assert_eq!(
+            err.to_string(),
+            "Unknown category 'electronics' (allowed: Apparel, Electronics)"
+        );
+    }
+
+    #[test]
+    fn test_migration_error_names_versions() {
+        let err = MigrationError {
+            last_applied: Some(4),
+            failed_version: 5,
+            reason: "syntax error".to_string(),
+        };
+        assert_eq!(err.to_string(), "Migration 5 failed (migrations up to 4 are applied): syntax error");
+    }
+}
@@ -0,0 +1,66 @@
+//! Domain events published for downstream integrations
+
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::sync::Mutex;
+
+use crate::models::{Order, OrderItem};
+
+/// A new order was stored
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderCreated {
+    pub order_id: String,
+    pub items: Vec<OrderItem>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OrderCreated {
+    pub(crate) fn for_order(order: &Order) -> Self {
+        Self {
+            order_id: order.id.clone(),
+            items: order.items.clone(),
+            created_at: order.created_at,
+        }
+    }
+}
+
+/// Receiver for domain events, e.g. a Kafka producer or webhook dispatcher
+///
+/// Events are published after the change has committed, so a sink never sees
+/// an order that was rolled back. Publishing is synchronous and cannot fail the
+/// operation; a sink that talks to the network should hand events off to its
+/// own task or queue rather than block.
+pub trait EventSink: fmt::Debug + Send + Sync {
+    fn order_created(&self, event: &OrderCreated);
+}
+
+/// Sink that drops every event, used when none is configured
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopEventSink;
+
+impl EventSink for NoopEventSink {
+    fn order_created(&self, _event: &OrderCreated) {}
+}
+
+/// Sink that keeps events in memory, for tests
+#[derive(Debug, Default)]
+pub struct VecEventSink {
+    events: Mutex<Vec<OrderCreated>>,
+}
+
+impl VecEventSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of the events received so far, oldest first
+    pub fn events(&self) -> Vec<OrderCreated> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+impl EventSink for VecEventSink {
+    fn order_created(&self, event: &OrderCreated) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
@@ -0,0 +1,29 @@
+//! Hooks around database queries for metrics and tracing
+
+use color_eyre::eyre::Report;
+use std::fmt;
+use std::time::Duration;
+
+/// Observer called around every query method of `Database`
+///
+/// `label` names the operation, and is the same label slow-query warnings use.
+/// It is the method name, e.g. `"update_picked"`, except that all order
+/// creation methods, including `create_order_idempotent`, report
+/// `"create_order"`, listing orders reports `"list_orders"` and both
+/// soft-reserve methods report `"soft_reserve"`. Each call reports exactly
+/// one label, even when the method runs several queries. Both hooks run on
+/// the calling task, so they should be cheap: record a counter or histogram
+/// sample and return.
+pub trait QueryInterceptor: fmt::Debug + Send + Sync {
+    /// Called just before the query starts
+    fn before(&self, _label: &str) {}
+
+    /// Called once the query finishes, with how long it took and whether it failed
+    fn after(&self, _label: &str, _duration: Duration, _result: Result<(), &Report>) {}
+}
+
+/// Interceptor that does nothing, used when none is configured
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopQueryInterceptor;
+
+impl QueryInterceptor for NoopQueryInterceptor {}
@@ -0,0 +1,271 @@
+use color_eyre::eyre::{Context, Result};
+use std::time::Duration;
+use tracing::info;
+
+use crate::error::{WmsDbError, FOREIGN_KEY_VIOLATION};
+use crate::models::{normalize_item_name, InventoryItem, UnitOfMeasure};
+use crate::Database;
+
+impl Database {
+    /// List the categories inventory items may use, alphabetically
+    pub async fn list_categories(&self) -> Result<Vec<String>> {
+        self.timed("list_categories", self.fetch_categories()).await
+    }
+
+    async fn fetch_categories(&self) -> Result<Vec<String>> {
+        sqlx::query_scalar("SELECT name FROM categories ORDER BY name")
+            .fetch_all(&mut *self.acquire().await?)
+            .await
+            .context("Failed to list categories")
+    }
+
+    /// Add a new inventory item
+    ///
+    /// The name is normalized first when `normalize_item_names` is configured.
+    /// Fails with `WmsDbError::UnknownCategory` if the category is not in the
+    /// `categories` table.
+    pub async fn create_inventory_item(&self, item: &InventoryItem) -> Result<InventoryItem> {
+        self.timed("create_inventory_item", async {
+            let name = if self.normalize_item_names {
+                normalize_item_name(&item.name)
+            } else {
+                item.name.clone()
+            };
+            let result = sqlx::query_as::<_, InventoryItem>(
+                "INSERT INTO inventory_items (sku, name, category, quantity, unit_price, reorder_threshold, unit, case_quantity, pallet_quantity) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
+                 RETURNING sku, name, category, quantity, unit_price, reorder_threshold, unit, case_quantity, pallet_quantity",
+            )
+            .bind(&item.sku)
+            .bind(&name)
+            .bind(&item.category)
+            .bind(item.quantity)
+            .bind(item.unit_price)
+            .bind(item.reorder_threshold)
+            .bind(item.unit)
+            .bind(item.case_quantity)
+            .bind(item.pallet_quantity)
+            .fetch_one(&mut *self.acquire().await?)
+            .await;
+
+            match result {
+                Ok(item) => Ok(item),
+                Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some(FOREIGN_KEY_VIOLATION) => {
+                    Err(WmsDbError::UnknownCategory {
+                        category: item.category.clone(),
+                        allowed: self.fetch_categories().await?,
+                    }
+                    .into())
+                }
+                Err(e) => Err(e).with_context(|| format!("Failed to insert inventory item '{}'", item.sku)),
+            }
+        })
+        .await
+    }
+
+    /// Fetch an inventory item by SKU
+    pub async fn get_inventory_item(&self, sku: &str) -> Result<Option<InventoryItem>> {
+        self.timed("get_inventory_item", async {
+            sqlx::query_as::<_, InventoryItem>(
+                "SELECT sku, name, category, quantity, unit_price, reorder_threshold, unit, case_quantity, pallet_quantity FROM inventory_items WHERE sku = $1",
+            )
+            .bind(sku)
+            .fetch_optional(&mut *self.acquire().await?)
+            .await
+            .context("Failed to fetch inventory item")
+        })
+        .await
+    }
+
+    /// Find inventory items by name, ignoring case and surrounding whitespace
+    pub async fn find_inventory_items_by_name(&self, name: &str) -> Result<Vec<InventoryItem>> {
+        self.timed("find_inventory_items_by_name", async {
+            sqlx::query_as::<_, InventoryItem>(
+                "SELECT sku, name, category, quantity, unit_price, reorder_threshold, unit, case_quantity, pallet_quantity FROM inventory_items \
+                 WHERE LOWER(TRIM(name)) = $1 ORDER BY sku",
+            )
+            .bind(normalize_item_name(name))
+            .fetch_all(&mut *self.acquire().await?)
+            .await
+            .context("Failed to find inventory items by name")
+        })
+        .await
+    }
+
+    /// List all inventory items ordered by SKU
+    pub async fn list_inventory_items(&self) -> Result<Vec<InventoryItem>> {
+        self.timed("list_inventory_items", async {
+            sqlx::query_as::<_, InventoryItem>(
+                "SELECT sku, name, category, quantity, unit_price, reorder_threshold, unit, case_quantity, pallet_quantity FROM inventory_items ORDER BY sku",
+            )
+            .fetch_all(&mut *self.acquire().await?)
+            .await
+            .context("Failed to list inventory items")
+        })
+        .await
+    }
+
+    /// List items whose quantity is at or below their reorder threshold, ordered by SKU
+    pub async fn low_stock_items(&self) -> Result<Vec<InventoryItem>> {
+        self.timed("low_stock_items", async {
+            sqlx::query_as::<_, InventoryItem>(
+                "SELECT sku, name, category, quantity, unit_price, reorder_threshold, unit, case_quantity, pallet_quantity FROM inventory_items \
+                 WHERE quantity <= reorder_threshold ORDER BY sku",
+            )
+            .fetch_all(&mut *self.acquire().await?)
+            .await
+            .context("Failed to list low-stock inventory items")
+        })
+        .await
+    }
+
+    /// Quantity on hand minus what unexpired soft reservations hold, `None` for an unknown SKU
+    pub async fn available_quantity(&self, sku: &str) -> Result<Option<i32>> {
+        self.timed("available_quantity", async {
+            sqlx::query_scalar(
+                "SELECT (i.quantity - COALESCE((SELECT SUM(r.quantity) FROM stock_reservations r \
+                 WHERE r.sku = i.sku AND r.expires_at > NOW()), 0))::INTEGER \
+                 FROM inventory_items i WHERE i.sku = $1",
+            )
+            .bind(sku)
+            .fetch_optional(&mut *self.acquire().await?)
+            .await
+            .with_context(|| format!("Failed to compute available quantity of '{}'", sku))
+        })
+        .await
+    }
+
+    /// Hold `quantity` units of `sku` for `ttl`, e.g. while a picker fills a cart
+    ///
+    /// `quantity` is counted in the item's stock unit; use
+    /// [`soft_reserve_in`](Self::soft_reserve_in) to reserve cases or pallets.
+    /// Returns the reservation ID to pass to
+    /// [`release_reservation`](Self::release_reservation) once the pick completes or is
+    /// abandoned. Held units count against [`available_quantity`](Self::available_quantity)
+    /// until then or until the TTL passes. Fails with `WmsDbError::InventoryItemNotFound`
+    /// for an unknown SKU and `WmsDbError::InsufficientStock` if fewer units are available.
+    /// A `quantity` that isn't positive is rejected before the item is locked.
+    pub async fn soft_reserve(&self, sku: &str, quantity: i32, ttl: Duration) -> Result<i64> {
+        self.reserve_stock(sku, quantity, None, ttl).await
+    }
+
+    /// Hold `quantity` of `sku` counted in `unit`, e.g. 2 cases
+    ///
+    /// The hold is converted to stock units with the item's
+    /// [`conversion_factor`](InventoryItem::conversion_factor), so 2 cases of 12
+    /// reserve 24 eaches, and `WmsDbError::InsufficientStock` reports stock
+    /// units too. Fails with `WmsDbError::UnitNotSellable` if the item is not
+    /// sold in `unit`. Otherwise behaves like [`soft_reserve`](Self::soft_reserve).
+    pub async fn soft_reserve_in(&self, sku: &str, quantity: i32, unit: UnitOfMeasure, ttl: Duration) -> Result<i64> {
+        self.reserve_stock(sku, quantity, Some(unit), ttl).await
+    }
+
+    async fn reserve_stock(&self, sku: &str, quantity: i32, unit: Option<UnitOfMeasure>, ttl: Duration) -> Result<i64> {
+        self.timed("soft_reserve", async {
+            if quantity <= 0 {
+                color_eyre::eyre::bail!("Quantity to reserve of '{}' must be greater than 0", sku);
+            }
+            let mut tx = self.begin().await?;
+            let item = sqlx::query_as::<_, InventoryItem>(
+                "SELECT sku, name, category, quantity, unit_price, reorder_threshold, unit, case_quantity, pallet_quantity \
+                 FROM inventory_items WHERE sku = $1 FOR UPDATE",
+            )
+            .bind(sku)
+            .fetch_optional(&mut tx)
+            .await
+            .with_context(|| format!("Failed to lock inventory item '{}'", sku))?;
+            let Some(item) = item else {
+                return Err(WmsDbError::InventoryItemNotFound { sku: sku.to_string() }.into());
+            };
+            let quantity = match unit {
+                None => quantity,
+                Some(unit) => {
+                    let factor = item
+                        .conversion_factor(unit)
+                        .ok_or_else(|| WmsDbError::UnitNotSellable { sku: sku.to_string(), unit })?;
+                    quantity
+                        .checked_mul(factor)
+                        .ok_or_else(|| color_eyre::eyre::eyre!("Cannot reserve {} {} of '{}': too many units", quantity, unit, sku))?
+                }
+            };
+
+            // Lapsed holds on this SKU go first, so they never block a new one
+            let available: i32 = sqlx::query_scalar(
+                "WITH expired AS (DELETE FROM stock_reservations WHERE sku = $1 AND expires_at <= NOW() RETURNING quantity) \
+                 UPDATE inventory_items SET reserved_quantity = reserved_quantity - COALESCE((SELECT SUM(quantity) FROM expired), 0) \
+                 WHERE sku = $1 RETURNING quantity - reserved_quantity",
+            )
+            .bind(sku)
+            .fetch_one(&mut tx)
+            .await
+            .with_context(|| format!("Failed to release expired reservations of '{}'", sku))?;
+            if quantity > available {
+                tx.rollback().await.context("Failed to roll back reservation")?;
+                return Err(WmsDbError::InsufficientStock { sku: sku.to_string(), requested: quantity, available }.into());
+            }
+
+            let id: i64 = sqlx::query_scalar(
+                "INSERT INTO stock_reservations (sku, quantity, expires_at) \
+                 VALUES ($1, $2, NOW() + make_interval(secs => $3)) RETURNING id",
+            )
+            .bind(sku)
+            .bind(quantity)
+            .bind(ttl.as_secs_f64())
+            .fetch_one(&mut tx)
+            .await
+            .with_context(|| format!("Failed to reserve {} of '{}'", quantity, sku))?;
+            sqlx::query("UPDATE inventory_items SET reserved_quantity = reserved_quantity + $2 WHERE sku = $1")
+                .bind(sku)
+                .bind(quantity)
+                .execute(&mut tx)
+                .await
+                .with_context(|| format!("Failed to reserve {} of '{}'", quantity, sku))?;
+            tx.commit().await.context("Failed to commit reservation")?;
+            Ok(id)
+        })
+        .await
+    }
+
+    /// Drop a soft reservation before it expires
+    ///
+    /// Returns `false` if no reservation has this ID, including one already expired and released.
+    pub async fn release_reservation(&self, reservation_id: i64) -> Result<bool> {
+        self.timed("release_reservation", async {
+            let result = sqlx::query(
+                "WITH released AS (DELETE FROM stock_reservations WHERE id = $1 RETURNING sku, quantity) \
+                 UPDATE inventory_items i SET reserved_quantity = i.reserved_quantity - r.quantity \
+                 FROM released r WHERE i.sku = r.sku",
+            )
+            .bind(reservation_id)
+            .execute(&mut *self.acquire().await?)
+            .await
+            .with_context(|| format!("Failed to release reservation {}", reservation_id))?;
+            Ok(result.rows_affected() > 0)
+        })
+        .await
+    }
+
+    /// Release every soft reservation past its TTL, returning how many were released
+    ///
+    /// Expired reservations already stop counting against availability; call this
+    /// periodically so `reserved_quantity` catches up and the rows are cleaned out.
+    pub async fn release_expired_reservations(&self) -> Result<u64> {
+        self.timed("release_expired_reservations", async {
+            let released: i64 = sqlx::query_scalar(
+                "WITH expired AS (DELETE FROM stock_reservations WHERE expires_at <= NOW() RETURNING sku, quantity), \
+                 totals AS (SELECT sku, SUM(quantity) AS quantity, COUNT(*) AS reservations FROM expired GROUP BY sku), \
+                 updated AS (UPDATE inventory_items i SET reserved_quantity = i.reserved_quantity - t.quantity \
+                             FROM totals t WHERE i.sku = t.sku RETURNING t.reservations) \
+                 SELECT COALESCE(SUM(reservations), 0)::BIGINT FROM updated",
+            )
+            .fetch_one(&mut *self.acquire().await?)
+            .await
+            .context("Failed to release expired reservations")?;
+            if released > 0 {
+                info!("Released {} expired stock reservation(s)", released);
+            }
+            Ok(released as u64)
+        })
+        .await
+    }
+}
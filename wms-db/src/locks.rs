@@ -0,0 +1,67 @@
+use color_eyre::eyre::{Context, Result};
+use sqlx::Connection;
+
+use crate::Database;
+
+impl Database {
+    /// Try to take the session-level advisory lock `key` without waiting
+    ///
+    /// Returns `false` if another session holds it, including another
+    /// `Database` in this or any other process. Postgres ties advisory locks to
+    /// a connection, so the connection that took the lock is kept out of the
+    /// pool until [`release_advisory_lock`](Self::release_advisory_lock) or
+    /// [`close`](Self::close). Locks are not re-entrant: asking again for a key
+    /// this `Database` already holds returns `false`.
+    pub async fn try_advisory_lock(&self, key: i64) -> Result<bool> {
+        self.timed("try_advisory_lock", async {
+            if self.advisory_locks.lock().unwrap().contains_key(&key) {
+                return Ok(false);
+            }
+
+            let mut conn = self.acquire().await?;
+            let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+                .bind(key)
+                .fetch_one(&mut *conn)
+                .await
+                .with_context(|| format!("Failed to take advisory lock {}", key))?;
+            if locked {
+                self.advisory_locks.lock().unwrap().insert(key, conn);
+            }
+            Ok(locked)
+        })
+        .await
+    }
+
+    /// Release an advisory lock taken with [`try_advisory_lock`](Self::try_advisory_lock)
+    ///
+    /// Returns `false` if this `Database` does not hold `key`.
+    pub async fn release_advisory_lock(&self, key: i64) -> Result<bool> {
+        self.timed("release_advisory_lock", async {
+            let Some(mut conn) = self.advisory_locks.lock().unwrap().remove(&key) else {
+                return Ok(false);
+            };
+
+            let released = sqlx::query_scalar("SELECT pg_advisory_unlock($1)")
+                .bind(key)
+                .fetch_one(&mut *conn)
+                .await;
+            match released {
+                Ok(released) => Ok(released),
+                Err(err) => {
+                    // Closing the session is the only other way to be sure the lock is gone
+                    let _ = conn.detach().close().await;
+                    Err(err).with_context(|| format!("Failed to release advisory lock {}", key))
+                }
+            }
+        })
+        .await
+    }
+
+    /// Close the connections holding advisory locks, which releases the locks
+    pub(crate) async fn release_all_advisory_locks(&self) {
+        let held: Vec<_> = self.advisory_locks.lock().unwrap().drain().map(|(_, conn)| conn).collect();
+        for conn in held {
+            let _ = conn.detach().close().await;
+        }
+    }
+}
@@ -0,0 +1,309 @@
+//! In-memory implementations of the repository traits
+//!
+//! Enabled for this crate's own tests and for dependents through the `mock`
+//! feature, e.g. for a mock mode that runs without Postgres, or the
+//! `test-support` feature for their tests.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::Result;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::events::{EventSink, OrderCreated};
+use crate::models::{
+    initial_order_status, normalize_order_tag, resolve_duplicate_items, DuplicateItemPolicy, NewOrderItem, Order,
+    OrderItem, OrderSort,
+};
+use crate::repository::OrderRepository;
+
+/// In-memory [`OrderRepository`] for tests that must not touch Postgres
+#[derive(Debug, Default)]
+pub struct MockOrderRepository {
+    orders: Mutex<Vec<Order>>,
+    last_order_number: Mutex<u64>, // never reused, like the database's order sequence
+    duplicate_item_policy: DuplicateItemPolicy,
+    unit_prices: HashMap<String, Decimal>,
+    tags: Mutex<HashMap<String, Vec<String>>>,
+    idempotency_keys: Mutex<HashMap<String, String>>,
+    lost_acknowledgements: Mutex<u32>,
+    event_sink: Option<Arc<dyn EventSink>>,
+}
+
+impl MockOrderRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_duplicate_item_policy(mut self, policy: DuplicateItemPolicy) -> Self {
+        self.duplicate_item_policy = policy;
+        self
+    }
+
+    /// Price lines for `sku` at `unit_price`; unpriced SKUs cost nothing
+    pub fn with_unit_price(mut self, sku: impl Into<String>, unit_price: Decimal) -> Self {
+        self.unit_prices.insert(sku.into(), unit_price);
+        self
+    }
+
+    /// Publish [`OrderCreated`] events to `sink`, as `Database::with_event_sink` does
+    pub fn with_event_sink(mut self, sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(sink);
+        self
+    }
+
+    /// Make the next `count` idempotent creates fail with a dropped connection
+    ///
+    /// The order is still stored, as when a commit succeeds but its
+    /// acknowledgement never reaches the client, so a retry must not insert it again.
+    /// Retries with the same key fail the same way until `count` is used up.
+    pub fn with_lost_acknowledgements(self, count: u32) -> Self {
+        *self.lost_acknowledgements.lock().unwrap() = count;
+        self
+    }
+
+    /// Snapshot of all stored orders, in insertion order
+    pub fn orders(&self) -> Vec<Order> {
+        self.orders.lock().unwrap().clone()
+    }
+
+    /// Tags added to an order, in the order they were added
+    pub fn tags(&self, order_id: &str) -> Vec<String> {
+        self.tags.lock().unwrap().get(order_id).cloned().unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl OrderRepository for MockOrderRepository {
+    async fn create_order(&self, items: &[NewOrderItem]) -> Result<Order> {
+        self.create_order_with_status(items, None).await
+    }
+
+    async fn create_order_with_status(&self, items: &[NewOrderItem], initial_status: Option<&str>) -> Result<Order> {
+        let status = initial_order_status(initial_status)?;
+        if items.is_empty() {
+            color_eyre::eyre::bail!("An order must contain at least one item");
+        }
+        if let Some(item) = items.iter().find(|item| item.quantity <= 0) {
+            color_eyre::eyre::bail!("Quantity for SKU '{}' must be greater than 0", item.sku);
+        }
+        let items = resolve_duplicate_items(items, self.duplicate_item_policy)?;
+
+        let mut orders = self.orders.lock().unwrap();
+        let mut last_order_number = self.last_order_number.lock().unwrap();
+        *last_order_number += 1;
+        let order = Order {
+            id: format!("ORD-{:06}", *last_order_number),
+            status,
+            created_at: Utc::now(),
+            sla_deadline: None,
+            items: items
+                .iter()
+                .map(|item| {
+                    let unit_price = self.unit_prices.get(&item.sku).copied().unwrap_or(Decimal::ZERO);
+                    OrderItem::new(&item.sku, &item.item_name, item.quantity, unit_price)
+                })
+                .collect(),
+        };
+        orders.push(order.clone());
+        if let Some(sink) = &self.event_sink {
+            sink.order_created(&OrderCreated::for_order(&order));
+        }
+        Ok(order)
+    }
+
+    async fn create_order_idempotent(
+        &self,
+        items: &[NewOrderItem],
+        initial_status: Option<&str>,
+        idempotency_key: &str,
+    ) -> Result<Order> {
+        if idempotency_key.trim().is_empty() {
+            color_eyre::eyre::bail!("Idempotency key cannot be empty");
+        }
+        let existing = self.idempotency_keys.lock().unwrap().get(idempotency_key).cloned();
+        let stored = match existing {
+            Some(id) => self.get_order(&id).await?,
+            None => None,
+        };
+        // As in the database, a key goes away with its order and may then be reused
+        let order = match stored {
+            Some(order) => order,
+            None => {
+                let order = self.create_order_with_status(items, initial_status).await?;
+                self.idempotency_keys.lock().unwrap().insert(idempotency_key.to_string(), order.id.clone());
+                order
+            }
+        };
+
+        let mut lost = self.lost_acknowledgements.lock().unwrap();
+        if *lost > 0 {
+            *lost -= 1;
+            let reset = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset by peer");
+            return Err(color_eyre::eyre::Report::from(sqlx::Error::Io(reset)).wrap_err("Failed to commit order"));
+        }
+        Ok(order)
+    }
+
+    async fn get_order(&self, id: &str) -> Result<Option<Order>> {
+        Ok(self.orders.lock().unwrap().iter().find(|order| order.id == id).cloned())
+    }
+
+    async fn list_orders(&self) -> Result<Vec<Order>> {
+        Ok(self.orders.lock().unwrap().iter().rev().cloned().collect())
+    }
+
+    async fn list_orders_sorted(&self, sort: OrderSort) -> Result<Vec<Order>> {
+        // Insertion order stands in for creation time, newest first as the base order
+        let mut orders: Vec<Order> = self.orders.lock().unwrap().iter().rev().cloned().collect();
+        let units = |order: &Order| order.items.iter().map(|item| item.quantity as i64).sum::<i64>();
+        match sort {
+            OrderSort::CreatedAtDesc => {}
+            OrderSort::CreatedAtAsc => orders.reverse(),
+            OrderSort::QuantityDesc => orders.sort_by_key(|order| std::cmp::Reverse(units(order))),
+            OrderSort::StatusAsc => orders.sort_by_key(|order| order.status.as_str()),
+        }
+        Ok(orders)
+    }
+
+    async fn update_picked(&self, order_id: &str, sku: &str, picked_quantity: i32) -> Result<OrderItem> {
+        let mut orders = self.orders.lock().unwrap();
+        let Some(order) = orders.iter_mut().find(|order| order.id == order_id) else {
+            color_eyre::eyre::bail!("Order {} not found", order_id);
+        };
+        let Some(item) = order.items.iter_mut().find(|item| item.sku == sku) else {
+            color_eyre::eyre::bail!("Order {} has no item with SKU '{}'", order_id, sku);
+        };
+        if picked_quantity < 0 || picked_quantity > item.quantity {
+            color_eyre::eyre::bail!(
+                "Picked quantity {} is outside 0..={} for SKU '{}'",
+                picked_quantity,
+                item.quantity,
+                sku
+            );
+        }
+        item.picked_quantity = picked_quantity;
+        Ok(item.clone())
+    }
+
+    async fn add_order_tag(&self, order_id: &str, tag: &str) -> Result<()> {
+        let tag = normalize_order_tag(tag)?;
+        if !self.orders.lock().unwrap().iter().any(|order| order.id == order_id) {
+            color_eyre::eyre::bail!("Order {} not found", order_id);
+        }
+        let mut tags = self.tags.lock().unwrap();
+        let order_tags = tags.entry(order_id.to_string()).or_default();
+        if !order_tags.contains(&tag) {
+            order_tags.push(tag);
+        }
+        Ok(())
+    }
+
+    async fn delete_order(&self, id: &str) -> Result<u64> {
+        let mut orders = self.orders.lock().unwrap();
+        let Some(index) = orders.iter().position(|order| order.id == id) else {
+            return Ok(0);
+        };
+        let order = orders.remove(index);
+        let tags = self.tags.lock().unwrap().remove(id).unwrap_or_default();
+        self.idempotency_keys.lock().unwrap().retain(|_, order_id| order_id != id);
+        Ok((1 + order.items.len() + tags.len()) as u64)
+    }
+
+    async fn set_sla_deadline(&self, order_id: &str, deadline: Option<DateTime<Utc>>) -> Result<()> {
+        let mut orders = self.orders.lock().unwrap();
+        let Some(order) = orders.iter_mut().find(|order| order.id == order_id) else {
+            color_eyre::eyre::bail!("Order {} not found", order_id);
+        };
+        order.sla_deadline = deadline;
+        Ok(())
+    }
+
+    async fn breached_orders(&self) -> Result<Vec<Order>> {
+        let now = Utc::now();
+        let mut breached: Vec<Order> =
+            self.orders.lock().unwrap().iter().filter(|order| order.is_sla_breached(now)).cloned().collect();
+        breached.sort_by_key(|order| order.sla_deadline);
+        Ok(breached)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_order_repository_round_trip() {
+        let repo = MockOrderRepository::new();
+        let order = repo
+            .create_order(&[NewOrderItem::new("SKU-001", "Widget A", 3)])
+            .await
+            .unwrap();
+
+        repo.update_picked(&order.id, "SKU-001", 2).await.unwrap();
+        let fetched = repo.get_order(&order.id).await.unwrap().unwrap();
+        assert_eq!(fetched.items[0].picked_quantity, 2);
+        assert_eq!(repo.list_orders().await.unwrap().len(), 1);
+        assert!(repo.update_picked(&order.id, "SKU-001", 4).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_order_ids_not_reused_after_delete() {
+        let repo = MockOrderRepository::new();
+        let items = [NewOrderItem::new("SKU-001", "Widget A", 1)];
+        let first = repo.create_order(&items).await.unwrap();
+        let second = repo.create_order(&items).await.unwrap();
+
+        repo.delete_order(&second.id).await.unwrap();
+        let third = repo.create_order(&items).await.unwrap();
+        assert_ne!(third.id, second.id);
+        assert_eq!(third.id, "ORD-000003");
+        assert_eq!(repo.get_order(&first.id).await.unwrap().unwrap().id, first.id);
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_create_returns_existing_order() {
+        let repo = MockOrderRepository::new();
+        let items = [NewOrderItem::new("SKU-001", "Widget A", 1)];
+        let first = repo.create_order_idempotent(&items, None, "key-1").await.unwrap();
+        let again = repo.create_order_idempotent(&items, None, "key-1").await.unwrap();
+        let other = repo.create_order_idempotent(&items, None, "key-2").await.unwrap();
+
+        assert_eq!(first.id, again.id);
+        assert_ne!(first.id, other.id);
+        assert_eq!(repo.orders().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_replayed_after_delete_creates_new_order() {
+        let repo = MockOrderRepository::new();
+        let items = [NewOrderItem::new("SKU-001", "Widget A", 1)];
+        let first = repo.create_order_idempotent(&items, None, "key-1").await.unwrap();
+        repo.delete_order(&first.id).await.unwrap();
+
+        let replayed = repo.create_order_idempotent(&items, None, "key-1").await.unwrap();
+        assert_ne!(replayed.id, first.id);
+        assert_eq!(repo.orders().len(), 1);
+        let again = repo.create_order_idempotent(&items, None, "key-1").await.unwrap();
+        assert_eq!(again.id, replayed.id);
+    }
+
+    #[tokio::test]
+    async fn test_order_created_event_emitted() {
+        let sink = Arc::new(crate::events::VecEventSink::new());
+        let repo = MockOrderRepository::new().with_event_sink(sink.clone());
+        let items = [NewOrderItem::new("SKU-001", "Widget A", 2)];
+        let order = repo.create_order_idempotent(&items, None, "key-1").await.unwrap();
+
+        let events = sink.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].order_id, order.id);
+        assert_eq!(events[0].items, order.items);
+        assert_eq!(events[0].created_at, order.created_at);
+
+        // Replaying the key returns the stored order without a second event
+        repo.create_order_idempotent(&items, None, "key-1").await.unwrap();
+        assert_eq!(sink.events().len(), 1);
+    }
+}
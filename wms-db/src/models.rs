@@ -0,0 +1,817 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef};
+use sqlx::Postgres;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::WmsDbError;
+
+/// Lifecycle status of an order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrderStatus {
+    Pending,
+    Confirmed,
+    Shipped,
+    Delivered,
+    Cancelled,
+}
+
+impl OrderStatus {
+    /// All statuses, in lifecycle order
+    pub const ALL: [OrderStatus; 5] = [
+        OrderStatus::Pending,
+        OrderStatus::Confirmed,
+        OrderStatus::Shipped,
+        OrderStatus::Delivered,
+        OrderStatus::Cancelled,
+    ];
+
+    /// Value stored in the `orders.status` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderStatus::Pending => "pending",
+            OrderStatus::Confirmed => "confirmed",
+            OrderStatus::Shipped => "shipped",
+            OrderStatus::Delivered => "delivered",
+            OrderStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// Whether an order may move from this status to `next`
+    ///
+    /// Orders move forward one step at a time, pending → confirmed → shipped →
+    /// delivered, and may be cancelled until they ship. Delivered and cancelled
+    /// orders are final, and staying in the same status is not a transition.
+    pub fn can_transition_to(&self, next: OrderStatus) -> bool {
+        matches!(
+            (self, next),
+            (OrderStatus::Pending, OrderStatus::Confirmed | OrderStatus::Cancelled)
+                | (OrderStatus::Confirmed, OrderStatus::Shipped | OrderStatus::Cancelled)
+                | (OrderStatus::Shipped, OrderStatus::Delivered)
+        )
+    }
+}
+
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OrderStatus {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OrderStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == s)
+            .ok_or_else(|| color_eyre::eyre::eyre!("Unknown order status '{}'", s))
+    }
+}
+
+/// Status a new order starts in: `requested` if given, otherwise `Pending`
+pub fn initial_order_status(requested: Option<&str>) -> color_eyre::eyre::Result<OrderStatus> {
+    match requested {
+        Some(status) => status.trim().parse(),
+        None => Ok(OrderStatus::Pending),
+    }
+}
+
+/// Longest tag accepted on an order
+pub const MAX_ORDER_TAG_LEN: usize = 64;
+
+/// Trim an order tag and check it is non-empty and at most `MAX_ORDER_TAG_LEN` characters
+pub fn normalize_order_tag(tag: &str) -> color_eyre::eyre::Result<String> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        color_eyre::eyre::bail!("Order tags cannot be empty");
+    }
+    if tag.chars().count() > MAX_ORDER_TAG_LEN {
+        color_eyre::eyre::bail!("Order tag '{}' is longer than {} characters", tag, MAX_ORDER_TAG_LEN);
+    }
+    Ok(tag.to_string())
+}
+
+/// Whitelisted orderings for listing orders
+///
+/// Each variant maps to a fixed SQL fragment; sort keys are never built from
+/// user input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OrderSort {
+    /// Newest first
+    #[default]
+    CreatedAtDesc,
+    /// Oldest first
+    CreatedAtAsc,
+    /// Most units ordered (summed over all lines) first
+    QuantityDesc,
+    /// Alphabetical by status, newest first within a status
+    StatusAsc,
+}
+
+impl OrderSort {
+    pub const ALL: [OrderSort; 4] = [
+        OrderSort::CreatedAtDesc,
+        OrderSort::CreatedAtAsc,
+        OrderSort::QuantityDesc,
+        OrderSort::StatusAsc,
+    ];
+
+    /// Name accepted on the command line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderSort::CreatedAtDesc => "created-at-desc",
+            OrderSort::CreatedAtAsc => "created-at-asc",
+            OrderSort::QuantityDesc => "quantity-desc",
+            OrderSort::StatusAsc => "status-asc",
+        }
+    }
+
+    /// `ORDER BY` clause over the `orders` table, with `id` as the final tie-break
+    pub fn order_by_sql(&self) -> &'static str {
+        match self {
+            OrderSort::CreatedAtDesc => "ORDER BY created_at DESC, id",
+            OrderSort::CreatedAtAsc => "ORDER BY created_at ASC, id",
+            OrderSort::QuantityDesc => {
+                "ORDER BY (SELECT COALESCE(SUM(quantity), 0) FROM order_items WHERE order_id = orders.id) DESC, \
+                 created_at DESC, id"
+            }
+            OrderSort::StatusAsc => "ORDER BY status ASC, created_at DESC, id",
+        }
+    }
+}
+
+impl fmt::Display for OrderSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OrderSort {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OrderSort::ALL
+            .into_iter()
+            .find(|sort| sort.as_str() == s)
+            .ok_or_else(|| {
+                let allowed: Vec<_> = OrderSort::ALL.iter().map(|sort| sort.as_str()).collect();
+                color_eyre::eyre::eyre!("Unknown order sort '{}' (allowed: {})", s, allowed.join(", "))
+            })
+    }
+}
+
+/// Unit a quantity is counted in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum UnitOfMeasure {
+    #[default]
+    Each,
+    Case,
+    Pallet,
+}
+
+impl UnitOfMeasure {
+    pub const ALL: [UnitOfMeasure; 3] = [UnitOfMeasure::Each, UnitOfMeasure::Case, UnitOfMeasure::Pallet];
+
+    /// Value stored in the `unit` columns
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnitOfMeasure::Each => "each",
+            UnitOfMeasure::Case => "case",
+            UnitOfMeasure::Pallet => "pallet",
+        }
+    }
+}
+
+impl fmt::Display for UnitOfMeasure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for UnitOfMeasure {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        UnitOfMeasure::ALL
+            .into_iter()
+            .find(|unit| unit.as_str() == s)
+            .ok_or_else(|| color_eyre::eyre::eyre!("Unknown unit of measure '{}' (expected each, case or pallet)", s))
+    }
+}
+
+impl sqlx::Type<Postgres> for UnitOfMeasure {
+    fn type_info() -> PgTypeInfo {
+        <&str as sqlx::Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <&str as sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl sqlx::Encode<'_, Postgres> for UnitOfMeasure {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        <&str as sqlx::Encode<Postgres>>::encode(self.as_str(), buf)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Postgres> for UnitOfMeasure {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let unit = <&str as sqlx::Decode<Postgres>>::decode(value)?;
+        unit.parse().map_err(|err: color_eyre::eyre::Report| err.to_string().into())
+    }
+}
+
+/// A line item of an order
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct OrderItem {
+    pub sku: String,
+    pub item_name: String,
+    /// Ordered quantity, counted in `unit`
+    pub quantity: i32,
+    pub picked_quantity: i32,
+    /// Price per `unit` when the order was created
+    pub unit_price: Decimal,
+    /// `quantity × unit_price`, stored so later price changes don't alter the order
+    pub total_price: Decimal,
+    pub unit: UnitOfMeasure,
+    /// Stock units in one `unit` when the order was created
+    pub conversion_factor: i32,
+}
+
+impl OrderItem {
+    /// A freshly ordered line with nothing picked yet, priced at `unit_price`
+    pub fn new(sku: impl Into<String>, item_name: impl Into<String>, quantity: i32, unit_price: Decimal) -> Self {
+        Self {
+            sku: sku.into(),
+            item_name: item_name.into(),
+            quantity,
+            picked_quantity: 0,
+            unit_price,
+            total_price: unit_price * Decimal::from(quantity),
+            unit: UnitOfMeasure::Each,
+            conversion_factor: 1,
+        }
+    }
+
+    /// Count the line in `unit`, each of which holds `conversion_factor` stock units
+    pub fn with_unit(mut self, unit: UnitOfMeasure, conversion_factor: i32) -> Self {
+        self.unit = unit;
+        self.conversion_factor = conversion_factor;
+        self
+    }
+
+    /// Whether every unit of this line has been picked
+    pub fn is_fulfilled(&self) -> bool {
+        self.picked_quantity >= self.quantity
+    }
+
+    /// Ordered quantity in stock units
+    pub fn stock_quantity(&self) -> i32 {
+        self.quantity * self.conversion_factor
+    }
+}
+
+/// A line item to be inserted with a new order
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewOrderItem {
+    pub sku: String,
+    pub item_name: String,
+    pub quantity: i32,
+    /// Unit `quantity` is counted in; it must be sellable for the item
+    pub unit: UnitOfMeasure,
+}
+
+impl NewOrderItem {
+    pub fn new(sku: impl Into<String>, item_name: impl Into<String>, quantity: i32) -> Self {
+        Self {
+            sku: sku.into(),
+            item_name: item_name.into(),
+            quantity,
+            unit: UnitOfMeasure::Each,
+        }
+    }
+
+    pub fn with_unit(mut self, unit: UnitOfMeasure) -> Self {
+        self.unit = unit;
+        self
+    }
+}
+
+/// A stocked item
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct InventoryItem {
+    pub sku: String,
+    pub name: String,
+    /// Must be one of the names in the `categories` table
+    pub category: String,
+    pub quantity: i32,
+    pub unit_price: Decimal,
+    /// Quantity at or below which the item counts as low on stock
+    pub reorder_threshold: i32,
+    /// Unit `quantity`, `unit_price` and `reorder_threshold` are counted in
+    pub unit: UnitOfMeasure,
+    /// Stock units in a case, `None` if the item is not sold by the case
+    pub case_quantity: Option<i32>,
+    /// Stock units on a pallet, `None` if the item is not sold by the pallet
+    pub pallet_quantity: Option<i32>,
+}
+
+impl InventoryItem {
+    pub fn new(sku: impl Into<String>, name: impl Into<String>, category: impl Into<String>, quantity: i32) -> Self {
+        Self {
+            sku: sku.into(),
+            name: name.into(),
+            category: category.into(),
+            quantity,
+            unit_price: Decimal::ZERO,
+            reorder_threshold: 0,
+            unit: UnitOfMeasure::Each,
+            case_quantity: None,
+            pallet_quantity: None,
+        }
+    }
+
+    pub fn with_unit_price(mut self, unit_price: Decimal) -> Self {
+        self.unit_price = unit_price;
+        self
+    }
+
+    pub fn with_reorder_threshold(mut self, reorder_threshold: i32) -> Self {
+        self.reorder_threshold = reorder_threshold;
+        self
+    }
+
+    /// Count stock in `unit` instead of eaches
+    pub fn with_unit(mut self, unit: UnitOfMeasure) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Also sell the item by the case, `case_quantity` stock units each
+    pub fn with_case_quantity(mut self, case_quantity: i32) -> Self {
+        self.case_quantity = Some(case_quantity);
+        self
+    }
+
+    /// Also sell the item by the pallet, `pallet_quantity` stock units each
+    pub fn with_pallet_quantity(mut self, pallet_quantity: i32) -> Self {
+        self.pallet_quantity = Some(pallet_quantity);
+        self
+    }
+
+    /// Stock units in one `unit`, or `None` if the item is not sold in it
+    ///
+    /// The stock unit itself always converts 1:1. Cases and pallets convert
+    /// only when their quantity is configured; eaches of an item stocked in
+    /// cases or pallets are not sellable.
+    pub fn conversion_factor(&self, unit: UnitOfMeasure) -> Option<i32> {
+        if unit == self.unit {
+            return Some(1);
+        }
+        match unit {
+            UnitOfMeasure::Each => None,
+            UnitOfMeasure::Case => self.case_quantity,
+            UnitOfMeasure::Pallet => self.pallet_quantity,
+        }
+    }
+
+    /// Whether the quantity has dropped to the reorder threshold
+    pub fn is_low_stock(&self) -> bool {
+        self.quantity <= self.reorder_threshold
+    }
+}
+
+/// A worker as persisted in the `workers` table
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkerRecord {
+    pub id: i32,
+    pub name: String,
+    pub is_available: bool,
+    pub current_load: f64,
+    pub max_tasks: i32,
+    /// Last recorded `(x, y)` position, `None` if the worker was never located
+    pub location: Option<(f64, f64)>,
+    /// Travel speed in units per minute, `None` to use the planner's default
+    pub travel_speed: Option<f64>,
+}
+
+/// A task as persisted in the `tasks` table
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskRecord {
+    pub id: i32,
+    pub location: (f64, f64),
+    /// One of `low`, `medium`, `high` or `critical`
+    pub priority: String,
+    /// Expected minutes to complete
+    pub estimated_duration: Option<f64>,
+}
+
+/// A finished assignment, as reported when recording its actual duration
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletedAssignment {
+    pub task_id: i32,
+    pub worker_id: i32,
+    /// Skill the task required, used to group similar tasks
+    pub skill: Option<String>,
+    /// Minutes the planner expected the task to take
+    pub estimated_duration: f64,
+}
+
+/// A stored completion from the `assignment_completions` table
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct CompletionRecord {
+    pub task_id: i32,
+    pub worker_id: i32,
+    pub skill: Option<String>,
+    pub estimated_duration: f64,
+    pub actual_duration: f64,
+    pub completed_at: DateTime<Utc>,
+}
+
+/// Format of generated order IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrderIdFormat {
+    /// `ORD-` followed by six random digits, e.g. `ORD-482913`
+    #[default]
+    Random,
+    /// `ORD-` followed by the UTC creation date and a daily sequence, e.g. `ORD-20240115-0042`
+    DatePrefixed,
+}
+
+impl OrderIdFormat {
+    /// Render a date-prefixed order ID for the given day and daily sequence number
+    pub fn date_prefixed(day: NaiveDate, sequence: u32) -> String {
+        format!("ORD-{}-{:04}", day.format("%Y%m%d"), sequence)
+    }
+}
+
+impl FromStr for OrderIdFormat {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "random" => Ok(OrderIdFormat::Random),
+            "date-prefixed" => Ok(OrderIdFormat::DatePrefixed),
+            _ => color_eyre::eyre::bail!("Unknown order ID format '{}' (expected 'random' or 'date-prefixed')", s),
+        }
+    }
+}
+
+/// How to treat an order containing the same SKU on several lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateItemPolicy {
+    /// Combine the lines into one, summing their quantities; lines in different units are rejected
+    #[default]
+    Merge,
+    /// Reject the order with `WmsDbError::DuplicateLineItem`
+    Reject,
+}
+
+impl FromStr for DuplicateItemPolicy {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "merge" => Ok(DuplicateItemPolicy::Merge),
+            "reject" => Ok(DuplicateItemPolicy::Reject),
+            _ => color_eyre::eyre::bail!("Unknown duplicate item policy '{}' (expected 'merge' or 'reject')", s),
+        }
+    }
+}
+
+/// Canonical form of an item name: surrounding whitespace trimmed and lowercased
+///
+/// "  Widget A " and "widget a" normalize to the same name.
+pub fn normalize_item_name(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Apply a duplicate-SKU policy to the requested line items
+///
+/// Merged lines keep the position and item name of the first occurrence.
+pub fn resolve_duplicate_items(
+    items: &[NewOrderItem],
+    policy: DuplicateItemPolicy,
+) -> Result<Vec<NewOrderItem>, WmsDbError> {
+    let mut resolved: Vec<NewOrderItem> = Vec::with_capacity(items.len());
+    for item in items {
+        match resolved.iter_mut().find(|existing| existing.sku == item.sku) {
+            Some(existing) => match policy {
+                DuplicateItemPolicy::Merge if existing.unit == item.unit => existing.quantity += item.quantity,
+                DuplicateItemPolicy::Merge | DuplicateItemPolicy::Reject => {
+                    return Err(WmsDbError::DuplicateLineItem { sku: item.sku.clone() })
+                }
+            },
+            None => resolved.push(item.clone()),
+        }
+    }
+    Ok(resolved)
+}
+
+/// An order together with its line items
+#[derive(Debug, Clone, PartialEq)]
+pub struct Order {
+    pub id: String,
+    pub status: OrderStatus,
+    pub created_at: DateTime<Utc>,
+    /// When the order must have shipped by, `None` without an SLA
+    pub sla_deadline: Option<DateTime<Utc>>,
+    pub items: Vec<OrderItem>,
+}
+
+impl Order {
+    /// Whether the SLA deadline has passed at `now` without the order shipping
+    ///
+    /// Shipped, delivered and cancelled orders are never in breach.
+    pub fn is_sla_breached(&self, now: DateTime<Utc>) -> bool {
+        let unshipped = matches!(self.status, OrderStatus::Pending | OrderStatus::Confirmed);
+        unshipped && self.sla_deadline.is_some_and(|deadline| deadline < now)
+    }
+
+    /// Fraction of ordered units picked so far, from 0.0 to 1.0
+    ///
+    /// An order without items is reported as 0.0.
+    pub fn fulfillment_ratio(&self) -> f64 {
+        let ordered: i64 = self.items.iter().map(|item| item.quantity as i64).sum();
+        if ordered == 0 {
+            return 0.0;
+        }
+        let picked: i64 = self
+            .items
+            .iter()
+            .map(|item| item.picked_quantity.min(item.quantity) as i64)
+            .sum();
+        picked as f64 / ordered as f64
+    }
+
+    /// Sum of the stored line totals
+    pub fn total_price(&self) -> Decimal {
+        self.items.iter().map(|item| item.total_price).sum()
+    }
+
+    /// Printable plain-text confirmation listing `items` and their total
+    ///
+    /// Timestamps are shown in UTC. Pass `&order.items` for the order's own lines.
+    pub fn receipt(&self, items: &[OrderItem]) -> String {
+        let rule = "-".repeat(RECEIPT_WIDTH);
+        let mut lines = vec![
+            "ORDER RECEIPT".to_string(),
+            format!("Order:  {}", self.id),
+            format!("Date:   {}", self.created_at.format("%Y-%m-%d %H:%M UTC")),
+            format!("Status: {}", self.status),
+            rule.clone(),
+        ];
+        for item in items {
+            lines.push(format!("{:<12} {}", item.sku, item.item_name));
+            let priced = match item.unit {
+                UnitOfMeasure::Each => format!("    {} x {}", item.quantity, item.unit_price),
+                unit => format!("    {} {} x {}", item.quantity, unit, item.unit_price),
+            };
+            lines.push(right_aligned(&priced, &item.total_price.to_string()));
+        }
+        let total: Decimal = items.iter().map(|item| item.total_price).sum();
+        lines.push(rule);
+        lines.push(right_aligned("TOTAL", &total.to_string()));
+        lines.join("\n")
+    }
+}
+
+/// Character width of [`Order::receipt`] lines
+const RECEIPT_WIDTH: usize = 40;
+
+/// `label`, then `amount` flush with the right edge of a receipt
+fn right_aligned(label: &str, amount: &str) -> String {
+    format!("{:<w$}{}", label, amount, w = RECEIPT_WIDTH.saturating_sub(amount.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(sku: &str, quantity: i32, picked_quantity: i32) -> OrderItem {
+        OrderItem {
+            picked_quantity,
+            ..OrderItem::new(sku, sku, quantity, Decimal::ZERO)
+        }
+    }
+
+    fn order(items: Vec<OrderItem>) -> Order {
+        Order {
+            id: "ORD-000001".to_string(),
+            status: OrderStatus::Pending,
+            created_at: Utc::now(),
+            sla_deadline: None,
+            items,
+        }
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        assert!(OrderStatus::Pending.can_transition_to(OrderStatus::Confirmed));
+        assert!(OrderStatus::Confirmed.can_transition_to(OrderStatus::Shipped));
+        assert!(OrderStatus::Shipped.can_transition_to(OrderStatus::Delivered));
+        assert!(OrderStatus::Confirmed.can_transition_to(OrderStatus::Cancelled));
+
+        assert!(!OrderStatus::Pending.can_transition_to(OrderStatus::Shipped));
+        assert!(!OrderStatus::Shipped.can_transition_to(OrderStatus::Cancelled));
+        assert!(!OrderStatus::Shipped.can_transition_to(OrderStatus::Shipped));
+        for next in OrderStatus::ALL {
+            assert!(!OrderStatus::Delivered.can_transition_to(next));
+            assert!(!OrderStatus::Cancelled.can_transition_to(next));
+        }
+    }
+
+    #[test]
+    fn test_fulfillment_ratio_partial() {
+        let order = order(vec![item("SKU-001", 10, 5), item("SKU-002", 10, 0)]);
+        assert!((order.fulfillment_ratio() - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fulfillment_ratio_full_and_empty() {
+        let full = order(vec![item("SKU-001", 3, 3), item("SKU-002", 2, 2)]);
+        assert_eq!(full.fulfillment_ratio(), 1.0);
+        assert!(full.items.iter().all(OrderItem::is_fulfilled));
+
+        assert_eq!(order(vec![]).fulfillment_ratio(), 0.0);
+    }
+
+    #[test]
+    fn test_order_total_multi_item() {
+        let order = order(vec![
+            OrderItem::new("SKU-001", "Widget A", 3, Decimal::new(1999, 2)),
+            OrderItem::new("SKU-002", "Widget B", 2, Decimal::new(550, 2)),
+        ]);
+        assert_eq!(order.items[0].total_price, Decimal::new(5997, 2));
+        assert_eq!(order.total_price(), Decimal::new(7097, 2));
+    }
+
+    #[test]
+    fn test_receipt_lists_lines_and_total() {
+        let mut order = order(vec![
+            OrderItem::new("SKU-001", "Widget A", 3, Decimal::new(1999, 2)),
+            OrderItem::new("SKU-002", "Widget B", 2, Decimal::new(550, 2)),
+        ]);
+        order.created_at = "2024-01-15T10:30:00Z".parse().unwrap();
+
+        let receipt = order.receipt(&order.items);
+        assert!(receipt.contains("Order:  ORD-000001"));
+        assert!(receipt.contains("Date:   2024-01-15 10:30 UTC"));
+        assert!(receipt.contains("SKU-001      Widget A"));
+        assert!(receipt.contains("SKU-002      Widget B"));
+        assert!(receipt.contains("3 x 19.99"));
+        assert!(receipt.contains("2 x 5.50"));
+        assert!(receipt.lines().last().unwrap().starts_with("TOTAL"));
+        assert!(receipt.lines().last().unwrap().ends_with("70.97"));
+        assert!(receipt.lines().all(|line| line.len() <= RECEIPT_WIDTH));
+    }
+
+    #[test]
+    fn test_order_total_decimal_precision() {
+        // 0.1 × 3 is exactly 0.3, unlike with f64
+        let priced = order(vec![
+            OrderItem::new("SKU-001", "Washer", 3, "0.1".parse().unwrap()),
+            OrderItem::new("SKU-002", "Bolt", 1, "0.2".parse().unwrap()),
+        ]);
+        assert_eq!(priced.items[0].total_price, "0.3".parse::<Decimal>().unwrap());
+        assert_eq!(priced.total_price(), "0.5".parse::<Decimal>().unwrap());
+        assert_eq!(priced.total_price().to_string(), "0.5");
+        assert_eq!(order(vec![]).total_price(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_normalize_item_name() {
+        assert_eq!(normalize_item_name("  Widget A "), "widget a");
+        assert_eq!(normalize_item_name("WIDGET A"), normalize_item_name("widget a"));
+        assert_eq!(normalize_item_name("Éclair"), "éclair");
+    }
+
+    #[test]
+    fn test_duplicate_items_merged() {
+        let items = [
+            NewOrderItem::new("SKU-001", "Widget A", 2),
+            NewOrderItem::new("SKU-002", "Widget B", 1),
+            NewOrderItem::new("SKU-001", "Widget A", 3),
+        ];
+        let resolved = resolve_duplicate_items(&items, DuplicateItemPolicy::Merge).unwrap();
+        assert_eq!(
+            resolved,
+            vec![
+                NewOrderItem::new("SKU-001", "Widget A", 5),
+                NewOrderItem::new("SKU-002", "Widget B", 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_items_rejected() {
+        let items = [
+            NewOrderItem::new("SKU-001", "Widget A", 2),
+            NewOrderItem::new("SKU-001", "Widget A", 3),
+        ];
+        let err = resolve_duplicate_items(&items, DuplicateItemPolicy::Reject).unwrap_err();
+        assert_eq!(err, WmsDbError::DuplicateLineItem { sku: "SKU-001".to_string() });
+
+        let unique = [NewOrderItem::new("SKU-001", "Widget A", 2)];
+        assert!(resolve_duplicate_items(&unique, DuplicateItemPolicy::Reject).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_items_in_different_units_not_merged() {
+        let items = [
+            NewOrderItem::new("SKU-001", "Widget A", 2),
+            NewOrderItem::new("SKU-001", "Widget A", 1).with_unit(UnitOfMeasure::Case),
+        ];
+        let err = resolve_duplicate_items(&items, DuplicateItemPolicy::Merge).unwrap_err();
+        assert_eq!(err, WmsDbError::DuplicateLineItem { sku: "SKU-001".to_string() });
+    }
+
+    #[test]
+    fn test_unit_conversion_factors() {
+        let item = InventoryItem::new("SKU-001", "Widget A", "Hardware", 100).with_case_quantity(12);
+        assert_eq!(item.conversion_factor(UnitOfMeasure::Each), Some(1));
+        assert_eq!(item.conversion_factor(UnitOfMeasure::Case), Some(12));
+        assert_eq!(item.conversion_factor(UnitOfMeasure::Pallet), None);
+
+        // Stocked by the case, with 40 cases to a pallet: single eaches aren't sellable
+        let cased = InventoryItem::new("SKU-002", "Widget B", "Hardware", 10)
+            .with_unit(UnitOfMeasure::Case)
+            .with_pallet_quantity(40);
+        assert_eq!(cased.conversion_factor(UnitOfMeasure::Case), Some(1));
+        assert_eq!(cased.conversion_factor(UnitOfMeasure::Pallet), Some(40));
+        assert_eq!(cased.conversion_factor(UnitOfMeasure::Each), None);
+
+        let line = OrderItem::new("SKU-001", "Widget A", 3, Decimal::new(1200, 2)).with_unit(UnitOfMeasure::Case, 12);
+        assert_eq!(line.stock_quantity(), 36);
+        assert_eq!(line.total_price, Decimal::new(3600, 2));
+
+        for unit in UnitOfMeasure::ALL {
+            assert_eq!(unit.to_string().parse::<UnitOfMeasure>().unwrap(), unit);
+        }
+        assert!("box".parse::<UnitOfMeasure>().is_err());
+    }
+
+    #[test]
+    fn test_date_prefixed_order_id() {
+        let day = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        assert_eq!(OrderIdFormat::date_prefixed(day, 42), "ORD-20240115-0042");
+        assert_eq!(OrderIdFormat::date_prefixed(day, 1), "ORD-20240115-0001");
+        // Sequences beyond four digits widen rather than wrap
+        assert_eq!(OrderIdFormat::date_prefixed(day, 12345), "ORD-20240115-12345");
+        assert_eq!("date-prefixed".parse::<OrderIdFormat>().unwrap(), OrderIdFormat::DatePrefixed);
+        assert!("daily".parse::<OrderIdFormat>().is_err());
+    }
+
+    #[test]
+    fn test_initial_order_status_defaults_to_pending() {
+        assert_eq!(initial_order_status(None).unwrap(), OrderStatus::Pending);
+        assert_eq!(initial_order_status(Some(" confirmed ")).unwrap(), OrderStatus::Confirmed);
+        assert!(initial_order_status(Some("on-hold")).is_err());
+    }
+
+    #[test]
+    fn test_order_status_round_trip() {
+        for status in OrderStatus::ALL {
+            assert_eq!(status.as_str().parse::<OrderStatus>().unwrap(), status);
+        }
+        assert!("unknown".parse::<OrderStatus>().is_err());
+    }
+
+    #[test]
+    fn test_order_sort_round_trip() {
+        for sort in OrderSort::ALL {
+            assert_eq!(sort.as_str().parse::<OrderSort>().unwrap(), sort);
+            assert!(sort.order_by_sql().starts_with("ORDER BY "));
+        }
+        let err = "created_at; DROP TABLE orders".parse::<OrderSort>().unwrap_err();
+        assert!(err.to_string().contains("allowed: created-at-desc"));
+    }
+
+    #[test]
+    fn test_normalize_order_tag() {
+        assert_eq!(normalize_order_tag("  fragile ").unwrap(), "fragile");
+        assert!(normalize_order_tag("   ").is_err());
+        assert!(normalize_order_tag(&"x".repeat(MAX_ORDER_TAG_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_sla_breach_only_for_unshipped_orders_past_deadline() {
+        let now = Utc::now();
+        let mut order = order(Vec::new());
+        assert!(!order.is_sla_breached(now));
+
+        order.sla_deadline = Some(now - chrono::Duration::hours(1));
+        assert!(order.is_sla_breached(now));
+        order.status = OrderStatus::Shipped;
+        assert!(!order.is_sla_breached(now));
+
+        order.status = OrderStatus::Confirmed;
+        order.sla_deadline = Some(now + chrono::Duration::hours(1));
+        assert!(!order.is_sla_breached(now));
+    }
+}
@@ -0,0 +1,630 @@
+use color_eyre::eyre::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::{postgres::PgRow, FromRow, Postgres, Row, Transaction};
+use std::collections::HashMap;
+use tracing::info;
+
+use crate::models::{
+    initial_order_status, normalize_item_name, normalize_order_tag, resolve_duplicate_items, InventoryItem, NewOrderItem, Order,
+    OrderIdFormat, OrderItem, OrderSort, OrderStatus, UnitOfMeasure,
+};
+use crate::error::{WmsDbError, FOREIGN_KEY_VIOLATION};
+use crate::events::OrderCreated;
+use crate::Database;
+
+/// Generate a random order identifier
+fn generate_random_order_id() -> String {
+    format!("ORD-{:06}", fastrand::u32(100000..999999))
+}
+
+/// Build an order (without items) from an `orders` row
+fn order_from_row(row: &PgRow) -> Result<Order> {
+    let status: String = row.try_get("status")?;
+    Ok(Order {
+        id: row.try_get("id")?,
+        status: status.parse()?,
+        created_at: row.try_get("created_at")?,
+        sla_deadline: row.try_get("sla_deadline")?,
+        items: Vec::new(),
+    })
+}
+
+/// Escape `LIKE` wildcards so `text` matches only itself
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// Generate the ID for a new order created on `day`, inside the creating transaction
+///
+/// Date-prefixed IDs take the next value of the day's counter with an atomic
+/// upsert, so concurrent creations never share a sequence number.
+async fn next_order_id(
+    tx: &mut Transaction<'_, Postgres>,
+    format: OrderIdFormat,
+    day: NaiveDate,
+) -> Result<String> {
+    match format {
+        OrderIdFormat::Random => Ok(generate_random_order_id()),
+        OrderIdFormat::DatePrefixed => {
+            let row = sqlx::query(
+                "INSERT INTO order_id_sequences (day, last_value) VALUES ($1, 1) \
+                 ON CONFLICT (day) DO UPDATE SET last_value = order_id_sequences.last_value + 1 \
+                 RETURNING last_value",
+            )
+            .bind(day)
+            .fetch_one(&mut *tx)
+            .await
+            .context("Failed to allocate order sequence number")?;
+            let sequence: i32 = row.try_get("last_value")?;
+            Ok(OrderIdFormat::date_prefixed(day, sequence as u32))
+        }
+    }
+}
+
+impl Database {
+    /// Create a new order with the given line items in a single transaction
+    ///
+    /// Lines repeating a SKU are merged or rejected according to the configured
+    /// [`DuplicateItemPolicy`]. Each line's unit must be sellable for its item
+    /// (see [`InventoryItem::conversion_factor`]), or the order fails with
+    /// `WmsDbError::UnitNotSellable`; lines are priced per ordered unit.
+    pub async fn create_order_with_items(&self, items: &[NewOrderItem]) -> Result<Order> {
+        self.create_order_with_items_at(items, None).await
+    }
+
+    /// Create an order, optionally with an explicit creation timestamp
+    ///
+    /// `created_at` is meant for importing historical orders and fails with
+    /// `WmsDbError::BackdateNotAllowed` unless `allow_backdate` is configured.
+    /// `None` uses the database's `now()`.
+    pub async fn create_order_with_items_at(
+        &self,
+        items: &[NewOrderItem],
+        created_at: Option<DateTime<Utc>>,
+    ) -> Result<Order> {
+        self.insert_order(items, created_at, OrderStatus::Pending, None).await
+    }
+
+    /// Create an order starting in `initial_status` instead of `pending`
+    ///
+    /// For workflows that auto-confirm orders. The status must name an
+    /// [`OrderStatus`]; `None` means `pending`.
+    pub async fn create_order_with_status(&self, items: &[NewOrderItem], initial_status: Option<&str>) -> Result<Order> {
+        let status = initial_order_status(initial_status)?;
+        self.insert_order(items, None, status, None).await
+    }
+
+    /// Create an order at most once per `idempotency_key`
+    ///
+    /// If an order was already created with this key, it is returned instead
+    /// of inserting another, so a caller that lost the result of an earlier
+    /// attempt (e.g. to a dropped connection) can safely retry with the same key.
+    pub async fn create_order_idempotent(
+        &self,
+        items: &[NewOrderItem],
+        initial_status: Option<&str>,
+        idempotency_key: &str,
+    ) -> Result<Order> {
+        self.timed("create_order", async {
+            if idempotency_key.trim().is_empty() {
+                color_eyre::eyre::bail!("Idempotency key cannot be empty");
+            }
+            if let Some(order) = self.fetch_order_by_idempotency_key(idempotency_key).await? {
+                return Ok(order);
+            }
+            let status = initial_order_status(initial_status)?;
+            self.write_order(items, None, status, Some(idempotency_key)).await
+        })
+        .await
+    }
+
+    async fn fetch_order_by_idempotency_key(&self, idempotency_key: &str) -> Result<Option<Order>> {
+        let id: Option<String> = sqlx::query_scalar("SELECT id FROM orders WHERE idempotency_key = $1")
+            .bind(idempotency_key)
+            .fetch_optional(&mut *self.acquire().await?)
+            .await
+            .context("Failed to look up idempotency key")?;
+        match id {
+            Some(id) => self.fetch_order(&id).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn insert_order(
+        &self,
+        items: &[NewOrderItem],
+        created_at: Option<DateTime<Utc>>,
+        status: OrderStatus,
+        idempotency_key: Option<&str>,
+    ) -> Result<Order> {
+        self.timed("create_order", self.write_order(items, created_at, status, idempotency_key)).await
+    }
+
+    async fn write_order(
+        &self,
+        items: &[NewOrderItem],
+        created_at: Option<DateTime<Utc>>,
+        status: OrderStatus,
+        idempotency_key: Option<&str>,
+    ) -> Result<Order> {
+        if created_at.is_some() && !self.allow_backdate {
+            return Err(WmsDbError::BackdateNotAllowed.into());
+        }
+        if items.is_empty() {
+            color_eyre::eyre::bail!("An order must contain at least one item");
+        }
+        for item in items {
+            if item.sku.trim().is_empty() {
+                color_eyre::eyre::bail!("Item SKU cannot be empty");
+            }
+            if item.quantity <= 0 {
+                color_eyre::eyre::bail!("Quantity for SKU '{}' must be greater than 0", item.sku);
+            }
+        }
+        let mut items = resolve_duplicate_items(items, self.duplicate_item_policy)?;
+        if self.normalize_item_names {
+            for item in &mut items {
+                item.item_name = normalize_item_name(&item.item_name);
+            }
+        }
+
+        let mut tx = self.begin().await?;
+        let day = created_at.unwrap_or_else(Utc::now).date_naive();
+        let order_id = next_order_id(&mut tx, self.order_id_format, day).await?;
+
+        // A concurrent attempt with the same key wins; hand back its order
+        let row = sqlx::query(
+            "INSERT INTO orders (id, status, created_at, idempotency_key) VALUES ($1, $2, COALESCE($3, NOW()), $4) \
+             ON CONFLICT (idempotency_key) DO NOTHING RETURNING created_at",
+        )
+        .bind(&order_id)
+        .bind(status.as_str())
+        .bind(created_at)
+        .bind(idempotency_key)
+        .fetch_optional(&mut tx)
+        .await
+        .context("Failed to insert order")?;
+        let Some(row) = row else {
+            tx.rollback().await.context("Failed to roll back duplicate order")?;
+            let key = idempotency_key.unwrap_or_default();
+            return self
+                .fetch_order_by_idempotency_key(key)
+                .await?
+                .ok_or_else(|| color_eyre::eyre::eyre!("Order with idempotency key '{}' disappeared", key));
+        };
+        let created_at = row.try_get("created_at")?;
+
+        // Prices and unit conversions are captured now so later inventory changes don't rewrite history
+        let skus: Vec<&str> = items.iter().map(|item| item.sku.as_str()).collect();
+        let stock: HashMap<String, InventoryItem> = sqlx::query_as::<_, InventoryItem>(
+            "SELECT sku, name, category, quantity, unit_price, reorder_threshold, unit, case_quantity, pallet_quantity \
+             FROM inventory_items WHERE sku = ANY($1)",
+        )
+        .bind(&skus)
+        .fetch_all(&mut tx)
+        .await
+        .context("Failed to look up unit prices")?
+        .into_iter()
+        .map(|item| (item.sku.clone(), item))
+        .collect();
+
+        let mut order_items = Vec::with_capacity(items.len());
+        for item in &items {
+            // SKUs not in inventory are priced at zero and only sold by the each
+            let (conversion_factor, unit_price) = match stock.get(&item.sku) {
+                Some(stocked) => (stocked.conversion_factor(item.unit), stocked.unit_price),
+                None => ((item.unit == UnitOfMeasure::Each).then_some(1), Decimal::ZERO),
+            };
+            let Some(conversion_factor) = conversion_factor else {
+                return Err(WmsDbError::UnitNotSellable { sku: item.sku.clone(), unit: item.unit }.into());
+            };
+            let order_item = OrderItem::new(
+                &item.sku,
+                &item.item_name,
+                item.quantity,
+                unit_price * Decimal::from(conversion_factor),
+            )
+            .with_unit(item.unit, conversion_factor);
+            sqlx::query(
+                "INSERT INTO order_items (order_id, sku, item_name, quantity, unit_price, total_price, unit, conversion_factor) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            )
+            .bind(&order_id)
+            .bind(&order_item.sku)
+            .bind(&order_item.item_name)
+            .bind(order_item.quantity)
+            .bind(order_item.unit_price)
+            .bind(order_item.total_price)
+            .bind(order_item.unit)
+            .bind(order_item.conversion_factor)
+            .execute(&mut tx)
+            .await
+            .with_context(|| format!("Failed to insert order item '{}'", item.sku))?;
+            order_items.push(order_item);
+        }
+
+        tx.commit().await.context("Failed to commit order")?;
+        info!("Created order {} with {} item(s)", order_id, items.len());
+
+        let order = Order {
+            id: order_id,
+            status,
+            created_at,
+            sla_deadline: None,
+            items: order_items,
+        };
+        self.event_sink.order_created(&OrderCreated::for_order(&order));
+        Ok(order)
+    }
+
+    /// Fetch an order and its line items, or `None` if it does not exist
+    pub async fn get_order(&self, id: &str) -> Result<Option<Order>> {
+        self.timed("get_order", self.fetch_order(id)).await
+    }
+
+    /// Whether an order with this ID exists, without loading it or its items
+    pub async fn order_exists(&self, id: &str) -> Result<bool> {
+        self.timed("order_exists", async {
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM orders WHERE id = $1)")
+                .bind(id)
+                .fetch_one(&mut *self.acquire().await?)
+                .await
+                .with_context(|| format!("Failed to check whether order {} exists", id))
+        })
+        .await
+    }
+
+    async fn fetch_order(&self, id: &str) -> Result<Option<Order>> {
+        let row = sqlx::query("SELECT id, status, created_at, sla_deadline FROM orders WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut *self.acquire().await?)
+            .await
+            .context("Failed to fetch order")?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let mut order = order_from_row(&row)?;
+        order.items = self.fetch_order_items(id).await?;
+        Ok(Some(order))
+    }
+
+    /// List all orders with their line items, newest first
+    pub async fn list_orders(&self) -> Result<Vec<Order>> {
+        self.list_orders_sorted(OrderSort::CreatedAtDesc).await
+    }
+
+    /// List all orders with their line items in the given order
+    pub async fn list_orders_sorted(&self, sort: OrderSort) -> Result<Vec<Order>> {
+        self.timed("list_orders", self.fetch_orders_sorted(sort)).await
+    }
+
+    async fn fetch_orders_sorted(&self, sort: OrderSort) -> Result<Vec<Order>> {
+        let rows = sqlx::query(&format!("SELECT id, status, created_at, sla_deadline FROM orders {}", sort.order_by_sql()))
+            .fetch_all(&mut *self.acquire().await?)
+            .await
+            .context("Failed to list orders")?;
+
+        let mut orders = rows.iter().map(order_from_row).collect::<Result<Vec<_>>>()?;
+        self.attach_items(&mut orders).await?;
+        Ok(orders)
+    }
+
+    /// Set or clear the deadline by which an order must ship
+    pub async fn set_sla_deadline(&self, order_id: &str, deadline: Option<DateTime<Utc>>) -> Result<()> {
+        self.timed("set_sla_deadline", async {
+            let result = sqlx::query("UPDATE orders SET sla_deadline = $2 WHERE id = $1")
+                .bind(order_id)
+                .bind(deadline)
+                .execute(&mut *self.acquire().await?)
+                .await
+                .with_context(|| format!("Failed to set SLA deadline of order {}", order_id))?;
+            if result.rows_affected() == 0 {
+                color_eyre::eyre::bail!("Order {} not found", order_id);
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Orders past their SLA deadline that have not shipped, most overdue first
+    ///
+    /// Matches [`Order::is_sla_breached`]: only pending and confirmed orders count.
+    pub async fn breached_orders(&self) -> Result<Vec<Order>> {
+        self.timed("breached_orders", async {
+            let rows = sqlx::query(
+                "SELECT id, status, created_at, sla_deadline FROM orders \
+                 WHERE sla_deadline < NOW() AND status IN ('pending', 'confirmed') \
+                 ORDER BY sla_deadline, id",
+            )
+            .fetch_all(&mut *self.acquire().await?)
+            .await
+            .context("Failed to list SLA breaches")?;
+
+            let mut orders = rows.iter().map(order_from_row).collect::<Result<Vec<_>>>()?;
+            self.attach_items(&mut orders).await?;
+            Ok(orders)
+        })
+        .await
+    }
+
+    /// Number of orders in each status, counted in a single query
+    ///
+    /// Every [`OrderStatus`] is present in the map, with 0 for statuses no order has.
+    pub async fn count_orders_by_status(&self) -> Result<HashMap<OrderStatus, i64>> {
+        self.timed("count_orders_by_status", async {
+            let rows: Vec<(String, i64)> = sqlx::query_as("SELECT status, COUNT(*) FROM orders GROUP BY status")
+                .fetch_all(&mut *self.acquire().await?)
+                .await
+                .context("Failed to count orders by status")?;
+
+            let mut counts: HashMap<OrderStatus, i64> = OrderStatus::ALL.into_iter().map(|status| (status, 0)).collect();
+            for (status, count) in rows {
+                counts.insert(status.parse()?, count);
+            }
+            Ok(counts)
+        })
+        .await
+    }
+
+    /// Delete an order together with its line items and tags in one transaction
+    ///
+    /// Returns the total number of rows removed, 0 if the order does not exist.
+    pub async fn delete_order(&self, id: &str) -> Result<u64> {
+        self.timed("delete_order", async {
+            let mut tx = self.begin().await?;
+
+            let mut removed = 0;
+            for (table, column) in [("order_items", "order_id"), ("order_tags", "order_id"), ("orders", "id")] {
+                removed += sqlx::query(&format!("DELETE FROM {} WHERE {} = $1", table, column))
+                    .bind(id)
+                    .execute(&mut tx)
+                    .await
+                    .with_context(|| format!("Failed to delete {} of order {}", table, id))?
+                    .rows_affected();
+            }
+
+            tx.commit().await.context("Failed to commit order deletion")?;
+            info!("Deleted order {} ({} row(s))", id, removed);
+            Ok(removed)
+        })
+        .await
+    }
+
+    /// Tag an order; adding a tag it already has is a no-op
+    pub async fn add_order_tag(&self, order_id: &str, tag: &str) -> Result<()> {
+        self.timed("add_order_tag", async {
+            let tag = normalize_order_tag(tag)?;
+            let result = sqlx::query("INSERT INTO order_tags (order_id, tag) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+                .bind(order_id)
+                .bind(&tag)
+                .execute(&mut *self.acquire().await?)
+                .await;
+
+            match result {
+                Ok(_) => Ok(()),
+                Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some(FOREIGN_KEY_VIOLATION) => {
+                    color_eyre::eyre::bail!("Order {} not found", order_id)
+                }
+                Err(e) => Err(e).with_context(|| format!("Failed to tag order {} with '{}'", order_id, tag)),
+            }
+        })
+        .await
+    }
+
+    /// Tags of an order, alphabetically
+    pub async fn get_order_tags(&self, order_id: &str) -> Result<Vec<String>> {
+        self.timed("get_order_tags", async {
+            sqlx::query_scalar("SELECT tag FROM order_tags WHERE order_id = $1 ORDER BY tag")
+                .bind(order_id)
+                .fetch_all(&mut *self.acquire().await?)
+                .await
+                .context("Failed to fetch order tags")
+        })
+        .await
+    }
+
+    /// List the orders carrying `tag` with their line items, newest first
+    pub async fn list_orders_with_tag(&self, tag: &str) -> Result<Vec<Order>> {
+        self.timed("list_orders_with_tag", async {
+            let rows = sqlx::query(
+                "SELECT o.id, o.status, o.created_at, o.sla_deadline FROM orders o \
+                 JOIN order_tags t ON t.order_id = o.id \
+                 WHERE t.tag = $1 ORDER BY o.created_at DESC, o.id",
+            )
+            .bind(tag.trim())
+            .fetch_all(&mut *self.acquire().await?)
+            .await
+            .context("Failed to list orders by tag")?;
+
+            let mut orders = rows.iter().map(order_from_row).collect::<Result<Vec<_>>>()?;
+            self.attach_items(&mut orders).await?;
+            Ok(orders)
+        })
+        .await
+    }
+
+    /// Fetch several orders with their line items in one round trip
+    ///
+    /// IDs that do not exist are skipped. The result order is unspecified and
+    /// does not follow the order of `ids`.
+    pub async fn get_orders(&self, ids: &[&str]) -> Result<Vec<Order>> {
+        self.timed("get_orders", async {
+            if ids.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let rows = sqlx::query("SELECT id, status, created_at, sla_deadline FROM orders WHERE id = ANY($1)")
+                .bind(ids)
+                .fetch_all(&mut *self.acquire().await?)
+                .await
+                .context("Failed to fetch orders")?;
+
+            let mut orders = rows.iter().map(order_from_row).collect::<Result<Vec<_>>>()?;
+            self.attach_items(&mut orders).await?;
+            Ok(orders)
+        })
+        .await
+    }
+
+    /// Move several orders to `new_status` at once, e.g. a wave shipped together
+    ///
+    /// Either every order moves or none does: the orders are locked and checked
+    /// with [`OrderStatus::can_transition_to`] inside one transaction, then
+    /// updated in a single statement. Fails with
+    /// `WmsDbError::IllegalStatusTransition` naming the first order that may not
+    /// move, or if an ID does not exist. Returns how many orders were updated.
+    pub async fn update_orders_status(&self, ids: &[&str], new_status: OrderStatus) -> Result<u64> {
+        self.timed("update_orders_status", async {
+            if ids.is_empty() {
+                return Ok(0);
+            }
+
+            let mut tx = self.begin().await?;
+            let current: Vec<(String, String)> =
+                sqlx::query_as("SELECT id, status FROM orders WHERE id = ANY($1) ORDER BY id FOR UPDATE")
+                    .bind(ids)
+                    .fetch_all(&mut tx)
+                    .await
+                    .context("Failed to lock orders for status update")?;
+
+            let found: HashMap<&str, &str> = current.iter().map(|(id, status)| (id.as_str(), status.as_str())).collect();
+            for &id in ids {
+                let Some(status) = found.get(id) else {
+                    color_eyre::eyre::bail!("Order {} not found", id);
+                };
+                let from: OrderStatus = status.parse()?;
+                if !from.can_transition_to(new_status) {
+                    return Err(WmsDbError::IllegalStatusTransition {
+                        order_id: id.to_string(),
+                        from,
+                        to: new_status,
+                    }
+                    .into());
+                }
+            }
+
+            let updated = sqlx::query("UPDATE orders SET status = $2 WHERE id = ANY($1)")
+                .bind(ids)
+                .bind(new_status.as_str())
+                .execute(&mut tx)
+                .await
+                .context("Failed to update order statuses")?
+                .rows_affected();
+            tx.commit().await.context("Failed to commit status update")?;
+            info!("Moved {} order(s) to {}", updated, new_status);
+            Ok(updated)
+        })
+        .await
+    }
+
+    /// Load the line items for a set of orders in one query
+    async fn attach_items(&self, orders: &mut [Order]) -> Result<()> {
+        let ids: Vec<String> = orders.iter().map(|order| order.id.clone()).collect();
+        let rows = sqlx::query(
+            "SELECT order_id, sku, item_name, quantity, picked_quantity, unit_price, total_price, unit, conversion_factor FROM order_items \
+             WHERE order_id = ANY($1) ORDER BY id",
+        )
+        .bind(&ids)
+        .fetch_all(&mut *self.acquire().await?)
+        .await
+        .context("Failed to fetch order items")?;
+
+        let mut items: HashMap<String, Vec<OrderItem>> = HashMap::new();
+        for row in rows {
+            let order_id: String = row.try_get("order_id")?;
+            items.entry(order_id).or_default().push(OrderItem::from_row(&row)?);
+        }
+        for order in orders.iter_mut() {
+            order.items = items.remove(&order.id).unwrap_or_default();
+        }
+        Ok(())
+    }
+
+    /// Fetch the line items of an order
+    pub async fn get_order_items(&self, order_id: &str) -> Result<Vec<OrderItem>> {
+        self.timed("get_order_items", self.fetch_order_items(order_id)).await
+    }
+
+    async fn fetch_order_items(&self, order_id: &str) -> Result<Vec<OrderItem>> {
+        sqlx::query_as::<_, OrderItem>(
+            "SELECT sku, item_name, quantity, picked_quantity, unit_price, total_price, unit, conversion_factor FROM order_items \
+             WHERE order_id = $1 ORDER BY id",
+        )
+        .bind(order_id)
+        .fetch_all(&mut *self.acquire().await?)
+        .await
+        .context("Failed to fetch order items")
+    }
+
+    /// Item names used on order lines, alphabetically, for typeahead
+    ///
+    /// With a `prefix`, only names starting with it (case-insensitive) are
+    /// returned; `%` and `_` in the prefix match literally.
+    pub async fn distinct_item_names(&self, prefix: Option<&str>, limit: i64) -> Result<Vec<String>> {
+        self.timed("distinct_item_names", async {
+            if limit < 0 {
+                color_eyre::eyre::bail!("Limit cannot be negative");
+            }
+            let pattern = format!("{}%", escape_like(prefix.unwrap_or_default()));
+
+            sqlx::query_scalar(
+                "SELECT DISTINCT item_name FROM order_items WHERE item_name ILIKE $1 \
+                 ORDER BY item_name LIMIT $2",
+            )
+            .bind(pattern)
+            .bind(limit)
+            .fetch_all(&mut *self.acquire().await?)
+            .await
+            .context("Failed to list item names")
+        })
+        .await
+    }
+
+    /// Set the picked quantity of an order line
+    ///
+    /// `picked_quantity` is the absolute number of units picked so far and may not
+    /// exceed the ordered quantity.
+    pub async fn update_picked(&self, order_id: &str, sku: &str, picked_quantity: i32) -> Result<OrderItem> {
+        self.timed("update_picked", async {
+            if picked_quantity < 0 {
+                color_eyre::eyre::bail!("Picked quantity cannot be negative");
+            }
+
+            let item = sqlx::query_as::<_, OrderItem>(
+                "UPDATE order_items SET picked_quantity = $3 \
+                 WHERE order_id = $1 AND sku = $2 AND quantity >= $3 \
+                 RETURNING sku, item_name, quantity, picked_quantity, unit_price, total_price, unit, conversion_factor",
+            )
+            .bind(order_id)
+            .bind(sku)
+            .bind(picked_quantity)
+            .fetch_optional(&mut *self.acquire().await?)
+            .await
+            .context("Failed to update picked quantity")?;
+
+            match item {
+                Some(item) => Ok(item),
+                None => {
+                    let exists = self
+                        .fetch_order_items(order_id)
+                        .await?
+                        .into_iter()
+                        .find(|item| item.sku == sku);
+                    match exists {
+                        Some(item) => color_eyre::eyre::bail!(
+                            "Picked quantity {} exceeds ordered quantity {} for SKU '{}'",
+                            picked_quantity,
+                            item.quantity,
+                            sku
+                        ),
+                        None => color_eyre::eyre::bail!("Order {} has no item with SKU '{}'", order_id, sku),
+                    }
+                }
+            }
+        })
+        .await
+    }
+}
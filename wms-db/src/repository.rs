@@ -0,0 +1,103 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::Result;
+
+use crate::models::{NewOrderItem, Order, OrderItem, OrderSort};
+use crate::Database;
+
+/// Storage operations on orders
+///
+/// Implemented by [`Database`]; command handlers take `&dyn OrderRepository`
+/// so they can be exercised against an in-memory implementation in tests.
+#[async_trait]
+pub trait OrderRepository: Send + Sync {
+    /// Create a new order with the given line items
+    async fn create_order(&self, items: &[NewOrderItem]) -> Result<Order>;
+
+    /// Create a new order starting in `initial_status` (an `OrderStatus` name), `pending` if `None`
+    async fn create_order_with_status(&self, items: &[NewOrderItem], initial_status: Option<&str>) -> Result<Order>;
+
+    /// Create an order at most once per `idempotency_key`, returning the existing order on a repeat
+    async fn create_order_idempotent(
+        &self,
+        items: &[NewOrderItem],
+        initial_status: Option<&str>,
+        idempotency_key: &str,
+    ) -> Result<Order>;
+
+    /// Fetch an order and its line items, or `None` if it does not exist
+    async fn get_order(&self, id: &str) -> Result<Option<Order>>;
+
+    /// List all orders, newest first
+    async fn list_orders(&self) -> Result<Vec<Order>>;
+
+    /// List all orders in the given order
+    async fn list_orders_sorted(&self, sort: OrderSort) -> Result<Vec<Order>>;
+
+    /// Set the picked quantity of an order line
+    async fn update_picked(&self, order_id: &str, sku: &str, picked_quantity: i32) -> Result<OrderItem>;
+
+    /// Tag an order; adding a tag it already has is a no-op
+    async fn add_order_tag(&self, order_id: &str, tag: &str) -> Result<()>;
+
+    /// Delete an order and everything attached to it, returning the rows removed
+    async fn delete_order(&self, id: &str) -> Result<u64>;
+
+    /// Set or clear the deadline by which an order must ship
+    async fn set_sla_deadline(&self, order_id: &str, deadline: Option<DateTime<Utc>>) -> Result<()>;
+
+    /// Unshipped orders past their SLA deadline, most overdue first
+    async fn breached_orders(&self) -> Result<Vec<Order>>;
+}
+
+#[async_trait]
+impl OrderRepository for Database {
+    async fn create_order(&self, items: &[NewOrderItem]) -> Result<Order> {
+        self.create_order_with_items(items).await
+    }
+
+    async fn create_order_with_status(&self, items: &[NewOrderItem], initial_status: Option<&str>) -> Result<Order> {
+        Database::create_order_with_status(self, items, initial_status).await
+    }
+
+    async fn create_order_idempotent(
+        &self,
+        items: &[NewOrderItem],
+        initial_status: Option<&str>,
+        idempotency_key: &str,
+    ) -> Result<Order> {
+        Database::create_order_idempotent(self, items, initial_status, idempotency_key).await
+    }
+
+    async fn get_order(&self, id: &str) -> Result<Option<Order>> {
+        Database::get_order(self, id).await
+    }
+
+    async fn list_orders(&self) -> Result<Vec<Order>> {
+        Database::list_orders(self).await
+    }
+
+    async fn list_orders_sorted(&self, sort: OrderSort) -> Result<Vec<Order>> {
+        Database::list_orders_sorted(self, sort).await
+    }
+
+    async fn update_picked(&self, order_id: &str, sku: &str, picked_quantity: i32) -> Result<OrderItem> {
+        Database::update_picked(self, order_id, sku, picked_quantity).await
+    }
+
+    async fn add_order_tag(&self, order_id: &str, tag: &str) -> Result<()> {
+        Database::add_order_tag(self, order_id, tag).await
+    }
+
+    async fn delete_order(&self, id: &str) -> Result<u64> {
+        Database::delete_order(self, id).await
+    }
+
+    async fn set_sla_deadline(&self, order_id: &str, deadline: Option<DateTime<Utc>>) -> Result<()> {
+        Database::set_sla_deadline(self, order_id, deadline).await
+    }
+
+    async fn breached_orders(&self) -> Result<Vec<Order>> {
+        Database::breached_orders(self).await
+    }
+}
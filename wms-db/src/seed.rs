@@ -0,0 +1,240 @@
+use color_eyre::eyre::{Context, Result};
+use rust_decimal::Decimal;
+use sqlx::{Postgres, Transaction};
+use tracing::info;
+
+use crate::models::{OrderItem, OrderStatus};
+use crate::Database;
+
+/// Demo inventory and orders use these prefixes, and demo workers and tasks are
+/// flagged `is_demo`, so they can be found and removed without touching real data
+const DEMO_SKU_PREFIX: &str = "DEMO-";
+const DEMO_ORDER_PREFIX: &str = "ORD-DEMO-";
+
+struct DemoItem {
+    sku: &'static str,
+    name: &'static str,
+    category: &'static str,
+    quantity: i32,
+    unit_price_cents: i64,
+}
+
+struct DemoWorker {
+    id: i32,
+    name: &'static str,
+    is_available: bool,
+    current_load: f64,
+    max_tasks: i32,
+    location: (f64, f64),
+}
+
+struct DemoTask {
+    id: i32,
+    location: (f64, f64),
+    priority: &'static str,
+    estimated_duration: Option<f64>,
+}
+
+struct DemoOrder {
+    id: &'static str,
+    status: OrderStatus,
+    /// `(sku, quantity)` of each line
+    lines: &'static [(&'static str, i32)],
+}
+
+const fn item(sku: &'static str, name: &'static str, category: &'static str, quantity: i32, unit_price_cents: i64) -> DemoItem {
+    DemoItem { sku, name, category, quantity, unit_price_cents }
+}
+
+const fn worker(id: i32, name: &'static str, is_available: bool, current_load: f64, max_tasks: i32, location: (f64, f64)) -> DemoWorker {
+    DemoWorker { id, name, is_available, current_load, max_tasks, location }
+}
+
+const fn task(id: i32, location: (f64, f64), priority: &'static str, estimated_duration: Option<f64>) -> DemoTask {
+    DemoTask { id, location, priority, estimated_duration }
+}
+
+const DEMO_INVENTORY: &[DemoItem] = &[
+    item("DEMO-001", "Wireless Mouse", "Electronics", 120, 2499),
+    item("DEMO-002", "USB-C Cable", "Electronics", 300, 999),
+    item("DEMO-003", "Work Gloves", "Apparel", 80, 1250),
+    item("DEMO-004", "Hex Bolt M8", "Hardware", 5000, 15),
+    item("DEMO-005", "Office Chair", "Furniture", 15, 14900),
+    item("DEMO-006", "Coffee Beans 1kg", "Food", 60, 1875),
+];
+
+const DEMO_WORKERS: &[DemoWorker] = &[
+    worker(9001, "Avery", true, 0.0, 3, (0.0, 0.0)),
+    worker(9002, "Jordan", true, 0.2, 3, (25.0, 10.0)),
+    worker(9003, "Sam", true, 0.5, 2, (50.0, 40.0)),
+    worker(9004, "Riley", false, 0.0, 3, (10.0, 45.0)),
+];
+
+const DEMO_TASKS: &[DemoTask] = &[
+    task(9001, (5.0, 3.0), "high", Some(15.0)),
+    task(9002, (22.0, 12.0), "medium", Some(30.0)),
+    task(9003, (48.0, 35.0), "critical", Some(10.0)),
+    task(9004, (30.0, 30.0), "low", None),
+    task(9005, (12.0, 40.0), "medium", Some(20.0)),
+    task(9006, (40.0, 5.0), "high", Some(25.0)),
+];
+
+const DEMO_ORDERS: &[DemoOrder] = &[
+    DemoOrder { id: "ORD-DEMO-0001", status: OrderStatus::Pending, lines: &[("DEMO-001", 2), ("DEMO-002", 3)] },
+    DemoOrder { id: "ORD-DEMO-0002", status: OrderStatus::Pending, lines: &[("DEMO-005", 1)] },
+    DemoOrder { id: "ORD-DEMO-0003", status: OrderStatus::Confirmed, lines: &[("DEMO-004", 200), ("DEMO-003", 4)] },
+];
+
+/// Result of seeding demo data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedOutcome {
+    /// Demo rows were inserted
+    Seeded,
+    /// Demo rows were already present, so nothing was inserted
+    AlreadySeeded,
+}
+
+impl Database {
+    /// Insert demo inventory, workers, tasks and orders in one transaction
+    ///
+    /// Idempotent: does nothing if demo data is already present, unless `reset`
+    /// is set, in which case existing demo rows are deleted first. Only demo rows
+    /// (`DEMO-` SKUs, `ORD-DEMO-` orders, workers and tasks flagged `is_demo`) are
+    /// ever touched. A demo row whose key is already taken by real data is
+    /// skipped rather than overwritten.
+    pub async fn seed_demo_data(&self, reset: bool) -> Result<SeedOutcome> {
+        self.timed("seed_demo_data", async {
+            let mut tx = self.begin().await?;
+
+            if reset {
+                clear_demo_data(&mut tx).await?;
+            }
+
+            let seeded: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM inventory_items WHERE sku = $1)")
+                .bind(DEMO_INVENTORY[0].sku)
+                .fetch_one(&mut tx)
+                .await
+                .context("Failed to check for demo data")?;
+            if seeded {
+                tx.rollback().await.context("Failed to roll back seed transaction")?;
+                info!("Demo data already present, skipping seed");
+                return Ok(SeedOutcome::AlreadySeeded);
+            }
+
+            for item in DEMO_INVENTORY {
+                sqlx::query(
+                    "INSERT INTO inventory_items (sku, name, category, quantity, unit_price) VALUES ($1, $2, $3, $4, $5) \
+                     ON CONFLICT (sku) DO NOTHING",
+                )
+                .bind(item.sku)
+                .bind(item.name)
+                .bind(item.category)
+                .bind(item.quantity)
+                .bind(Decimal::new(item.unit_price_cents, 2))
+                .execute(&mut tx)
+                .await
+                .with_context(|| format!("Failed to seed inventory item '{}'", item.sku))?;
+            }
+
+            for worker in DEMO_WORKERS {
+                sqlx::query(
+                    "INSERT INTO workers (id, name, is_available, current_load, max_tasks, location_x, location_y, is_demo) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, TRUE) ON CONFLICT (id) DO NOTHING",
+                )
+                .bind(worker.id)
+                .bind(worker.name)
+                .bind(worker.is_available)
+                .bind(worker.current_load)
+                .bind(worker.max_tasks)
+                .bind(worker.location.0)
+                .bind(worker.location.1)
+                .execute(&mut tx)
+                .await
+                .with_context(|| format!("Failed to seed worker {}", worker.id))?;
+            }
+
+            for task in DEMO_TASKS {
+                sqlx::query(
+                    "INSERT INTO tasks (id, location_x, location_y, priority, estimated_duration, is_demo) \
+                     VALUES ($1, $2, $3, $4, $5, TRUE) ON CONFLICT (id) DO NOTHING",
+                )
+                .bind(task.id)
+                .bind(task.location.0)
+                .bind(task.location.1)
+                .bind(task.priority)
+                .bind(task.estimated_duration)
+                .execute(&mut tx)
+                .await
+                .with_context(|| format!("Failed to seed task {}", task.id))?;
+            }
+
+            for order in DEMO_ORDERS {
+                sqlx::query("INSERT INTO orders (id, status) VALUES ($1, $2) ON CONFLICT (id) DO NOTHING")
+                    .bind(order.id)
+                    .bind(order.status.as_str())
+                    .execute(&mut tx)
+                    .await
+                    .with_context(|| format!("Failed to seed order {}", order.id))?;
+
+                for &(sku, quantity) in order.lines {
+                    let stocked = DEMO_INVENTORY
+                        .iter()
+                        .find(|item| item.sku == sku)
+                        .expect("demo orders only reference demo SKUs");
+                    let item = OrderItem::new(sku, stocked.name, quantity, Decimal::new(stocked.unit_price_cents, 2));
+                    sqlx::query(
+                        "INSERT INTO order_items (order_id, sku, item_name, quantity, unit_price, total_price) \
+                         VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (order_id, sku) DO NOTHING",
+                    )
+                    .bind(order.id)
+                    .bind(&item.sku)
+                    .bind(&item.item_name)
+                    .bind(item.quantity)
+                    .bind(item.unit_price)
+                    .bind(item.total_price)
+                    .execute(&mut tx)
+                    .await
+                    .with_context(|| format!("Failed to seed order item '{}' of {}", sku, order.id))?;
+                }
+            }
+
+            tx.commit().await.context("Failed to commit demo data")?;
+            info!(
+                "Seeded {} inventory item(s), {} worker(s), {} task(s) and {} order(s)",
+                DEMO_INVENTORY.len(),
+                DEMO_WORKERS.len(),
+                DEMO_TASKS.len(),
+                DEMO_ORDERS.len()
+            );
+            Ok(SeedOutcome::Seeded)
+        })
+        .await
+    }
+}
+
+/// Delete every demo row, children before parents
+async fn clear_demo_data(tx: &mut Transaction<'_, Postgres>) -> Result<()> {
+    let order_pattern = format!("{}%", DEMO_ORDER_PREFIX);
+    sqlx::query("DELETE FROM order_items WHERE order_id LIKE $1")
+        .bind(&order_pattern)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to clear demo order items")?;
+    sqlx::query("DELETE FROM orders WHERE id LIKE $1")
+        .bind(&order_pattern)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to clear demo orders")?;
+    sqlx::query("DELETE FROM inventory_items WHERE sku LIKE $1")
+        .bind(format!("{}%", DEMO_SKU_PREFIX))
+        .execute(&mut *tx)
+        .await
+        .context("Failed to clear demo inventory")?;
+    for table in ["workers", "tasks"] {
+        sqlx::query(&format!("DELETE FROM {} WHERE is_demo", table))
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("Failed to clear demo {}", table))?;
+    }
+    Ok(())
+}
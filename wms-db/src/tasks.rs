@@ -0,0 +1,31 @@
+use color_eyre::eyre::{Context, Result};
+use sqlx::Row;
+
+use crate::models::TaskRecord;
+use crate::Database;
+
+impl Database {
+    /// List all stored tasks, ordered by ID
+    pub async fn list_tasks(&self) -> Result<Vec<TaskRecord>> {
+        self.timed("list_tasks", async {
+            let rows = sqlx::query(
+                "SELECT id, location_x, location_y, priority, estimated_duration FROM tasks ORDER BY id",
+            )
+            .fetch_all(&mut *self.acquire().await?)
+            .await
+            .context("Failed to list tasks")?;
+
+            rows.iter()
+                .map(|row| {
+                    Ok(TaskRecord {
+                        id: row.try_get("id")?,
+                        location: (row.try_get("location_x")?, row.try_get("location_y")?),
+                        priority: row.try_get("priority")?,
+                        estimated_duration: row.try_get("estimated_duration")?,
+                    })
+                })
+                .collect()
+        })
+        .await
+    }
+}
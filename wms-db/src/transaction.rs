@@ -0,0 +1,109 @@
+use color_eyre::eyre::{Context, Report, Result};
+use sqlx::{Postgres, Transaction};
+use std::fmt;
+
+use crate::Database;
+
+/// Postgres SQLSTATE for serialization failures
+const SERIALIZATION_FAILURE: &str = "40001";
+
+/// Postgres SQLSTATE for deadlocks
+const DEADLOCK_DETECTED: &str = "40P01";
+
+/// Transaction isolation level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationLevel {
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+impl fmt::Display for IsolationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_sql())
+    }
+}
+
+/// Whether an error was caused by a serialization failure that is safe to retry
+pub fn is_serialization_failure(err: &Report) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<sqlx::Error>(),
+            Some(sqlx::Error::Database(db_err)) if db_err.code().as_deref() == Some(SERIALIZATION_FAILURE)
+        )
+    })
+}
+
+/// Whether an error is likely to go away if the operation is simply retried
+///
+/// Covers dropped or refused connections, pool timeouts, server restarts,
+/// deadlocks and serialization failures. A dropped connection may hide a
+/// commit that did succeed, so only retry writes that are idempotent.
+pub fn is_transient_error(err: &Report) -> bool {
+    err.chain().any(|cause| match cause.downcast_ref::<sqlx::Error>() {
+        Some(sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut) => true,
+        Some(sqlx::Error::Database(db_err)) => db_err.code().is_some_and(|code| {
+            // Class 08 is connection exceptions, 57P0x the server shutting down or restarting
+            code == SERIALIZATION_FAILURE || code == DEADLOCK_DETECTED || code.starts_with("08") || code.starts_with("57P0")
+        }),
+        _ => false,
+    })
+}
+
+impl Database {
+    /// Begin a transaction running at the given isolation level
+    ///
+    /// Under `RepeatableRead` and `Serializable`, Postgres aborts transactions
+    /// that conflict with concurrent ones, either on a statement or at commit.
+    /// Callers must be prepared to retry the whole transaction when that happens;
+    /// [`is_serialization_failure`] identifies those errors.
+    pub async fn transaction_with_isolation(&self, level: IsolationLevel) -> Result<Transaction<'static, Postgres>> {
+        self.timed("transaction_with_isolation", async {
+            let mut tx = self.begin().await?;
+            sqlx::query(&format!("SET TRANSACTION ISOLATION LEVEL {}", level.as_sql()))
+                .execute(&mut tx)
+                .await
+                .with_context(|| format!("Failed to set isolation level {}", level))?;
+            Ok(tx)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isolation_level_sql() {
+        assert_eq!(IsolationLevel::Serializable.as_sql(), "SERIALIZABLE");
+        assert_eq!(IsolationLevel::RepeatableRead.to_string(), "REPEATABLE READ");
+    }
+
+    #[test]
+    fn test_non_database_error_is_not_serialization_failure() {
+        let err: Report = sqlx::Error::RowNotFound.into();
+        assert!(!is_serialization_failure(&err));
+        assert!(!is_serialization_failure(&color_eyre::eyre::eyre!("boom")));
+    }
+
+    #[test]
+    fn test_connection_errors_are_transient() {
+        let reset = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset");
+        let err = Report::from(sqlx::Error::Io(reset)).wrap_err("Failed to commit order");
+        assert!(is_transient_error(&err));
+        assert!(is_transient_error(&sqlx::Error::PoolTimedOut.into()));
+        assert!(!is_transient_error(&sqlx::Error::RowNotFound.into()));
+        assert!(!is_transient_error(&color_eyre::eyre::eyre!("Quantity must be greater than 0")));
+    }
+}
@@ -0,0 +1,143 @@
+use color_eyre::eyre::{Context, Result};
+use sqlx::Row;
+use std::time::Duration;
+
+use crate::error::WmsDbError;
+use crate::models::WorkerRecord;
+use crate::Database;
+
+impl Database {
+    /// Insert a worker or replace its stored state
+    pub async fn upsert_worker(&self, worker: &WorkerRecord) -> Result<()> {
+        self.timed("upsert_worker", async {
+            let (x, y) = worker.location.unzip();
+            sqlx::query(
+                "INSERT INTO workers (id, name, is_available, current_load, max_tasks, location_x, location_y, travel_speed) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+                 ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name, is_available = EXCLUDED.is_available, \
+                 current_load = EXCLUDED.current_load, max_tasks = EXCLUDED.max_tasks, \
+                 location_x = EXCLUDED.location_x, location_y = EXCLUDED.location_y, \
+                 travel_speed = EXCLUDED.travel_speed",
+            )
+            .bind(worker.id)
+            .bind(&worker.name)
+            .bind(worker.is_available)
+            .bind(worker.current_load)
+            .bind(worker.max_tasks)
+            .bind(x)
+            .bind(y)
+            .bind(worker.travel_speed)
+            .execute(&mut *self.acquire().await?)
+            .await
+            .with_context(|| format!("Failed to store worker {}", worker.id))?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Mark a worker as available for new tasks or not
+    ///
+    /// Fails with `WmsDbError::WorkerNotFound` if no worker has this ID.
+    pub async fn set_worker_available(&self, worker_id: i32, available: bool) -> Result<()> {
+        self.timed("set_worker_available", async {
+            let result = sqlx::query("UPDATE workers SET is_available = $2 WHERE id = $1")
+                .bind(worker_id)
+                .bind(available)
+                .execute(&mut *self.acquire().await?)
+                .await
+                .with_context(|| format!("Failed to update availability of worker {}", worker_id))?;
+
+            if result.rows_affected() == 0 {
+                return Err(WmsDbError::WorkerNotFound { worker_id }.into());
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Claim a worker for `ttl`, hiding it from [`list_workers`](Self::list_workers)
+    ///
+    /// Returns `false` if the worker is already reserved and the reservation
+    /// has not expired. An expired reservation counts as released, so the
+    /// claim lapses on its own if the caller dies. Fails with
+    /// `WmsDbError::WorkerNotFound` if no worker has this ID.
+    pub async fn reserve_worker(&self, worker_id: i32, ttl: Duration) -> Result<bool> {
+        self.timed("reserve_worker", async {
+            let result = sqlx::query(
+                "UPDATE workers SET reserved_until = NOW() + make_interval(secs => $2) \
+                 WHERE id = $1 AND (reserved_until IS NULL OR reserved_until <= NOW())",
+            )
+            .bind(worker_id)
+            .bind(ttl.as_secs_f64())
+            .execute(&mut *self.acquire().await?)
+            .await
+            .with_context(|| format!("Failed to reserve worker {}", worker_id))?;
+
+            if result.rows_affected() > 0 {
+                return Ok(true);
+            }
+            let exists: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM workers WHERE id = $1)")
+                .bind(worker_id)
+                .fetch_one(&mut *self.acquire().await?)
+                .await
+                .with_context(|| format!("Failed to look up worker {}", worker_id))?;
+            if !exists {
+                return Err(WmsDbError::WorkerNotFound { worker_id }.into());
+            }
+            Ok(false)
+        })
+        .await
+    }
+
+    /// Drop a worker's reservation, whoever made it
+    ///
+    /// Fails with `WmsDbError::WorkerNotFound` if no worker has this ID.
+    pub async fn release_worker(&self, worker_id: i32) -> Result<()> {
+        self.timed("release_worker", async {
+            let result = sqlx::query("UPDATE workers SET reserved_until = NULL WHERE id = $1")
+                .bind(worker_id)
+                .execute(&mut *self.acquire().await?)
+                .await
+                .with_context(|| format!("Failed to release worker {}", worker_id))?;
+
+            if result.rows_affected() == 0 {
+                return Err(WmsDbError::WorkerNotFound { worker_id }.into());
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// List workers with their persisted state, ordered by ID
+    ///
+    /// Workers under an unexpired [`reserve_worker`](Self::reserve_worker)
+    /// reservation are left out.
+    pub async fn list_workers(&self) -> Result<Vec<WorkerRecord>> {
+        self.timed("list_workers", async {
+            let rows = sqlx::query(
+                "SELECT id, name, is_available, current_load, max_tasks, location_x, location_y, travel_speed \
+                 FROM workers WHERE reserved_until IS NULL OR reserved_until <= NOW() ORDER BY id",
+            )
+            .fetch_all(&mut *self.acquire().await?)
+            .await
+            .context("Failed to list workers")?;
+
+            rows.iter()
+                .map(|row| {
+                    let x: Option<f64> = row.try_get("location_x")?;
+                    let y: Option<f64> = row.try_get("location_y")?;
+                    Ok(WorkerRecord {
+                        id: row.try_get("id")?,
+                        name: row.try_get("name")?,
+                        is_available: row.try_get("is_available")?,
+                        current_load: row.try_get("current_load")?,
+                        max_tasks: row.try_get("max_tasks")?,
+                        location: x.zip(y),
+                        travel_speed: row.try_get("travel_speed")?,
+                    })
+                })
+                .collect()
+        })
+        .await
+    }
+}
@@ -0,0 +1,5 @@
+-- Applies cleanly
+CREATE TABLE widgets (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL
+);
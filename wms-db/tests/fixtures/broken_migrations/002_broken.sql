@@ -0,0 +1,2 @@
+-- Deliberately invalid: the column type does not exist
+ALTER TABLE widgets ADD COLUMN weight NOT_A_TYPE;
@@ -0,0 +1,2 @@
+-- Never reached, since 002 fails first
+CREATE INDEX idx_widgets_name ON widgets(name);
@@ -0,0 +1,155 @@
+use crate::types::{Assignment, TaskId, WorkerId};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// A task, or one worker's part of it, that moved to a different worker between two plans
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssignmentChange {
+    pub old: Assignment,
+    pub new: Assignment,
+}
+
+/// Difference between two plans, keyed by task and worker
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlanDiff {
+    /// Assignments only in the new plan, not taking over from an old one
+    pub added: Vec<Assignment>,
+    /// Assignments only in the old plan, not taken over by a new one
+    pub removed: Vec<Assignment>,
+    /// Assignments of a task in both plans but to different workers
+    pub changed: Vec<AssignmentChange>,
+}
+
+impl PlanDiff {
+    /// Whether the two plans assign every task to the same worker
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare two plans so only affected workers need to be notified
+///
+/// Assignments are matched by task and worker ID; a task kept on the same
+/// worker is unchanged even if its estimated cost or share differs. A task
+/// held by several workers, a team or split task, is compared worker by
+/// worker: each worker newly on it takes over from one that left it, in plan
+/// order, as a change, and any left over are added or removed. Results follow
+/// the order of `new` (for added and changed) and `old` (for removed).
+pub fn diff_plans(old: &[Assignment], new: &[Assignment]) -> PlanDiff {
+    let old_pairs: HashSet<(TaskId, WorkerId)> = old.iter().map(|a| (a.task_id, a.worker_id)).collect();
+    let new_pairs: HashSet<(TaskId, WorkerId)> = new.iter().map(|a| (a.task_id, a.worker_id)).collect();
+
+    // Old assignments whose worker no longer holds the task, in plan order
+    let mut vacated: HashMap<TaskId, VecDeque<usize>> = HashMap::new();
+    for (index, assignment) in old.iter().enumerate() {
+        if !new_pairs.contains(&(assignment.task_id, assignment.worker_id)) {
+            vacated.entry(assignment.task_id).or_default().push_back(index);
+        }
+    }
+
+    let mut diff = PlanDiff::default();
+    let mut taken_over = HashSet::new();
+    for assignment in new {
+        if old_pairs.contains(&(assignment.task_id, assignment.worker_id)) {
+            continue;
+        }
+        match vacated.get_mut(&assignment.task_id).and_then(VecDeque::pop_front) {
+            Some(index) => {
+                taken_over.insert(index);
+                diff.changed.push(AssignmentChange {
+                    old: old[index].clone(),
+                    new: assignment.clone(),
+                });
+            }
+            None => diff.added.push(assignment.clone()),
+        }
+    }
+    diff.removed = old
+        .iter()
+        .enumerate()
+        .filter(|(index, a)| !new_pairs.contains(&(a.task_id, a.worker_id)) && !taken_over.contains(index))
+        .map(|(_, a)| a.clone())
+        .collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_moved_and_new_task() {
+        let old = vec![
+            Assignment::new(1, 10, 2.0),
+            Assignment::new(2, 20, 3.0),
+            Assignment::new(3, 30, 1.0),
+        ];
+        let new = vec![
+            Assignment::new(1, 10, 2.5), // same worker, new cost: unchanged
+            Assignment::new(2, 30, 4.0), // moved to another worker
+            Assignment::new(4, 20, 1.0), // newly assigned
+        ];
+
+        let diff = diff_plans(&old, &new);
+        assert_eq!(diff.added, vec![Assignment::new(4, 20, 1.0)]);
+        assert_eq!(diff.removed, vec![Assignment::new(3, 30, 1.0)]);
+        assert_eq!(
+            diff.changed,
+            vec![AssignmentChange {
+                old: Assignment::new(2, 20, 3.0),
+                new: Assignment::new(2, 30, 4.0),
+            }]
+        );
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_team_member_swapped() {
+        let old = vec![Assignment::new(5, 1, 1.0), Assignment::new(5, 2, 1.0)];
+        let new = vec![Assignment::new(5, 1, 1.0), Assignment::new(5, 3, 2.0)];
+
+        let diff = diff_plans(&old, &new);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(
+            diff.changed,
+            vec![AssignmentChange {
+                old: Assignment::new(5, 2, 1.0),
+                new: Assignment::new(5, 3, 2.0),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_split_task_gains_and_loses_workers() {
+        let old = vec![
+            Assignment::new(7, 1, 1.0).with_share(2.0),
+            Assignment::new(7, 2, 1.0).with_share(1.0),
+        ];
+        // Worker 1 keeps a smaller share, worker 2 drops out, workers 3 and 4 join
+        let new = vec![
+            Assignment::new(7, 1, 1.0).with_share(1.0),
+            Assignment::new(7, 3, 1.0).with_share(1.0),
+            Assignment::new(7, 4, 1.0).with_share(1.0),
+        ];
+
+        let diff = diff_plans(&old, &new);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!((diff.changed[0].old.worker_id, diff.changed[0].new.worker_id), (2, 3));
+        assert_eq!(diff.added, vec![Assignment::new(7, 4, 1.0).with_share(1.0)]);
+        assert!(diff.removed.is_empty());
+
+        // Back to a single worker: one old part is taken over, the other is removed
+        let diff = diff_plans(&new, &[Assignment::new(7, 5, 3.0)]);
+        assert_eq!((diff.changed[0].old.worker_id, diff.changed[0].new.worker_id), (1, 5));
+        let removed: Vec<WorkerId> = diff.removed.iter().map(|a| a.worker_id).collect();
+        assert_eq!(removed, [3, 4]);
+        assert!(diff.added.is_empty());
+    }
+
+    #[test]
+    fn test_diff_identical_plans_is_empty() {
+        let plan = vec![Assignment::new(1, 10, 2.0)];
+        assert!(diff_plans(&plan, &plan).is_empty());
+    }
+}
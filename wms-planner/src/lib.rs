@@ -1,10 +1,25 @@
+pub mod diff;
 pub mod planner;
+pub mod schema;
+pub mod shift;
+pub mod summary;
+pub mod timed;
 pub mod types;
+pub mod validation;
 
 // Re-export commonly used items
+pub use diff::{diff_plans, AssignmentChange, PlanDiff};
 pub use planner::traits::TaskPlanner;
 pub use planner::greedy::GreedyPlanner;
-pub use types::{Task, Worker, Assignment, Location, Priority, TaskId, WorkerId};
+pub use planner::cache::CachingPlanner;
+pub use planner::min_travel::{route_distance, MinTravelPlan, MinTravelPlanner};
+pub use planner::observer::{AssignmentLog, NoopObserver, PlannerObserver, RejectionReason};
+pub use schema::schema;
+pub use shift::{reset_worker_loads, ShiftPlanner, ShiftSchedule};
+pub use summary::{gini_coefficient, PlanSummary, WorkerSummary};
+pub use timed::{estimate_makespan, worker_finish_times, TimeAwarePlanner, TimedPlan};
+pub use types::{path_length, Task, Worker, Assignment, Location, Priority, Proficiency, Skill, TaskId, Velocity, WorkerId};
+pub use validation::{dependency_order, input_problems, unknown_skills, validate_input, InputError};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,221 @@
+use crate::planner::traits::TaskPlanner;
+use crate::types::{Assignment, Location, Task, Worker};
+use chrono::{DateTime, Utc};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+/// Number of distinct inputs a `CachingPlanner` remembers by default
+pub const DEFAULT_PLAN_CACHE_CAPACITY: usize = 16;
+
+/// A remembered input and the plan produced for it
+#[derive(Debug)]
+struct CacheEntry {
+    hash: u64,
+    tasks: Vec<Task>,
+    workers: Vec<Worker>,
+    assignments: Vec<Assignment>,
+}
+
+/// Opt-in wrapper that returns the previous plan when the same tasks and workers
+/// are planned again
+///
+/// Inputs are looked up by a content hash and confirmed with a full equality
+/// check, so a hash collision never returns the wrong plan. The wrapped planner
+/// (including its cost estimator) cannot change once wrapped, so it does not
+/// need to be part of the key. Estimators that read the clock, such as
+/// `TimeCostEstimator` without a fixed `now`, will keep returning the cached
+/// plan as time passes. The least recently stored entry is evicted once
+/// `capacity` inputs are cached.
+#[derive(Debug)]
+pub struct CachingPlanner<P> {
+    planner: P,
+    capacity: usize,
+    entries: Mutex<VecDeque<CacheEntry>>,
+}
+
+impl<P: TaskPlanner> CachingPlanner<P> {
+    pub fn new(planner: P) -> Self {
+        Self {
+            planner,
+            capacity: DEFAULT_PLAN_CACHE_CAPACITY,
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Remember at most `capacity` inputs (at least one)
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Forget every cached plan
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// Number of cached plans
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<P: TaskPlanner> TaskPlanner for CachingPlanner<P> {
+    fn plan(&self, tasks: &[Task], workers: &[Worker]) -> Vec<Assignment> {
+        let hash = content_hash(tasks, workers);
+        {
+            let entries = self.entries.lock().unwrap();
+            let cached = entries
+                .iter()
+                .find(|entry| entry.hash == hash && entry.tasks == tasks && entry.workers == workers);
+            if let Some(entry) = cached {
+                return entry.assignments.clone();
+            }
+        }
+
+        // Planned without holding the lock so concurrent callers aren't serialized
+        let assignments = self.planner.plan(tasks, workers);
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(CacheEntry {
+            hash,
+            tasks: tasks.to_vec(),
+            workers: workers.to_vec(),
+            assignments: assignments.clone(),
+        });
+        assignments
+    }
+
+    /// Not cached, since the same input can plan differently at another time
+    fn plan_at(&self, tasks: &[Task], workers: &[Worker], now: DateTime<Utc>) -> Vec<Assignment> {
+        self.planner.plan_at(tasks, workers, now)
+    }
+}
+
+/// Hash of every planning-relevant field, independent of set and map iteration order
+fn content_hash(tasks: &[Task], workers: &[Worker]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    tasks.len().hash(&mut hasher);
+    for task in tasks {
+        task.id.hash(&mut hasher);
+        hash_location(&task.location, &mut hasher);
+        task.priority.to_numeric().hash(&mut hasher);
+        task.estimated_duration.map(f64::to_bits).hash(&mut hasher);
+        let mut incompatible: Vec<_> = task.incompatible_with.iter().collect();
+        incompatible.sort();
+        incompatible.hash(&mut hasher);
+        task.required_skill.hash(&mut hasher);
+        task.size.to_bits().hash(&mut hasher);
+        task.preferred_worker.hash(&mut hasher);
+        match &task.dropoff {
+            Some(dropoff) => hash_location(dropoff, &mut hasher),
+            None => 0u8.hash(&mut hasher),
+        }
+    }
+
+    workers.len().hash(&mut hasher);
+    for worker in workers {
+        worker.id.hash(&mut hasher);
+        hash_location(&worker.location, &mut hasher);
+        worker.is_available.hash(&mut hasher);
+        worker.current_load.to_bits().hash(&mut hasher);
+        worker.max_tasks.hash(&mut hasher);
+        worker.busy_until.hash(&mut hasher);
+        let mut skills: Vec<_> = worker.skills.iter().collect();
+        skills.sort();
+        skills.hash(&mut hasher);
+        worker.capacity.to_bits().hash(&mut hasher);
+        worker
+            .velocity
+            .map(|velocity| (velocity.dx.to_bits(), velocity.dy.to_bits()))
+            .hash(&mut hasher);
+        worker.located_at.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn hash_location(location: &Location, hasher: &mut DefaultHasher) {
+    location.x.to_bits().hash(hasher);
+    location.y.to_bits().hash(hasher);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planner::greedy::GreedyPlanner;
+    use crate::types::Priority;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Planner that counts how often it actually runs
+    #[derive(Default)]
+    struct CountingPlanner {
+        calls: AtomicUsize,
+    }
+
+    impl TaskPlanner for CountingPlanner {
+        fn plan(&self, tasks: &[Task], workers: &[Worker]) -> Vec<Assignment> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            GreedyPlanner::new().plan(tasks, workers)
+        }
+    }
+
+    fn input() -> (Vec<Task>, Vec<Worker>) {
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High).with_incompatible([2, 3]),
+            Task::new(2, Location::new(10.0, 10.0), Priority::Medium),
+        ];
+        let workers = vec![
+            Worker::new(1, Location::new(1.0, 1.0), true),
+            Worker::new(2, Location::new(11.0, 11.0), true),
+        ];
+        (tasks, workers)
+    }
+
+    #[test]
+    fn test_identical_input_hits_cache() {
+        let planner = CachingPlanner::new(CountingPlanner::default());
+        let (tasks, workers) = input();
+
+        let first = planner.plan(&tasks, &workers);
+        let (tasks_again, workers_again) = input();
+        let second = planner.plan(&tasks_again, &workers_again);
+
+        assert_eq!(first, second);
+        assert_eq!(planner.planner.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(planner.len(), 1);
+    }
+
+    #[test]
+    fn test_changed_input_misses_cache() {
+        let planner = CachingPlanner::new(CountingPlanner::default());
+        let (tasks, mut workers) = input();
+
+        planner.plan(&tasks, &workers);
+        workers[1].current_load = 0.5;
+        planner.plan(&tasks, &workers);
+        assert_eq!(planner.planner.calls.load(Ordering::SeqCst), 2);
+
+        planner.clear();
+        planner.plan(&tasks, &workers);
+        assert_eq!(planner.planner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_oldest_entry_evicted_at_capacity() {
+        let planner = CachingPlanner::new(CountingPlanner::default()).with_capacity(1);
+        let (tasks, workers) = input();
+
+        planner.plan(&tasks, &workers);
+        planner.plan(&tasks[..1], &workers);
+        planner.plan(&tasks, &workers);
+        assert_eq!(planner.planner.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(planner.len(), 1);
+    }
+}
@@ -0,0 +1,190 @@
+use crate::planner::traits::{CostEstimator, DistanceCostEstimator, TaskPlanner};
+use crate::types::{Assignment, Task, TaskId, Worker, WorkerId};
+use crate::validation::{is_plannable, sort_by_dependencies, validate_input, InputError};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+
+/// Greedy batch planner that respects prerequisites declared in `Task.depends_on`
+///
+/// Tasks are considered in [`dependency_order`](crate::validation::dependency_order),
+/// and a task is only assigned once each task it depends on is assigned earlier
+/// in the same plan or was marked completed with [`with_completed`](Self::with_completed).
+/// Anything waiting on an unassigned prerequisite stays unassigned, as does every
+/// task in or behind a dependency cycle; [`try_plan`](Self::try_plan) reports
+/// cycles as an error instead.
+///
+/// Each task goes to the cheapest worker that can still take it: available,
+/// skilled, below its `max_tasks`, with room for the task and not holding an
+/// incompatible one. Assignments are returned in the order they were made, so
+/// a worker's prerequisites always precede its dependent tasks.
+#[derive(Debug)]
+pub struct DependencyPlanner<C = DistanceCostEstimator>
+where
+    C: CostEstimator,
+{
+    cost_estimator: C,
+    completed: HashSet<TaskId>,
+    now: Option<DateTime<Utc>>, // reference time for skill expiry; `None` uses the current time
+}
+
+impl Default for DependencyPlanner<DistanceCostEstimator> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DependencyPlanner<DistanceCostEstimator> {
+    pub fn new() -> Self {
+        Self::with_cost_estimator(DistanceCostEstimator)
+    }
+}
+
+impl<C> DependencyPlanner<C>
+where
+    C: CostEstimator,
+{
+    pub fn with_cost_estimator(cost_estimator: C) -> Self {
+        Self {
+            cost_estimator,
+            completed: HashSet::new(),
+            now: None,
+        }
+    }
+
+    /// Treat `task_ids` as already done, satisfying tasks that depend on them
+    pub fn with_completed(mut self, task_ids: impl IntoIterator<Item = TaskId>) -> Self {
+        self.completed.extend(task_ids);
+        self
+    }
+
+    /// Check skill expiry at `now` instead of the current time, for reproducible plans
+    pub fn with_now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Validate the input with [`validate_input`], including dependency cycles, then plan
+    pub fn try_plan(&self, tasks: &[Task], workers: &[Worker]) -> Result<Vec<Assignment>, InputError> {
+        validate_input(tasks, workers)?;
+        Ok(self.plan(tasks, workers))
+    }
+
+    /// Cheapest worker that can add `task` to what it already holds
+    fn best_worker(
+        &self,
+        task: &Task,
+        workers: &[Worker],
+        held: &HashMap<WorkerId, Vec<&Task>>,
+        now: DateTime<Utc>,
+    ) -> Option<Assignment> {
+        workers
+            .iter()
+            .filter(|worker| {
+                let current = held.get(&worker.id).map(Vec::as_slice).unwrap_or(&[]);
+                let used: f64 = current.iter().map(|other| other.size).sum();
+                worker.can_accept_task()
+                    && current.len() < worker.max_tasks
+                    && worker.can_handle_at(task, now)
+                    && worker.has_room_for(task, used)
+                    && current.iter().all(|other| task.is_compatible_with(other))
+            })
+            .map(|worker| Assignment::new(task.id, worker.id, self.cost_estimator.estimate(task, worker)))
+            .filter(|assignment| assignment.estimated_cost.is_finite())
+            .min_by(|a, b| a.estimated_cost.total_cmp(&b.estimated_cost))
+    }
+}
+
+impl<C> TaskPlanner for DependencyPlanner<C>
+where
+    C: CostEstimator,
+{
+    fn plan(&self, tasks: &[Task], workers: &[Worker]) -> Vec<Assignment> {
+        self.plan_at(tasks, workers, self.now.unwrap_or_else(Utc::now))
+    }
+
+    fn plan_at(&self, tasks: &[Task], workers: &[Worker], now: DateTime<Utc>) -> Vec<Assignment> {
+        if !is_plannable(tasks, workers) {
+            return Vec::new();
+        }
+        // Tasks in or behind a cycle are left out of the order, so never assigned
+        let (order, _) = sort_by_dependencies(tasks);
+
+        let mut assignments = Vec::new();
+        let mut assigned = HashSet::new();
+        let mut held: HashMap<WorkerId, Vec<&Task>> = HashMap::new();
+        for task in order {
+            let ready = task
+                .depends_on
+                .iter()
+                .all(|prerequisite| assigned.contains(prerequisite) || self.completed.contains(prerequisite));
+            if !ready {
+                continue;
+            }
+            if let Some(assignment) = self.best_worker(task, workers, &held, now) {
+                held.entry(assignment.worker_id).or_default().push(task);
+                assigned.insert(task.id);
+                assignments.push(assignment);
+            }
+        }
+        assignments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Location, Priority};
+
+    fn task(id: TaskId, x: f64) -> Task {
+        Task::new(id, Location::new(x, 0.0), Priority::Medium)
+    }
+
+    #[test]
+    fn test_linear_chain_assigned_in_dependency_order() {
+        // Load depends on stage, which depends on pick; listed backwards
+        let tasks = vec![
+            task(3, 3.0).with_depends_on([2]),
+            task(2, 2.0).with_depends_on([1]),
+            task(1, 1.0),
+        ];
+        let workers = vec![Worker::new(1, Location::new(0.0, 0.0), true).with_max_tasks(3)];
+
+        let assignments = DependencyPlanner::new().try_plan(&tasks, &workers).unwrap();
+        let order: Vec<TaskId> = assignments.iter().map(|a| a.task_id).collect();
+        assert_eq!(order, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_dependents_wait_for_unassigned_prerequisites() {
+        let tasks = vec![
+            task(1, 1.0).with_required_skill("forklift"),
+            task(2, 2.0).with_depends_on([1]),
+            task(3, 3.0).with_depends_on([2]),
+            task(4, 4.0).with_depends_on([10]),
+        ];
+        let workers = vec![Worker::new(1, Location::new(0.0, 0.0), true).with_max_tasks(5)];
+
+        // Nobody can do task 1, so the chain behind it stays unassigned
+        assert!(DependencyPlanner::new().plan(&tasks, &workers).is_empty());
+
+        // Task 10 was finished earlier
+        let planned = DependencyPlanner::new().with_completed([10]).plan(&tasks, &workers);
+        assert_eq!(planned.iter().map(|a| a.task_id).collect::<Vec<_>>(), [4]);
+    }
+
+    #[test]
+    fn test_cycle_reported_as_error() {
+        let tasks = vec![
+            task(1, 1.0).with_depends_on([2]),
+            task(2, 2.0).with_depends_on([1]),
+            task(3, 3.0),
+        ];
+        let workers = vec![Worker::new(1, Location::new(0.0, 0.0), true).with_max_tasks(3)];
+        let planner = DependencyPlanner::new();
+
+        assert_eq!(planner.try_plan(&tasks, &workers), Err(InputError::DependencyCycle(vec![1, 2])));
+        // Planning anyway leaves the cycle out
+        let planned = planner.plan(&tasks, &workers);
+        assert_eq!(planned.iter().map(|a| a.task_id).collect::<Vec<_>>(), [3]);
+    }
+}
@@ -1,6 +1,9 @@
+use crate::planner::observer::{NoopObserver, PlannerObserver, RejectionReason};
 use crate::planner::traits::{CostEstimator, TaskPlanner, DistanceCostEstimator};
-use crate::types::{Assignment, Task, Worker};
-use std::collections::HashSet;
+use crate::types::{Assignment, Location, Priority, Task, TaskId, Worker, WorkerId};
+use crate::validation::{is_plannable, validate_input, InputError};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
 
 /// Greedy task planner that assigns each task to the nearest available worker
 #[derive(Debug)]
@@ -9,6 +12,12 @@ where
     C: CostEstimator,
 {
     cost_estimator: C,
+    reserved_workers: HashSet<WorkerId>,
+    idle_tie_tolerance: Option<f64>,
+    idle_engagement_weight: f64,
+    max_cost: Option<f64>,
+    explain: bool,
+    now: Option<DateTime<Utc>>, // reference time for skill expiry and idleness; `None` uses the current time
 }
 
 impl Default for GreedyPlanner<DistanceCostEstimator> {
@@ -20,9 +29,7 @@ impl Default for GreedyPlanner<DistanceCostEstimator> {
 impl GreedyPlanner<DistanceCostEstimator> {
     /// Create a new greedy planner with default distance-based cost estimation
     pub fn new() -> Self {
-        Self {
-            cost_estimator: DistanceCostEstimator,
-        }
+        Self::with_cost_estimator(DistanceCostEstimator)
     }
 }
 
@@ -32,7 +39,67 @@ where
 {
     /// Create a new greedy planner with a custom cost estimator
     pub fn with_cost_estimator(cost_estimator: C) -> Self {
-        Self { cost_estimator }
+        Self {
+            cost_estimator,
+            reserved_workers: HashSet::new(),
+            idle_tie_tolerance: None,
+            idle_engagement_weight: 0.0,
+            max_cost: None,
+            explain: false,
+            now: None,
+        }
+    }
+
+    /// Hold `worker_ids` back as float workers that only take `Critical` tasks
+    pub fn with_reserved_workers(mut self, worker_ids: impl IntoIterator<Item = WorkerId>) -> Self {
+        self.reserved_workers = worker_ids.into_iter().collect();
+        self
+    }
+
+    /// Among workers within `tolerance` of the cheapest cost, pick the one idle longest
+    ///
+    /// Idle time is judged by `Worker.last_assigned_at`, a worker never
+    /// assigned counting as idle longest, so comparable work rotates instead
+    /// of going to the same workers. Remaining ties go to the cheaper worker.
+    /// The chosen worker may cost up to `tolerance` more than the cheapest, so
+    /// its `margin` can be negative.
+    pub fn with_idle_tie_break(mut self, tolerance: f64) -> Self {
+        self.idle_tie_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Never make an assignment costing more than `max_cost`
+    ///
+    /// Costlier candidates are rejected with `RejectionReason::TooExpensive`,
+    /// and a task with no candidate under the ceiling stays unassigned.
+    pub fn with_max_cost(mut self, max_cost: f64) -> Self {
+        self.max_cost = Some(max_cost);
+        self
+    }
+
+    /// Subtract `weight` from the cost of giving a task to an idle worker
+    ///
+    /// A positive weight mobilizes idle workers ahead of slightly nearer ones
+    /// that are already loaded, moving or busy; a negative weight keeps those
+    /// productive and leaves idle workers for when they are clearly cheaper.
+    /// See [`Worker::is_idle`], judged at the planner's reference time (see
+    /// [`with_now`](Self::with_now)). The adjusted cost is what the assignment
+    /// records; `max_cost` applies to the estimate before adjustment.
+    pub fn with_idle_engagement_weight(mut self, weight: f64) -> Self {
+        self.idle_engagement_weight = weight;
+        self
+    }
+
+    /// Give each assignment a short `explanation` of why its worker was chosen
+    pub fn with_explanations(mut self) -> Self {
+        self.explain = true;
+        self
+    }
+
+    /// Check skill expiry and idleness at `now` instead of the current time, for reproducible plans
+    pub fn with_now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
     }
 }
 
@@ -41,42 +108,49 @@ where
     C: CostEstimator,
 {
     fn plan(&self, tasks: &[Task], workers: &[Worker]) -> Vec<Assignment> {
+        self.plan_with_observer(tasks, workers, &mut NoopObserver)
+    }
+
+    fn plan_at(&self, tasks: &[Task], workers: &[Worker], now: DateTime<Utc>) -> Vec<Assignment> {
+        self.plan_observed_at(tasks, workers, now, &mut NoopObserver)
+    }
+
+    fn plan_with_observer(
+        &self,
+        tasks: &[Task],
+        workers: &[Worker],
+        observer: &mut dyn PlannerObserver,
+    ) -> Vec<Assignment> {
+        self.plan_observed_at(tasks, workers, self.now.unwrap_or_else(Utc::now), observer)
+    }
+}
+
+impl<C> GreedyPlanner<C> 
+where 
+    C: CostEstimator,
+{
+    fn plan_observed_at(
+        &self,
+        tasks: &[Task],
+        workers: &[Worker],
+        now: DateTime<Utc>,
+        observer: &mut dyn PlannerObserver,
+    ) -> Vec<Assignment> {
         let mut assignments = Vec::new();
+        if !is_plannable(tasks, workers) {
+            return assignments;
+        }
         let mut assigned_workers = HashSet::new();
         let mut assigned_tasks = HashSet::new();
 
-        // Sort tasks by priority (highest first) to ensure critical tasks get assigned first
-        let mut sorted_tasks: Vec<_> = tasks.iter().enumerate().collect();
-        sorted_tasks.sort_by(|a, b| {
-            b.1.priority.to_numeric().cmp(&a.1.priority.to_numeric())
-        });
-
-        for (_, task) in sorted_tasks {
+        for task in by_priority(tasks) {
             // Skip if task is already assigned
             if assigned_tasks.contains(&task.id) {
                 continue;
             }
 
-            let mut best_assignment: Option<Assignment> = None;
-            let mut best_cost = f64::INFINITY;
-
-            // Find the best available worker for this task
-            for worker in workers {
-                // Skip if worker is already assigned or not available
-                if assigned_workers.contains(&worker.id) || !worker.can_accept_task() {
-                    continue;
-                }
-
-                let cost = self.cost_estimator.estimate(task, worker);
-                
-                if cost < best_cost {
-                    best_cost = cost;
-                    best_assignment = Some(Assignment::new(task.id, worker.id, cost));
-                }
-            }
-
             // Make the assignment if we found a suitable worker
-            if let Some(assignment) = best_assignment {
+            if let Some(assignment) = self.assign_task(task, workers, &assigned_workers, now, observer) {
                 assigned_workers.insert(assignment.worker_id);
                 assigned_tasks.insert(assignment.task_id);
                 assignments.push(assignment);
@@ -85,6 +159,199 @@ where
 
         assignments
     }
+
+    /// Assignments in the order they are decided, each yielded as soon as it is made
+    ///
+    /// Yields exactly what [`plan`](TaskPlanner::plan) returns, but lazily, so a
+    /// consumer can notify a worker before later tasks have been considered.
+    pub fn plan_iter<'a>(&'a self, tasks: &'a [Task], workers: &'a [Worker]) -> impl Iterator<Item = Assignment> + 'a {
+        let now = self.now.unwrap_or_else(Utc::now);
+        let mut assigned_workers = HashSet::new();
+        let mut assigned_tasks = HashSet::new();
+        let tasks = if is_plannable(tasks, workers) { by_priority(tasks) } else { Vec::new() };
+        tasks.into_iter().filter_map(move |task| {
+            if assigned_tasks.contains(&task.id) {
+                return None;
+            }
+            let assignment = self.assign_task(task, workers, &assigned_workers, now, &mut NoopObserver)?;
+            assigned_workers.insert(assignment.worker_id);
+            assigned_tasks.insert(assignment.task_id);
+            Some(assignment)
+        })
+    }
+
+    /// Pick the cheapest worker for `task` among those not yet assigned
+    fn assign_task(
+        &self,
+        task: &Task,
+        workers: &[Worker],
+        assigned_workers: &HashSet<WorkerId>,
+        now: DateTime<Utc>,
+        observer: &mut dyn PlannerObserver,
+    ) -> Option<Assignment> {
+        let mut best_assignment: Option<Assignment> = None;
+        let mut best_cost = f64::INFINITY;
+        let mut candidates = Vec::new();
+
+        // Find the best available worker for this task
+        for worker in workers {
+            // Skip if worker is already assigned or not available
+            if assigned_workers.contains(&worker.id) {
+                observer.on_candidate_rejected(task.id, worker.id, RejectionReason::AtCapacity);
+                continue;
+            }
+            if !worker.can_accept_task() {
+                observer.on_candidate_rejected(task.id, worker.id, RejectionReason::WorkerUnavailable);
+                continue;
+            }
+            if task.priority != Priority::Critical && self.reserved_workers.contains(&worker.id) {
+                observer.on_candidate_rejected(task.id, worker.id, RejectionReason::Reserved);
+                continue;
+            }
+            if !worker.can_handle_at(task, now) {
+                observer.on_candidate_rejected(task.id, worker.id, RejectionReason::MissingSkill);
+                continue;
+            }
+            if !worker.has_room_for(task, 0.0) {
+                observer.on_candidate_rejected(
+                    task.id,
+                    worker.id,
+                    RejectionReason::InsufficientCapacity {
+                        size: task.size,
+                        remaining: worker.remaining_capacity(),
+                    },
+                );
+                continue;
+            }
+
+            let cost = self.cost_estimator.estimate(task, worker);
+            if !cost.is_finite() {
+                observer.on_candidate_rejected(task.id, worker.id, RejectionReason::Infeasible);
+                continue;
+            }
+            if let Some(max_cost) = self.max_cost.filter(|&max_cost| cost > max_cost) {
+                observer.on_candidate_rejected(task.id, worker.id, RejectionReason::TooExpensive { cost, max_cost });
+                continue;
+            }
+            let cost = if worker.is_idle(now) { cost - self.idle_engagement_weight } else { cost };
+            candidates.push((worker.id, cost));
+            
+            if cost < best_cost {
+                best_cost = cost;
+                best_assignment = Some(Assignment::new(task.id, worker.id, cost));
+            }
+        }
+
+        let mut assignment = best_assignment?;
+        if let Some(tolerance) = self.idle_tie_tolerance {
+            assignment = longest_idle(&assignment, &candidates, workers, tolerance);
+        }
+        assignment.margin = runner_up_margin(&assignment, &candidates);
+        if self.explain {
+            assignment.explanation = Some(explain(task, &assignment, &candidates, workers.len(), false));
+        }
+        report_costlier_candidates(observer, &assignment, &candidates);
+        observer.on_assignment(&assignment);
+        Some(assignment)
+    }
+}
+
+/// Rationale for choosing `chosen` from the feasible `candidates` out of `considered` workers
+///
+/// e.g. "worker 2 chosen: lowest cost (3.2) among 4 feasible of 5 considered, has skill forklift, next best +1.4"
+fn explain(task: &Task, chosen: &Assignment, candidates: &[(WorkerId, f64)], considered: usize, kept: bool) -> String {
+    let cheapest = candidates.iter().map(|&(_, cost)| cost).fold(chosen.estimated_cost, f64::min);
+    let factor = if kept {
+        "kept from the previous plan"
+    } else if chosen.estimated_cost > cheapest {
+        "idle longest among comparable workers"
+    } else if candidates.len() == 1 {
+        "only feasible worker"
+    } else {
+        "lowest cost"
+    };
+    let mut explanation = format!(
+        "worker {} chosen: {} ({:.1}) among {} feasible of {} considered",
+        chosen.worker_id,
+        factor,
+        chosen.estimated_cost,
+        candidates.len(),
+        considered
+    );
+    if let Some(skill) = &task.required_skill {
+        explanation.push_str(&format!(", has skill {}", skill));
+    }
+    if let Some(margin) = chosen.margin {
+        explanation.push_str(&format!(", next best {:+.1}", margin));
+    }
+    explanation
+}
+
+/// The candidate within `tolerance` of `cheapest` whose last assignment is oldest
+fn longest_idle(cheapest: &Assignment, candidates: &[(WorkerId, f64)], workers: &[Worker], tolerance: f64) -> Assignment {
+    let last_assigned: HashMap<WorkerId, _> = workers.iter().map(|worker| (worker.id, worker.last_assigned_at)).collect();
+    candidates
+        .iter()
+        .filter(|&&(_, cost)| cost <= cheapest.estimated_cost + tolerance)
+        .min_by(|a, b| last_assigned[&a.0].cmp(&last_assigned[&b.0]).then(a.1.total_cmp(&b.1)))
+        .map(|&(worker_id, cost)| Assignment::new(cheapest.task_id, worker_id, cost))
+        .unwrap_or_else(|| cheapest.clone())
+}
+
+/// Tasks highest priority first, keeping input order within a priority
+fn by_priority(tasks: &[Task]) -> Vec<&Task> {
+    // Critical tasks get assigned first
+    let mut sorted: Vec<_> = tasks.iter().collect();
+    sorted.sort_by_key(|task| std::cmp::Reverse(task.priority.to_numeric()));
+    sorted
+}
+
+/// Report every feasible candidate that lost to the chosen assignment
+fn report_costlier_candidates(
+    observer: &mut dyn PlannerObserver,
+    chosen: &Assignment,
+    candidates: &[(WorkerId, f64)],
+) {
+    for &(worker_id, cost) in candidates {
+        if worker_id != chosen.worker_id {
+            observer.on_candidate_rejected(
+                chosen.task_id,
+                worker_id,
+                RejectionReason::HigherCost { cost, best_cost: chosen.estimated_cost },
+            );
+        }
+    }
+}
+
+/// How much more the cheapest losing candidate would have cost
+fn runner_up_margin(chosen: &Assignment, candidates: &[(WorkerId, f64)]) -> Option<f64> {
+    candidates
+        .iter()
+        .filter(|&&(worker_id, _)| worker_id != chosen.worker_id)
+        .map(|&(_, cost)| cost - chosen.estimated_cost)
+        .min_by(f64::total_cmp)
+}
+
+/// How far apart the tasks of one worker may be in a batch plan
+///
+/// The first task a worker receives anchors it; every later task for that
+/// worker must be allowed relative to the anchor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZoneConstraint {
+    /// Later tasks must be in the anchor's `zone`; unzoned tasks only join unzoned ones
+    SameZone,
+    /// Later tasks must lie within this distance of the anchor's location
+    WithinRadius(f64),
+}
+
+impl ZoneConstraint {
+    /// Whether `task` may join a worker whose first task was `anchor`
+    pub fn allows(&self, anchor: &Task, task: &Task) -> bool {
+        match self {
+            ZoneConstraint::SameZone => anchor.zone == task.zone,
+            ZoneConstraint::WithinRadius(radius) => anchor.location.distance_to(&task.location) <= *radius,
+        }
+    }
 }
 
 /// Greedy planner that supports batch assignments (multiple tasks per worker)
@@ -94,6 +361,16 @@ where
     C: CostEstimator,
 {
     cost_estimator: C,
+    critical_overflow_penalty: Option<f64>,
+    reserved_workers: HashSet<WorkerId>,
+    zone_constraint: Option<ZoneConstraint>,
+    min_one_per_worker: bool,
+    team_radius: Option<f64>,
+    worker_max_tasks: bool,
+    idle_engagement_weight: f64,
+    max_cost: Option<f64>,
+    explain: bool,
+    now: Option<DateTime<Utc>>, // reference time for skill expiry and idleness; `None` uses the current time
 }
 
 impl Default for GreedyBatchPlanner<DistanceCostEstimator> {
@@ -104,9 +381,7 @@ impl Default for GreedyBatchPlanner<DistanceCostEstimator> {
 
 impl GreedyBatchPlanner<DistanceCostEstimator> {
     pub fn new() -> Self {
-        Self {
-            cost_estimator: DistanceCostEstimator,
-        }
+        Self::with_cost_estimator(DistanceCostEstimator)
     }
 }
 
@@ -115,20 +390,198 @@ where
     C: CostEstimator,
 {
     pub fn with_cost_estimator(cost_estimator: C) -> Self {
-        Self { cost_estimator }
+        Self {
+            cost_estimator,
+            critical_overflow_penalty: None,
+            reserved_workers: HashSet::new(),
+            zone_constraint: None,
+            min_one_per_worker: false,
+            team_radius: None,
+            worker_max_tasks: false,
+            idle_engagement_weight: 0.0,
+            max_cost: None,
+            explain: false,
+            now: None,
+        }
+    }
+
+    /// Hold `worker_ids` back as float workers that only take `Critical` tasks
+    ///
+    /// Reserved workers sit idle during normal assignment so urgent arrivals
+    /// always find someone free.
+    pub fn with_reserved_workers(mut self, worker_ids: impl IntoIterator<Item = WorkerId>) -> Self {
+        self.reserved_workers = worker_ids.into_iter().collect();
+        self
+    }
+
+    /// Let `Critical` tasks exceed `max_tasks_per_worker` rather than go unassigned
+    ///
+    /// Each task beyond the limit adds `penalty` to the assignment cost, so an
+    /// overflow is only chosen when no worker has room. Other priorities never
+    /// overflow.
+    pub fn with_critical_overflow(mut self, penalty: f64) -> Self {
+        self.critical_overflow_penalty = Some(penalty);
+        self
+    }
+
+    /// Also stop at each worker's own `max_tasks`
+    ///
+    /// A worker then takes tasks until it reaches the smaller of
+    /// `max_tasks_per_worker` and its `max_tasks`, or until the next task no
+    /// longer fits its remaining `capacity`, whichever comes first. The count
+    /// limit is reported as `RejectionReason::AtCapacity` and the weight limit
+    /// as `RejectionReason::InsufficientCapacity`.
+    pub fn with_worker_max_tasks(mut self) -> Self {
+        self.worker_max_tasks = true;
+        self
+    }
+
+    /// Never make an assignment whose estimated cost exceeds `max_cost`
+    ///
+    /// The ceiling applies to the estimator's cost, before any critical
+    /// overflow penalty. Costlier candidates are rejected with
+    /// `RejectionReason::TooExpensive`, and tasks are never moved to idle
+    /// workers above it.
+    pub fn with_max_cost(mut self, max_cost: f64) -> Self {
+        self.max_cost = Some(max_cost);
+        self
+    }
+
+    /// Subtract `weight` from the cost of a worker's first task while it is idle
+    ///
+    /// As with [`GreedyPlanner::with_idle_engagement_weight`]: positive weights
+    /// spread work to idle workers, negative ones keep already active workers
+    /// busy. A worker stops counting as idle once it has a task in the plan.
+    pub fn with_idle_engagement_weight(mut self, weight: f64) -> Self {
+        self.idle_engagement_weight = weight;
+        self
+    }
+
+    /// Give each assignment a short `explanation` of why its worker was chosen
+    pub fn with_explanations(mut self) -> Self {
+        self.explain = true;
+        self
+    }
+
+    /// Check skill expiry and idleness at `now` instead of the current time, for reproducible plans
+    pub fn with_now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Keep each worker's tasks together, as described by `constraint`
+    ///
+    /// A task outside a worker's zone is never given to it, even when that
+    /// worker is the cheapest; the task goes to another worker or stays
+    /// unassigned.
+    pub fn with_zone_constraint(mut self, constraint: ZoneConstraint) -> Self {
+        self.zone_constraint = Some(constraint);
+        self
+    }
+
+    /// Give every available worker at least one task where feasible
+    ///
+    /// After the normal pass, each worker left idle takes over the cheapest task
+    /// it can handle from a worker holding several. Only a worker's second and
+    /// later tasks move, so every task stays assigned and each donor keeps its
+    /// first (zone-anchoring) task. A team member only moves to a worker within
+    /// the team radius of all its teammates (see [`with_teams`](Self::with_teams)).
+    /// Reserved workers are not topped up, and moved assignments are not
+    /// reported to observers and keep the margin from the normal pass.
+    pub fn with_min_one_per_worker(mut self) -> Self {
+        self.min_one_per_worker = true;
+        self
+    }
+
+    /// Staff tasks needing several workers with that many co-located workers at once
+    ///
+    /// A task with `required_workers` above 1 goes to that many feasible
+    /// workers standing within `radius` of the team's cheapest member, one
+    /// assignment each. The cheapest feasible worker that has enough others
+    /// close by leads the team, joined by the cheapest of them. If no worker
+    /// does, the task stays unassigned and each feasible worker is reported
+    /// with `RejectionReason::TeamIncomplete`. Without this, `required_workers`
+    /// is ignored and every task gets a single worker.
+    pub fn with_teams(mut self, radius: f64) -> Self {
+        self.team_radius = Some(radius);
+        self
     }
 
     /// Plan assignments allowing multiple tasks per worker
     pub fn plan_batch(&self, tasks: &[Task], workers: &[Worker], max_tasks_per_worker: usize) -> Vec<Assignment> {
+        self.plan_batch_with_observer(tasks, workers, max_tasks_per_worker, &mut NoopObserver)
+    }
+
+    /// Validate the input with [`validate_input`], then plan batch assignments
+    pub fn try_plan_batch(
+        &self,
+        tasks: &[Task],
+        workers: &[Worker],
+        max_tasks_per_worker: usize,
+    ) -> Result<Vec<Assignment>, InputError> {
+        validate_input(tasks, workers)?;
+        Ok(self.plan_batch(tasks, workers, max_tasks_per_worker))
+    }
+
+    /// Plan batch assignments starting from `warm_start`, a previous plan, to keep churn low
+    ///
+    /// A task keeps its worker from `warm_start` whenever that worker is still
+    /// feasible for it, even if another worker has become cheaper; only tasks
+    /// whose previous worker can no longer take them, and tasks new to the
+    /// input, are placed afresh. Within each priority, previously assigned
+    /// tasks are placed first so new tasks don't take their workers' room.
+    /// A task held by several workers in `warm_start` keeps whichever of them
+    /// is still feasible and cheapest. Team tasks (see [`with_teams`](Self::with_teams))
+    /// are staffed afresh, so a previous team is not kept together, and tasks
+    /// missing from `tasks` are ignored. An empty `warm_start` plans from
+    /// scratch, like [`plan_batch`](Self::plan_batch).
+    ///
+    /// A kept assignment's `margin` is negative when another worker would now
+    /// be cheaper.
+    pub fn plan_batch_with_warm_start(
+        &self,
+        tasks: &[Task],
+        workers: &[Worker],
+        max_tasks_per_worker: usize,
+        warm_start: &[Assignment],
+    ) -> Vec<Assignment> {
+        self.plan_batch_from(tasks, workers, max_tasks_per_worker, warm_start, &mut NoopObserver)
+    }
+
+    /// Plan batch assignments, reporting decisions to `observer` along the way
+    pub fn plan_batch_with_observer(
+        &self,
+        tasks: &[Task],
+        workers: &[Worker],
+        max_tasks_per_worker: usize,
+        observer: &mut dyn PlannerObserver,
+    ) -> Vec<Assignment> {
+        self.plan_batch_from(tasks, workers, max_tasks_per_worker, &[], observer)
+    }
+
+    fn plan_batch_from(
+        &self,
+        tasks: &[Task],
+        workers: &[Worker],
+        max_tasks_per_worker: usize,
+        warm_start: &[Assignment],
+        observer: &mut dyn PlannerObserver,
+    ) -> Vec<Assignment> {
+        let now = self.now.unwrap_or_else(Utc::now);
         let mut assignments = Vec::new();
-        let mut worker_task_counts: std::collections::HashMap<u32, usize> = std::collections::HashMap::new();
+        if !is_plannable(tasks, workers) {
+            return assignments;
+        }
+        let mut worker_tasks: HashMap<u32, Vec<&Task>> = HashMap::new();
         let mut assigned_tasks = HashSet::new();
+        let mut previous: HashMap<TaskId, Vec<WorkerId>> = HashMap::new();
+        for assignment in warm_start {
+            previous.entry(assignment.task_id).or_default().push(assignment.worker_id);
+        }
 
-        // Sort tasks by priority (highest first)
+        // Sort tasks by priority (highest first), previously assigned ones first within a priority
         let mut sorted_tasks: Vec<_> = tasks.iter().collect();
-        sorted_tasks.sort_by(|a, b| {
-            b.priority.to_numeric().cmp(&a.priority.to_numeric())
-        });
+        sorted_tasks.sort_by_key(|task| (std::cmp::Reverse(task.priority.to_numeric()), !previous.contains_key(&task.id)));
 
         for task in sorted_tasks {
             // Skip if task is already assigned
@@ -138,19 +591,81 @@ where
 
             let mut best_assignment: Option<Assignment> = None;
             let mut best_cost = f64::INFINITY;
+            let mut candidates = Vec::new();
 
             // Find the best available worker for this task
             for worker in workers {
                 if !worker.can_accept_task() {
+                    observer.on_candidate_rejected(task.id, worker.id, RejectionReason::WorkerUnavailable);
+                    continue;
+                }
+                if task.priority != Priority::Critical && self.reserved_workers.contains(&worker.id) {
+                    observer.on_candidate_rejected(task.id, worker.id, RejectionReason::Reserved);
+                    continue;
+                }
+                if !worker.can_handle_at(task, now) {
+                    observer.on_candidate_rejected(task.id, worker.id, RejectionReason::MissingSkill);
+                    continue;
+                }
+
+                let current_tasks = worker_tasks.get(&worker.id).map(Vec::as_slice).unwrap_or(&[]);
+                let task_limit = self.task_limit(worker, max_tasks_per_worker);
+                let mut overflow_penalty = 0.0;
+                if current_tasks.len() >= task_limit {
+                    match self.critical_overflow_penalty {
+                        Some(penalty) if task.priority == Priority::Critical => {
+                            let overflow = current_tasks.len() + 1 - task_limit;
+                            overflow_penalty = penalty * overflow as f64;
+                        }
+                        _ => {
+                            observer.on_candidate_rejected(task.id, worker.id, RejectionReason::AtCapacity);
+                            continue;
+                        }
+                    }
+                }
+
+                if let (Some(constraint), Some(anchor)) = (self.zone_constraint, current_tasks.first()) {
+                    if !constraint.allows(anchor, task) {
+                        observer.on_candidate_rejected(task.id, worker.id, RejectionReason::OutsideZone);
+                        continue;
+                    }
+                }
+
+                // Never combine mutually incompatible tasks on one worker
+                if let Some(other) = current_tasks.iter().find(|other| !task.is_compatible_with(other)) {
+                    observer.on_candidate_rejected(task.id, worker.id, RejectionReason::IncompatibleTask(other.id));
                     continue;
                 }
 
-                let current_task_count = worker_task_counts.get(&worker.id).unwrap_or(&0);
-                if *current_task_count >= max_tasks_per_worker {
+                let used: f64 = current_tasks.iter().map(|t| t.size).sum();
+                if !worker.has_room_for(task, used) {
+                    observer.on_candidate_rejected(
+                        task.id,
+                        worker.id,
+                        RejectionReason::InsufficientCapacity {
+                            size: task.size,
+                            remaining: worker.remaining_capacity() - used,
+                        },
+                    );
                     continue;
                 }
 
-                let cost = self.cost_estimator.estimate(task, worker);
+                let estimate = self.cost_estimator.estimate(task, worker);
+                if !estimate.is_finite() {
+                    observer.on_candidate_rejected(task.id, worker.id, RejectionReason::Infeasible);
+                    continue;
+                }
+                if let Some(max_cost) = self.max_cost.filter(|&max_cost| estimate > max_cost) {
+                    observer.on_candidate_rejected(
+                        task.id,
+                        worker.id,
+                        RejectionReason::TooExpensive { cost: estimate, max_cost },
+                    );
+                    continue;
+                }
+                let idle_credit = if current_tasks.is_empty() && worker.is_idle(now) { self.idle_engagement_weight } else { 0.0 };
+                let cost = estimate + overflow_penalty - idle_credit;
+                candidates.push((worker.id, cost));
                 
                 if cost < best_cost {
                     best_cost = cost;
@@ -158,22 +673,173 @@ where
                 }
             }
 
+            let team_size = if self.team_radius.is_some() { task.required_workers } else { 1 };
+            if team_size > 1 {
+                self.assign_team(task, candidates, workers, observer, &mut worker_tasks, &mut assignments);
+                assigned_tasks.insert(task.id);
+                continue;
+            }
+
+            // A previous worker that is still feasible keeps the task
+            let kept = previous
+                .get(&task.id)
+                .and_then(|worker_ids| {
+                    candidates
+                        .iter()
+                        .filter(|(candidate, _)| worker_ids.contains(candidate))
+                        .min_by(|a, b| a.1.total_cmp(&b.1))
+                })
+                .map(|&(worker_id, cost)| Assignment::new(task.id, worker_id, cost));
+            let is_kept = kept.is_some();
+            if is_kept {
+                best_assignment = kept;
+            }
+
             // Make the assignment if we found a suitable worker
-            if let Some(assignment) = best_assignment {
-                *worker_task_counts.entry(assignment.worker_id).or_insert(0) += 1;
+            if let Some(mut assignment) = best_assignment {
+                assignment.margin = runner_up_margin(&assignment, &candidates);
+                if self.explain {
+                    assignment.explanation = Some(explain(task, &assignment, &candidates, workers.len(), is_kept));
+                }
+                report_costlier_candidates(observer, &assignment, &candidates);
+                observer.on_assignment(&assignment);
+                worker_tasks.entry(assignment.worker_id).or_default().push(task);
                 assigned_tasks.insert(assignment.task_id);
                 assignments.push(assignment);
             }
         }
 
+        if self.min_one_per_worker {
+            self.spread_to_idle_workers(tasks, workers, now, &mut assignments);
+        }
         assignments
     }
+
+    /// Give `task` to its `required_workers` co-located candidates together, or to none of them
+    fn assign_team<'a>(
+        &self,
+        task: &'a Task,
+        mut candidates: Vec<(WorkerId, f64)>,
+        workers: &[Worker],
+        observer: &mut dyn PlannerObserver,
+        worker_tasks: &mut HashMap<WorkerId, Vec<&'a Task>>,
+        assignments: &mut Vec<Assignment>,
+    ) {
+        let team_size = task.required_workers;
+        let radius = self.team_radius.unwrap_or(f64::INFINITY);
+        let locations: HashMap<WorkerId, &Location> = workers.iter().map(|worker| (worker.id, &worker.location)).collect();
+        candidates.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        // Each candidate leading a team of the cheaper-to-costlier candidates near it
+        let groups: Vec<Vec<(WorkerId, f64)>> = (0..candidates.len())
+            .map(|lead| {
+                let at = locations[&candidates[lead].0];
+                candidates[lead..]
+                    .iter()
+                    .filter(|(worker_id, _)| locations[worker_id].distance_to(at) <= radius)
+                    .copied()
+                    .collect()
+            })
+            .collect();
+        let Some(mut team) = groups.iter().find(|group| group.len() >= team_size).cloned() else {
+            let available = groups.iter().map(Vec::len).max().unwrap_or(0);
+            let reason = RejectionReason::TeamIncomplete { required: team_size, available };
+            for &(worker_id, _) in &candidates {
+                observer.on_candidate_rejected(task.id, worker_id, reason.clone());
+            }
+            return;
+        };
+
+        team.truncate(team_size);
+        let best_cost = team[team_size - 1].1;
+        for &(worker_id, cost) in candidates.iter().filter(|candidate| !team.contains(candidate)) {
+            let reason = if cost < best_cost {
+                RejectionReason::NotColocated { radius }
+            } else {
+                RejectionReason::HigherCost { cost, best_cost }
+            };
+            observer.on_candidate_rejected(task.id, worker_id, reason);
+        }
+        for &(worker_id, cost) in &team {
+            let mut assignment = Assignment::new(task.id, worker_id, cost);
+            if self.explain {
+                assignment.explanation = Some(format!(
+                    "worker {} chosen: one of the {} cheapest ({:.1}) of {} feasible for a team task",
+                    worker_id,
+                    team_size,
+                    cost,
+                    candidates.len()
+                ));
+            }
+            observer.on_assignment(&assignment);
+            worker_tasks.entry(worker_id).or_default().push(task);
+            assignments.push(assignment);
+        }
+    }
+
+    /// How many tasks `worker` may hold in this plan
+    fn task_limit(&self, worker: &Worker, max_tasks_per_worker: usize) -> usize {
+        if self.worker_max_tasks {
+            max_tasks_per_worker.min(worker.max_tasks)
+        } else {
+            max_tasks_per_worker
+        }
+    }
+
+    /// Move surplus tasks to workers the primary pass left idle
+    fn spread_to_idle_workers(&self, tasks: &[Task], workers: &[Worker], now: DateTime<Utc>, assignments: &mut [Assignment]) {
+        let tasks: HashMap<TaskId, &Task> = tasks.iter().map(|task| (task.id, task)).collect();
+        let locations: HashMap<WorkerId, &Location> = workers.iter().map(|worker| (worker.id, &worker.location)).collect();
+
+        for idle in workers {
+            let busy = assignments.iter().any(|a| a.worker_id == idle.id);
+            let no_room = self.task_limit(idle, usize::MAX) == 0;
+            if busy || no_room || !idle.can_accept_task() || self.reserved_workers.contains(&idle.id) {
+                continue;
+            }
+
+            // Indices of each worker's assignments, in the order they were made
+            let mut held: HashMap<WorkerId, Vec<usize>> = HashMap::new();
+            for (index, assignment) in assignments.iter().enumerate() {
+                held.entry(assignment.worker_id).or_default().push(index);
+            }
+
+            let best = held
+                .values()
+                .flat_map(|indices| indices.iter().skip(1).copied())
+                .filter_map(|index| {
+                    let task = tasks[&assignments[index].task_id];
+                    // Teammates stay together: everyone else on the task must be close by
+                    let radius = self.team_radius.unwrap_or(f64::INFINITY);
+                    let stays_together = assignments.iter().enumerate().all(|(other, assignment)| {
+                        other == index
+                            || assignment.task_id != task.id
+                            || locations[&assignment.worker_id].distance_to(&idle.location) <= radius
+                    });
+                    let feasible = stays_together && idle.can_handle_at(task, now) && idle.has_room_for(task, 0.0);
+                    feasible.then(|| (index, self.cost_estimator.estimate(task, idle)))
+                })
+                .filter(|&(_, cost)| cost.is_finite() && self.max_cost.is_none_or(|max_cost| cost <= max_cost))
+                .min_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+
+            if let Some((index, cost)) = best {
+                let donor = assignments[index].worker_id;
+                assignments[index].worker_id = idle.id;
+                assignments[index].estimated_cost = cost;
+                if self.explain {
+                    assignments[index].explanation =
+                        Some(format!("worker {} chosen: was left idle, takes over from worker {}", idle.id, donor));
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{Location, Priority};
+    use crate::diff::diff_plans;
+    use crate::types::{Location, Proficiency, TaskId, Velocity};
 
     #[test]
     fn test_greedy_planner_basic_assignment() {
@@ -244,6 +910,26 @@ mod tests {
         assert_eq!(assignments.len(), 0);
     }
 
+    #[test]
+    fn test_invalid_input_assigns_nothing() {
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High),
+            Task::new(2, Location::new(f64::NAN, 0.0), Priority::Medium),
+        ];
+        let workers = vec![
+            Worker::new(1, Location::new(1.0, 1.0), true).with_max_tasks(2),
+            Worker::new(2, Location::new(2.0, 2.0), true),
+        ];
+
+        assert!(GreedyPlanner::new().plan(&tasks, &workers).is_empty());
+        assert_eq!(GreedyPlanner::new().plan_iter(&tasks, &workers).count(), 0);
+        assert!(GreedyBatchPlanner::new().plan_batch(&tasks, &workers, 2).is_empty());
+        assert!(matches!(
+            GreedyPlanner::new().try_plan(&tasks, &workers),
+            Err(InputError::NonFiniteTaskLocation { task_id: 2, .. })
+        ));
+    }
+
     #[test]
     fn test_greedy_batch_planner() {
         let planner = GreedyBatchPlanner::new();
@@ -271,6 +957,614 @@ mod tests {
         assert!(assignments.iter().any(|a| a.task_id == 2)); // Medium priority
     }
 
+    #[test]
+    fn test_greedy_batch_planner_separates_incompatible_tasks() {
+        let planner = GreedyBatchPlanner::new();
+
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High).with_incompatible([2]),
+            Task::new(2, Location::new(1.0, 0.0), Priority::Medium),
+        ];
+
+        // Worker 1 is nearest to both tasks and has room for both
+        let workers = vec![
+            Worker::new(1, Location::new(0.5, 0.0), true),
+            Worker::new(2, Location::new(20.0, 20.0), true),
+        ];
+
+        let assignments = planner.plan_batch(&tasks, &workers, 2);
+
+        assert_eq!(assignments.len(), 2);
+        let task1_worker = assignments.iter().find(|a| a.task_id == 1).unwrap().worker_id;
+        let task2_worker = assignments.iter().find(|a| a.task_id == 2).unwrap().worker_id;
+        assert_eq!(task1_worker, 1);
+        assert_eq!(task2_worker, 2);
+    }
+
+    #[test]
+    fn test_zone_constraint_keeps_second_task_in_zone() {
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High).with_zone("A"),
+            // Nearest to worker 1, but across the aisle in zone B
+            Task::new(2, Location::new(1.0, 0.0), Priority::Medium).with_zone("B"),
+            Task::new(3, Location::new(0.0, 5.0), Priority::Low).with_zone("A"),
+        ];
+        let workers = vec![Worker::new(1, Location::new(0.0, 0.0), true)];
+
+        let unconstrained = GreedyBatchPlanner::new().plan_batch(&tasks, &workers, 2);
+        assert_eq!(unconstrained.iter().map(|a| a.task_id).collect::<Vec<_>>(), [1, 2]);
+
+        let planner = GreedyBatchPlanner::new().with_zone_constraint(ZoneConstraint::SameZone);
+        let mut log = RecordingObserver::default();
+        let zoned = planner.plan_batch_with_observer(&tasks, &workers, 2, &mut log);
+        assert_eq!(zoned.iter().map(|a| a.task_id).collect::<Vec<_>>(), [1, 3]);
+        assert!(log.events.contains(&"reject task 2 worker 1: outside-zone".to_string()));
+
+        // A radius keeps the nearby task and drops the far one instead
+        let planner = GreedyBatchPlanner::new().with_zone_constraint(ZoneConstraint::WithinRadius(2.0));
+        let within_radius = planner.plan_batch(&tasks, &workers, 2);
+        assert_eq!(within_radius.iter().map(|a| a.task_id).collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn test_min_one_per_worker_engages_idle_worker() {
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High),
+            Task::new(2, Location::new(1.0, 0.0), Priority::Medium),
+            Task::new(3, Location::new(2.0, 0.0), Priority::Low).with_required_skill("forklift"),
+        ];
+        let workers = vec![
+            Worker::new(1, Location::new(0.0, 0.0), true).with_skill("forklift", Proficiency::Competent),
+            Worker::new(2, Location::new(10.0, 0.0), true),
+        ];
+
+        // Greedy piles everything on the nearby worker
+        let greedy = GreedyBatchPlanner::new().plan_batch(&tasks, &workers, 3);
+        assert!(greedy.iter().all(|a| a.worker_id == 1));
+
+        let spread = GreedyBatchPlanner::new().with_min_one_per_worker().plan_batch(&tasks, &workers, 3);
+        let owners: Vec<(TaskId, WorkerId)> = spread.iter().map(|a| (a.task_id, a.worker_id)).collect();
+        // Worker 2 takes the one surplus task it can do; the forklift task stays put
+        assert_eq!(owners, [(1, 1), (2, 2), (3, 1)]);
+        assert!((spread[1].estimated_cost - 9.0).abs() < 1e-9);
+        assert!(spread[1].margin.is_some());
+        assert_eq!(spread[1].margin, greedy[1].margin);
+    }
+
+    #[test]
+    fn test_min_one_per_worker_keeps_teams_together() {
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High),
+            Task::new(2, Location::new(0.0, 0.0), Priority::Medium).with_required_workers(2),
+        ];
+        let workers = vec![
+            Worker::new(1, Location::new(0.0, 0.0), true),
+            Worker::new(2, Location::new(0.5, 0.0), true),
+            Worker::new(3, Location::new(40.0, 40.0), true),
+        ];
+
+        let planner = GreedyBatchPlanner::new().with_teams(2.0);
+        let before = planner.plan_batch(&tasks, &workers, 3);
+        let owners: Vec<(TaskId, WorkerId)> = before.iter().map(|a| (a.task_id, a.worker_id)).collect();
+        assert_eq!(owners, [(1, 1), (2, 1), (2, 2)]);
+
+        // Worker 3 is too far from worker 2 to take worker 1's place on the team
+        let spread = planner.with_min_one_per_worker().plan_batch(&tasks, &workers, 3);
+        assert_eq!(spread, before);
+    }
+
+    #[test]
+    fn test_min_one_per_worker_moves_team_member_nearby() {
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High),
+            Task::new(2, Location::new(0.0, 0.0), Priority::Medium).with_required_workers(2),
+        ];
+        let workers = vec![
+            Worker::new(1, Location::new(0.0, 0.0), true),
+            Worker::new(2, Location::new(0.5, 0.0), true),
+            Worker::new(3, Location::new(1.5, 0.0), true),
+        ];
+
+        let planner = GreedyBatchPlanner::new().with_teams(2.0);
+        let spread = planner.with_min_one_per_worker().plan_batch(&tasks, &workers, 3);
+        let owners: Vec<(TaskId, WorkerId)> = spread.iter().map(|a| (a.task_id, a.worker_id)).collect();
+        assert_eq!(owners, [(1, 1), (2, 3), (2, 2)]);
+    }
+
+    #[test]
+    fn test_small_task_fits_nearly_full_worker() {
+        let planner = GreedyPlanner::new();
+
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High).with_size(1.0),
+            Task::new(2, Location::new(0.0, 0.0), Priority::Medium).with_size(0.5),
+        ];
+        // Worker 1 is back from lunch with half its capacity; worker 2 is far away
+        let workers = vec![
+            Worker::new(1, Location::new(0.5, 0.0), true).with_load(0.5),
+            Worker::new(2, Location::new(50.0, 50.0), true),
+        ];
+
+        let assignments = planner.plan(&tasks, &workers);
+        assert_eq!(assignments.len(), 2);
+        assert_eq!(assignments.iter().find(|a| a.task_id == 1).unwrap().worker_id, 2);
+        assert_eq!(assignments.iter().find(|a| a.task_id == 2).unwrap().worker_id, 1);
+    }
+
+    #[test]
+    fn test_batch_planner_respects_remaining_capacity() {
+        let planner = GreedyBatchPlanner::new();
+
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High).with_size(0.5),
+            Task::new(2, Location::new(0.0, 0.0), Priority::Medium).with_size(0.5),
+            Task::new(3, Location::new(0.0, 0.0), Priority::Low).with_size(0.25),
+        ];
+        let workers = vec![Worker::new(1, Location::new(0.0, 0.0), true).with_load(0.25)];
+
+        // 0.75 units remaining: the first task fits, the second no longer does, the third does
+        let assignments = planner.plan_batch(&tasks, &workers, 5);
+        let assigned: Vec<_> = assignments.iter().map(|a| a.task_id).collect();
+        assert_eq!(assigned, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_plan_waves_resets_capacity_between_waves() {
+        let planner = GreedyPlanner::new();
+
+        // Seven tasks released in arrival order; one critical task arrives late
+        let mut tasks: Vec<Task> = (1..=6)
+            .map(|id| Task::new(id, Location::new(id as f64, 0.0), Priority::Medium))
+            .collect();
+        tasks.push(Task::new(7, Location::new(7.0, 0.0), Priority::Critical));
+        let workers = vec![
+            Worker::new(1, Location::new(0.0, 0.0), true),
+            Worker::new(2, Location::new(5.0, 0.0), true),
+            Worker::new(3, Location::new(10.0, 0.0), true),
+        ];
+
+        let waves = planner.plan_waves(&tasks, &workers, 3);
+        assert_eq!(waves.len(), 3);
+        assert_eq!(waves.iter().map(Vec::len).collect::<Vec<_>>(), vec![3, 3, 1]);
+
+        for wave in &waves {
+            // Each worker takes at most one task per wave
+            let workers_used: HashSet<_> = wave.iter().map(|a| a.worker_id).collect();
+            assert_eq!(workers_used.len(), wave.len());
+        }
+
+        // The critical task goes out first, then the rest by arrival
+        let wave_tasks: Vec<Vec<TaskId>> = waves
+            .iter()
+            .map(|wave| {
+                let mut ids: Vec<_> = wave.iter().map(|a| a.task_id).collect();
+                ids.sort();
+                ids
+            })
+            .collect();
+        assert_eq!(wave_tasks, vec![vec![1, 2, 7], vec![3, 4, 5], vec![6]]);
+    }
+
+    #[test]
+    fn test_assignment_log_follows_priority_order() {
+        let planner = GreedyPlanner::new();
+
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::Low),
+            Task::new(2, Location::new(1.0, 0.0), Priority::Critical),
+            Task::new(3, Location::new(2.0, 0.0), Priority::Medium),
+            Task::new(4, Location::new(3.0, 0.0), Priority::High),
+        ];
+        let workers: Vec<Worker> = (1..=4)
+            .map(|id| Worker::new(id, Location::new(id as f64, 1.0), true))
+            .collect();
+
+        let log = planner.plan_with_log(&tasks, &workers);
+        let steps: Vec<(usize, TaskId)> = log.iter().map(|(step, a)| (*step, a.task_id)).collect();
+        assert_eq!(steps, vec![(0, 2), (1, 4), (2, 3), (3, 1)]);
+
+        // The log holds the same assignments as a plain plan
+        let logged: Vec<Assignment> = log.into_iter().map(|(_, a)| a).collect();
+        assert_eq!(logged, planner.plan(&tasks, &workers));
+    }
+
+    #[test]
+    fn test_critical_overflow_only_for_critical_tasks() {
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::Critical),
+            Task::new(2, Location::new(1.0, 0.0), Priority::Critical),
+            Task::new(3, Location::new(2.0, 0.0), Priority::Low),
+        ];
+        let workers = vec![Worker::new(1, Location::new(0.0, 0.0), true)];
+
+        // Without overflow the single slot goes to the first critical task
+        let strict = GreedyBatchPlanner::new().plan_batch(&tasks, &workers, 1);
+        assert_eq!(strict.len(), 1);
+
+        let planner = GreedyBatchPlanner::new().with_critical_overflow(100.0);
+        let assignments = planner.plan_batch(&tasks, &workers, 1);
+        let assigned: Vec<_> = assignments.iter().map(|a| a.task_id).collect();
+        assert_eq!(assigned, vec![1, 2]);
+
+        // The overflowing assignment carries the penalty
+        let overflowed = assignments.iter().find(|a| a.task_id == 2).unwrap();
+        assert!(overflowed.estimated_cost >= 100.0);
+    }
+
+    #[test]
+    fn test_critical_overflow_prefers_worker_with_room() {
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::Critical),
+            Task::new(2, Location::new(0.0, 0.0), Priority::Critical),
+        ];
+        // Worker 2 is farther away but still has a free slot
+        let workers = vec![
+            Worker::new(1, Location::new(0.0, 0.0), true),
+            Worker::new(2, Location::new(30.0, 0.0), true),
+        ];
+
+        let planner = GreedyBatchPlanner::new().with_critical_overflow(100.0);
+        let assignments = planner.plan_batch(&tasks, &workers, 1);
+        assert_eq!(assignments.iter().find(|a| a.task_id == 2).unwrap().worker_id, 2);
+    }
+
+    #[test]
+    fn test_team_task_gets_two_nearest_workers() {
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::High).with_required_workers(2)];
+        let workers = vec![
+            Worker::new(1, Location::new(5.0, 0.0), true),
+            Worker::new(2, Location::new(1.0, 0.0), true),
+            Worker::new(3, Location::new(2.0, 0.0), true),
+        ];
+
+        // Without teams the task goes to a single worker
+        assert_eq!(GreedyBatchPlanner::new().plan_batch(&tasks, &workers, 1).len(), 1);
+
+        let planner = GreedyBatchPlanner::new().with_teams(10.0);
+        let mut log = RecordingObserver::default();
+        let assignments = planner.plan_batch_with_observer(&tasks, &workers, 1, &mut log);
+        let team: Vec<(TaskId, WorkerId)> = assignments.iter().map(|a| (a.task_id, a.worker_id)).collect();
+        assert_eq!(team, [(1, 2), (1, 3)]);
+        assert!(log.events.contains(&"reject task 1 worker 1: higher-cost".to_string()));
+    }
+
+    #[test]
+    fn test_team_task_unassigned_without_enough_workers() {
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::High).with_required_workers(2)];
+        let workers = vec![
+            Worker::new(1, Location::new(1.0, 0.0), true),
+            Worker::new(2, Location::new(2.0, 0.0), false),
+        ];
+
+        let planner = GreedyBatchPlanner::new().with_teams(10.0);
+        let mut log = RecordingObserver::default();
+        assert!(planner.plan_batch_with_observer(&tasks, &workers, 1, &mut log).is_empty());
+        assert!(log.events.contains(&"reject task 1 worker 1: team-incomplete".to_string()));
+        assert!(log.events.contains(&"reject task 1 worker 2: unavailable".to_string()));
+    }
+
+    #[test]
+    fn test_team_members_must_stand_together() {
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::High).with_required_workers(2)];
+        // The two cheapest workers are on opposite sides of the task
+        let workers = vec![
+            Worker::new(1, Location::new(-3.0, 0.0), true),
+            Worker::new(2, Location::new(3.0, 0.0), true),
+        ];
+
+        let mut log = RecordingObserver::default();
+        let planner = GreedyBatchPlanner::new().with_teams(2.0);
+        assert!(planner.plan_batch_with_observer(&tasks, &workers, 1, &mut log).is_empty());
+        assert_eq!(log.events, ["reject task 1 worker 1: team-incomplete", "reject task 1 worker 2: team-incomplete"]);
+
+        // A pair farther out but side by side makes the team instead
+        let mut workers = workers;
+        workers.push(Worker::new(3, Location::new(0.0, 8.0), true));
+        workers.push(Worker::new(4, Location::new(1.0, 8.0), true));
+        let mut log = RecordingObserver::default();
+        let assignments = planner.plan_batch_with_observer(&tasks, &workers, 1, &mut log);
+        let team: Vec<WorkerId> = assignments.iter().map(|a| a.worker_id).collect();
+        assert_eq!(team, [3, 4]);
+        assert!(log.events.contains(&"reject task 1 worker 1: not-colocated".to_string()));
+
+        // Close enough together, the cheapest pair is chosen again
+        let assignments = GreedyBatchPlanner::new().with_teams(6.0).plan_batch(&tasks, &workers, 1);
+        let team: Vec<WorkerId> = assignments.iter().map(|a| a.worker_id).collect();
+        assert_eq!(team, [1, 2]);
+    }
+
+    #[test]
+    fn test_worker_max_tasks_and_capacity_both_bind() {
+        let tasks: Vec<Task> = [Priority::High, Priority::Medium, Priority::Medium, Priority::Low]
+            .into_iter()
+            .enumerate()
+            .map(|(i, priority)| Task::new(i as TaskId + 1, Location::new(0.0, 0.0), priority).with_size(0.4))
+            .collect();
+        let workers = vec![
+            // Room for any weight, but only one task
+            Worker::new(1, Location::new(0.0, 0.0), true).with_max_tasks(1).with_capacity(10.0),
+            // Any number of tasks, but 1.0 of weight
+            Worker::new(2, Location::new(1.0, 0.0), true).with_max_tasks(5).with_capacity(1.0),
+        ];
+
+        // The batch limit alone lets worker 1 take everything
+        let unbounded = GreedyBatchPlanner::new().plan_batch(&tasks, &workers, 5);
+        assert!(unbounded.iter().all(|a| a.worker_id == 1));
+
+        let planner = GreedyBatchPlanner::new().with_worker_max_tasks();
+        let mut log = RecordingObserver::default();
+        let assignments = planner.plan_batch_with_observer(&tasks, &workers, 5, &mut log);
+        let plan: Vec<(TaskId, WorkerId)> = assignments.iter().map(|a| (a.task_id, a.worker_id)).collect();
+        assert_eq!(plan, [(1, 1), (2, 2), (3, 2)]);
+        assert!(log.events.contains(&"reject task 4 worker 1: at-capacity".to_string()));
+        assert!(log.events.contains(&"reject task 4 worker 2: insufficient-capacity".to_string()));
+    }
+
+    #[test]
+    fn test_idle_tie_break_prefers_longest_idle_worker() {
+        let now = chrono::Utc::now();
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::Medium)];
+        let workers = vec![
+            Worker::new(1, Location::new(3.0, 4.0), true).with_last_assigned_at(now - chrono::Duration::minutes(10)),
+            // Same distance, idle for two hours
+            Worker::new(2, Location::new(-4.0, 3.0), true).with_last_assigned_at(now - chrono::Duration::hours(2)),
+            // Never assigned, but well outside the tolerance
+            Worker::new(3, Location::new(30.0, 40.0), true),
+        ];
+
+        assert_eq!(GreedyPlanner::new().plan(&tasks, &workers)[0].worker_id, 1);
+
+        let assignments = GreedyPlanner::new().with_idle_tie_break(0.5).plan(&tasks, &workers);
+        assert_eq!(assignments[0].worker_id, 2);
+        assert_eq!(assignments[0].margin, Some(0.0));
+    }
+
+    #[test]
+    fn test_max_cost_leaves_distant_task_unassigned() {
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::Medium),
+            // Only reachable by a long trek
+            Task::new(2, Location::new(200.0, 0.0), Priority::Medium),
+        ];
+        let workers = vec![Worker::new(1, Location::new(3.0, 4.0), true).with_max_tasks(2)];
+
+        assert_eq!(GreedyBatchPlanner::new().plan_batch(&tasks, &workers, 2).len(), 2);
+
+        let planner = GreedyBatchPlanner::new().with_max_cost(50.0);
+        let mut log = RecordingObserver::default();
+        let assignments = planner.plan_batch_with_observer(&tasks, &workers, 2, &mut log);
+        assert_eq!(assignments.iter().map(|a| a.task_id).collect::<Vec<_>>(), [1]);
+        assert!(log.events.contains(&"reject task 2 worker 1: too-expensive".to_string()));
+
+        // The single-task planner applies the same ceiling
+        let mut log = RecordingObserver::default();
+        let single = GreedyPlanner::new().with_max_cost(50.0).plan_with_observer(&tasks[1..], &workers, &mut log);
+        assert!(single.is_empty());
+        assert_eq!(log.events, ["reject task 2 worker 1: too-expensive"]);
+    }
+
+    #[test]
+    fn test_idle_engagement_weight_flips_choice() {
+        let now = chrono::Utc::now();
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::Medium)];
+        let workers = vec![
+            // Already walking past, 5 units away
+            Worker::new(1, Location::new(3.0, 4.0), true).with_velocity(Velocity::new(1.0, 0.0), now),
+            // Standing idle, 6 units away
+            Worker::new(2, Location::new(6.0, 0.0), true),
+        ];
+        assert!(!workers[0].is_idle(now) && workers[1].is_idle(now));
+
+        let chosen = |weight: f64| GreedyPlanner::new().with_idle_engagement_weight(weight).plan(&tasks, &workers)[0].worker_id;
+        assert_eq!(chosen(0.0), 1);
+        assert_eq!(chosen(2.0), 2, "idle worker engaged");
+        assert_eq!(chosen(-2.0), 1, "active worker kept productive");
+
+        // Flipping the distances flips which weight changes the outcome
+        let nearer_idle = vec![workers[0].clone(), Worker::new(2, Location::new(4.0, 0.0), true)];
+        let chosen = |weight: f64| {
+            GreedyBatchPlanner::new().with_idle_engagement_weight(weight).plan_batch(&tasks, &nearer_idle, 2)[0].worker_id
+        };
+        assert_eq!(chosen(2.0), 2);
+        assert_eq!(chosen(-2.0), 1);
+    }
+
+    #[test]
+    fn test_idleness_judged_at_planner_now() {
+        let now = chrono::DateTime::parse_from_rfc3339("2024-01-15T10:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::Medium)];
+        let workers = vec![
+            // Nearer, but busy until 10:10
+            Worker::new(1, Location::new(5.0, 0.0), true).with_busy_until(now + chrono::Duration::minutes(10)),
+            Worker::new(2, Location::new(6.0, 0.0), true),
+        ];
+        assert!(!workers[0].is_idle(now) && workers[0].is_idle(now + chrono::Duration::minutes(20)));
+
+        let chosen = |at| GreedyPlanner::new().with_idle_engagement_weight(2.0).with_now(at).plan(&tasks, &workers)[0].worker_id;
+        assert_eq!(chosen(now), 2, "only the free worker earns the idle credit");
+        assert_eq!(chosen(now + chrono::Duration::minutes(20)), 1, "both idle by then");
+    }
+
+    #[test]
+    fn test_warm_start_keeps_previous_plan_after_small_change() {
+        let tasks = vec![
+            Task::new(1, Location::new(5.0, 0.0), Priority::Medium),
+            Task::new(2, Location::new(9.0, 0.0), Priority::Medium),
+            Task::new(3, Location::new(15.0, 0.0), Priority::Medium),
+        ];
+        let mut workers = vec![
+            Worker::new(1, Location::new(4.0, 0.0), true),
+            Worker::new(2, Location::new(10.0, 0.0), true),
+            Worker::new(3, Location::new(20.0, 0.0), true),
+        ];
+        let planner = GreedyBatchPlanner::new();
+        let previous = planner.plan_batch(&tasks, &workers, 1);
+
+        // Worker 2 walks towards task 1, which a cold start hands over to it
+        workers[1].location = Location::new(5.5, 0.0);
+        let cold = planner.plan_batch(&tasks, &workers, 1);
+        let warm = planner.plan_batch_with_warm_start(&tasks, &workers, 1, &previous);
+        assert_eq!(diff_plans(&previous, &cold).changed.len(), 2);
+        assert!(diff_plans(&previous, &warm).is_empty());
+
+        // A previous worker that can no longer take its task gives up only that task
+        workers[2].is_available = false;
+        workers.push(Worker::new(4, Location::new(16.0, 0.0), true));
+        let warm = planner.plan_batch_with_warm_start(&tasks, &workers, 1, &previous);
+        let diff = diff_plans(&previous, &warm);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!((diff.changed[0].new.task_id, diff.changed[0].new.worker_id), (3, 4));
+    }
+
+    #[test]
+    fn test_warm_start_keeps_one_of_several_previous_workers() {
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::Medium)];
+        let workers = vec![
+            Worker::new(1, Location::new(1.0, 0.0), true),
+            Worker::new(2, Location::new(4.0, 0.0), true),
+            Worker::new(3, Location::new(3.0, 0.0), true),
+        ];
+        // The task was split between workers 2 and 3 last time
+        let previous = vec![Assignment::new(1, 2, 4.0).with_share(0.5), Assignment::new(1, 3, 3.0).with_share(0.5)];
+
+        let warm = GreedyBatchPlanner::new().plan_batch_with_warm_start(&tasks, &workers, 1, &previous);
+        assert_eq!(warm.len(), 1);
+        assert_eq!(warm[0].worker_id, 3);
+    }
+
+    #[test]
+    fn test_expired_certification_leaves_task_unassigned() {
+        let now = chrono::Utc::now();
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::High).with_required_skill("forklift")];
+        let lapsed = vec![Worker::new(1, Location::new(1.0, 0.0), true)
+            .with_skill_expiring("forklift", Proficiency::Expert, now - chrono::Duration::days(1))];
+
+        let mut log = RecordingObserver::default();
+        assert!(GreedyPlanner::new().plan_with_observer(&tasks, &lapsed, &mut log).is_empty());
+        assert_eq!(log.events, ["reject task 1 worker 1: missing-skill"]);
+
+        // Renewed, the same worker qualifies again
+        let renewed = vec![Worker::new(1, Location::new(1.0, 0.0), true)
+            .with_skill_expiring("forklift", Proficiency::Expert, now + chrono::Duration::days(365))];
+        assert_eq!(GreedyPlanner::new().plan(&tasks, &renewed).len(), 1);
+    }
+
+    #[test]
+    fn test_skill_expiry_judged_at_planner_now() {
+        let now = chrono::DateTime::parse_from_rfc3339("2024-01-15T10:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::High).with_required_skill("forklift")];
+        let workers = vec![Worker::new(1, Location::new(1.0, 0.0), true)
+            .with_skill_expiring("forklift", Proficiency::Expert, now + chrono::Duration::days(1))];
+
+        assert_eq!(GreedyPlanner::new().with_now(now).plan(&tasks, &workers).len(), 1);
+        let next_week = now + chrono::Duration::days(7);
+        assert!(GreedyPlanner::new().with_now(next_week).plan(&tasks, &workers).is_empty());
+        assert!(GreedyPlanner::new().plan_at(&tasks, &workers, next_week).is_empty());
+        assert!(GreedyBatchPlanner::new().with_now(next_week).plan_batch(&tasks, &workers, 1).is_empty());
+    }
+
+    #[test]
+    fn test_explanations_name_chosen_worker_and_reason() {
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::Medium).with_required_skill("forklift")];
+        let workers = vec![
+            Worker::new(1, Location::new(1.0, 0.0), true),
+            Worker::new(2, Location::new(3.0, 4.0), true).with_skill("forklift", Proficiency::Competent),
+            Worker::new(3, Location::new(6.0, 8.0), true).with_skill("forklift", Proficiency::Competent),
+        ];
+
+        assert_eq!(GreedyPlanner::new().plan(&tasks, &workers)[0].explanation, None);
+
+        let assignments = GreedyPlanner::new().with_explanations().plan(&tasks, &workers);
+        let explanation = assignments[0].explanation.as_deref().unwrap();
+        assert!(explanation.starts_with("worker 2 chosen: lowest cost"), "{}", explanation);
+        assert!(explanation.contains("among 2 feasible of 3 considered"), "{}", explanation);
+        assert!(explanation.contains("has skill forklift"), "{}", explanation);
+
+        let batch = GreedyBatchPlanner::new().with_explanations().plan_batch(&tasks, &workers[2..], 2);
+        assert!(batch[0].explanation.as_deref().unwrap().starts_with("worker 3 chosen: only feasible worker"));
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingObserver {
+        events: Vec<String>,
+    }
+
+    impl PlannerObserver for RecordingObserver {
+        fn on_candidate_rejected(&mut self, task_id: TaskId, worker_id: WorkerId, reason: RejectionReason) {
+            let reason = match reason {
+                RejectionReason::WorkerUnavailable => "unavailable".to_string(),
+                RejectionReason::MissingSkill => "missing-skill".to_string(),
+                RejectionReason::AtCapacity => "at-capacity".to_string(),
+                RejectionReason::InsufficientCapacity { .. } => "insufficient-capacity".to_string(),
+                RejectionReason::IncompatibleTask(other) => format!("incompatible-with-{}", other),
+                RejectionReason::Reserved => "reserved".to_string(),
+                RejectionReason::OutsideZone => "outside-zone".to_string(),
+                RejectionReason::NotColocated { .. } => "not-colocated".to_string(),
+                RejectionReason::TeamIncomplete { .. } => "team-incomplete".to_string(),
+                RejectionReason::Infeasible => "infeasible".to_string(),
+                RejectionReason::TooExpensive { .. } => "too-expensive".to_string(),
+                RejectionReason::HigherCost { .. } => "higher-cost".to_string(),
+            };
+            self.events.push(format!("reject task {} worker {}: {}", task_id, worker_id, reason));
+        }
+
+        fn on_assignment(&mut self, assignment: &Assignment) {
+            self.events.push(format!("assign task {} worker {}", assignment.task_id, assignment.worker_id));
+        }
+    }
+
+    #[test]
+    fn test_observer_receives_callback_sequence() {
+        let planner = GreedyPlanner::new();
+
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High),
+            Task::new(2, Location::new(10.0, 10.0), Priority::Low),
+        ];
+        let workers = vec![
+            Worker::new(1, Location::new(1.0, 1.0), true),
+            Worker::new(2, Location::new(5.0, 5.0), false),
+            Worker::new(3, Location::new(9.0, 9.0), true),
+        ];
+
+        let mut observer = RecordingObserver::default();
+        let assignments = planner.plan_with_observer(&tasks, &workers, &mut observer);
+
+        assert_eq!(assignments, planner.plan(&tasks, &workers));
+        assert_eq!(
+            observer.events,
+            vec![
+                "reject task 1 worker 2: unavailable",
+                "reject task 1 worker 3: higher-cost",
+                "assign task 1 worker 1",
+                "reject task 2 worker 1: at-capacity",
+                "reject task 2 worker 2: unavailable",
+                "assign task 2 worker 3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_greedy_planner_requires_skill() {
+        let planner = GreedyPlanner::new();
+
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High).with_required_skill("forklift"),
+        ];
+        let workers = vec![
+            Worker::new(1, Location::new(0.5, 0.5), true),
+            Worker::new(2, Location::new(8.0, 8.0), true).with_skill("forklift", Proficiency::Novice),
+        ];
+
+        let assignments = planner.plan(&tasks, &workers);
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].worker_id, 2);
+    }
+
     #[test]
     fn test_worker_load_affects_cost() {
         let planner = GreedyPlanner::new();
@@ -290,4 +1584,109 @@ mod tests {
         // Should prefer worker with lower load
         assert_eq!(assignments[0].worker_id, 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_projected_positions_favor_approaching_worker() {
+        let now = chrono::DateTime::parse_from_rfc3339("2024-01-15T10:10:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::High)];
+        let workers = vec![
+            Worker::new(1, Location::new(8.0, 0.0), true),
+            // Last seen 10 minutes ago at x=20, walking toward the task at 1.5 units/minute
+            Worker::new(2, Location::new(20.0, 0.0), true)
+                .with_velocity(Velocity::new(-1.5, 0.0), now - chrono::Duration::minutes(10)),
+        ];
+        let planner = GreedyPlanner::new();
+
+        assert_eq!(planner.plan(&tasks, &workers)[0].worker_id, 1);
+        let projected = planner.plan_projected(&tasks, &workers, now);
+        assert_eq!(projected[0].worker_id, 2);
+        assert!((projected[0].estimated_cost - 5.0 * 0.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_margin_separates_clear_and_contested_assignments() {
+        let planner = GreedyPlanner::new();
+        let estimator = DistanceCostEstimator;
+
+        // Worker 1 is far closer than worker 2
+        let clear_task = Task::new(1, Location::new(0.0, 0.0), Priority::Medium);
+        let clear_workers = vec![
+            Worker::new(1, Location::new(1.0, 0.0), true),
+            Worker::new(2, Location::new(30.0, 0.0), true),
+        ];
+        let clear = &planner.plan(std::slice::from_ref(&clear_task), &clear_workers)[0];
+        let expected = estimator.estimate(&clear_task, &clear_workers[1]) - estimator.estimate(&clear_task, &clear_workers[0]);
+        assert_eq!(clear.worker_id, 1);
+        assert!((clear.margin.unwrap() - expected).abs() < 1e-9);
+        assert!(!clear.is_contested(1.0));
+
+        // Both workers are almost exactly as far away
+        let tied_workers = vec![
+            Worker::new(1, Location::new(5.0, 0.0), true),
+            Worker::new(2, Location::new(-5.05, 0.0), true),
+        ];
+        let tied = &planner.plan(std::slice::from_ref(&clear_task), &tied_workers)[0];
+        assert_eq!(tied.worker_id, 1);
+        assert!(tied.margin.unwrap() > 0.0 && tied.margin.unwrap() < 0.1);
+        assert!(tied.is_contested(1.0));
+
+        // Without a runner-up there is nothing to compare against
+        let alone = &planner.plan(&[clear_task], &clear_workers[..1])[0];
+        assert_eq!(alone.margin, None);
+    }
+
+    #[test]
+    fn test_reserved_worker_only_takes_critical_tasks() {
+        let workers = vec![
+            Worker::new(1, Location::new(0.0, 0.0), true).with_max_tasks(3),
+            // The float worker is closest to everything
+            Worker::new(2, Location::new(10.0, 0.0), true).with_max_tasks(3),
+        ];
+        let routine = vec![
+            Task::new(1, Location::new(10.0, 1.0), Priority::High),
+            Task::new(2, Location::new(11.0, 0.0), Priority::Medium),
+        ];
+
+        let planner = GreedyBatchPlanner::new().with_reserved_workers([2]);
+        let mut log = RecordingObserver::default();
+        let assignments = planner.plan_batch_with_observer(&routine, &workers, 3, &mut log);
+        assert_eq!(assignments.len(), 2);
+        assert!(assignments.iter().all(|a| a.worker_id == 1));
+        assert!(log.events.contains(&"reject task 1 worker 2: reserved".to_string()));
+
+        let mut with_urgent = routine.clone();
+        with_urgent.push(Task::new(3, Location::new(10.0, -1.0), Priority::Critical));
+        let assignments = planner.plan_batch(&with_urgent, &workers, 3);
+        assert_eq!(assignments.iter().find(|a| a.task_id == 3).unwrap().worker_id, 2);
+        assert_eq!(assignments.iter().filter(|a| a.worker_id == 2).count(), 1);
+
+        let single = GreedyPlanner::new().with_reserved_workers([2]).plan(&with_urgent, &workers);
+        assert_eq!(single.iter().find(|a| a.task_id == 3).unwrap().worker_id, 2);
+        assert_eq!(single.len(), 2);
+    }
+
+    #[test]
+    fn test_plan_iter_yields_same_assignments_as_plan() {
+        let tasks = vec![
+            Task::new(1, Location::new(5.0, 0.0), Priority::Low),
+            Task::new(2, Location::new(0.0, 5.0), Priority::Critical),
+            Task::new(3, Location::new(9.0, 9.0), Priority::High).with_required_skill("forklift"),
+            Task::new(4, Location::new(1.0, 1.0), Priority::Medium),
+        ];
+        let workers = vec![
+            Worker::new(1, Location::new(0.0, 0.0), true),
+            Worker::new(2, Location::new(10.0, 10.0), true).with_skill("forklift", Proficiency::Competent),
+            Worker::new(3, Location::new(4.0, 0.0), true),
+        ];
+        let planner = GreedyPlanner::new();
+
+        let streamed: Vec<_> = planner.plan_iter(&tasks, &workers).collect();
+        assert_eq!(streamed, planner.plan(&tasks, &workers));
+        assert_eq!(streamed.len(), 3);
+
+        // The first assignment is available before the rest are decided
+        assert_eq!(planner.plan_iter(&tasks, &workers).next().unwrap().task_id, 2);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::planner::traits::{CostEstimator, TimeCostEstimator};
+use crate::types::{Task, Worker, WorkerId};
+use std::collections::HashMap;
+
+/// Running total of actual/estimated duration ratios
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Observed {
+    ratio_sum: f64,
+    count: usize,
+}
+
+/// Time-based estimator that corrects task durations with each worker's history
+///
+/// Completions are grouped by worker and required skill. For each group the
+/// estimator tracks how long tasks actually took relative to their estimate,
+/// and scales new estimates by that ratio before costing them like
+/// [`TimeCostEstimator`]. The ratio is blended with a neutral 1.0 weighted as
+/// `prior_weight` completions, so a single outlier moves estimates only a little
+/// and the learned value takes over as history builds up.
+#[derive(Debug)]
+pub struct LearnedCostEstimator {
+    base: TimeCostEstimator,
+    prior_weight: f64,
+    history: HashMap<(WorkerId, Option<String>), Observed>,
+}
+
+impl Default for LearnedCostEstimator {
+    fn default() -> Self {
+        Self::new(TimeCostEstimator::default())
+    }
+}
+
+impl LearnedCostEstimator {
+    pub fn new(base: TimeCostEstimator) -> Self {
+        Self {
+            base,
+            prior_weight: 3.0, // history counts fully after a handful of completions
+            history: HashMap::new(),
+        }
+    }
+
+    /// Weight of the neutral prior, in number of completions (0 trusts history fully)
+    pub fn with_prior_weight(mut self, prior_weight: f64) -> Self {
+        self.prior_weight = prior_weight.max(0.0);
+        self
+    }
+
+    /// Add a completed task to the history
+    ///
+    /// Non-positive or non-finite durations are ignored.
+    pub fn record(&mut self, worker_id: WorkerId, skill: Option<&str>, estimated_minutes: f64, actual_minutes: f64) {
+        let valid = |minutes: f64| minutes.is_finite() && minutes > 0.0;
+        if !valid(estimated_minutes) || !valid(actual_minutes) {
+            return;
+        }
+        let observed = self.history.entry((worker_id, skill.map(str::to_string))).or_default();
+        observed.ratio_sum += actual_minutes / estimated_minutes;
+        observed.count += 1;
+    }
+
+    /// Multiplier applied to `task`'s duration for `worker`, 1.0 without history
+    pub fn duration_factor(&self, task: &Task, worker: &Worker) -> f64 {
+        let key = (worker.id, task.required_skill.clone());
+        match self.history.get(&key) {
+            Some(observed) => {
+                (self.prior_weight + observed.ratio_sum) / (self.prior_weight + observed.count as f64)
+            }
+            None => 1.0,
+        }
+    }
+
+    /// Duration the worker is expected to need for `task`, in minutes, before proficiency scaling
+    pub fn learned_duration(&self, task: &Task, worker: &Worker) -> f64 {
+        task.estimated_duration.unwrap_or(self.base.default_duration) * self.duration_factor(task, worker)
+    }
+}
+
+impl CostEstimator for LearnedCostEstimator {
+    fn estimate(&self, task: &Task, worker: &Worker) -> f64 {
+        let factor = self.duration_factor(task, worker);
+        if factor == 1.0 {
+            return self.base.estimate(task, worker);
+        }
+        let adjusted = task.clone().with_duration(self.learned_duration(task, worker));
+        self.base.estimate(&adjusted, worker)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Location, Priority, Proficiency};
+
+    fn setup() -> (Task, Worker) {
+        let task = Task::new(1, Location::new(0.0, 0.0), Priority::Medium)
+            .with_duration(20.0)
+            .with_required_skill("forklift");
+        let worker = Worker::new(1, Location::new(0.0, 0.0), true).with_skill("forklift", Proficiency::Competent);
+        (task, worker)
+    }
+
+    #[test]
+    fn test_without_history_matches_time_estimator() {
+        let (task, worker) = setup();
+        let learned = LearnedCostEstimator::default();
+        let base = TimeCostEstimator::default();
+        assert_eq!(learned.estimate(&task, &worker), base.estimate(&task, &worker));
+    }
+
+    #[test]
+    fn test_estimate_shifts_toward_observed_durations() {
+        let (task, worker) = setup();
+        let mut learned = LearnedCostEstimator::default();
+        let untrained = learned.learned_duration(&task, &worker);
+
+        // This worker consistently takes 50% longer than estimated on forklift tasks
+        learned.record(1, Some("forklift"), 20.0, 30.0);
+        let after_one = learned.learned_duration(&task, &worker);
+        for _ in 0..20 {
+            learned.record(1, Some("forklift"), 20.0, 30.0);
+        }
+        let after_many = learned.learned_duration(&task, &worker);
+
+        assert!(untrained < after_one && after_one < after_many);
+        assert!((after_many - 30.0).abs() < 1.5, "learned {} should approach 30", after_many);
+        assert!(learned.estimate(&task, &worker) > TimeCostEstimator::default().estimate(&task, &worker));
+    }
+
+    #[test]
+    fn test_history_scales_configured_default_duration() {
+        let (mut task, worker) = setup();
+        task.estimated_duration = None;
+        let mut learned = LearnedCostEstimator::new(TimeCostEstimator::default().with_default_duration(10.0)).with_prior_weight(0.0);
+        assert_eq!(learned.learned_duration(&task, &worker), 10.0);
+
+        learned.record(1, Some("forklift"), 10.0, 20.0);
+        assert_eq!(learned.learned_duration(&task, &worker), 20.0);
+        let twice = TimeCostEstimator::default().with_default_duration(20.0);
+        assert!((learned.estimate(&task, &worker) - twice.estimate(&task, &worker)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_history_is_per_worker_and_skill() {
+        let (task, worker) = setup();
+        let mut learned = LearnedCostEstimator::default().with_prior_weight(0.0);
+        learned.record(2, Some("forklift"), 20.0, 40.0);
+        learned.record(1, None, 20.0, 10.0);
+        learned.record(1, Some("forklift"), f64::NAN, 10.0);
+        assert_eq!(learned.duration_factor(&task, &worker), 1.0);
+
+        learned.record(1, Some("forklift"), 20.0, 10.0);
+        assert_eq!(learned.duration_factor(&task, &worker), 0.5);
+    }
+}
@@ -0,0 +1,374 @@
+use crate::planner::traits::TaskPlanner;
+use crate::types::{Assignment, Location, Task, TaskId, Worker, WorkerId};
+use crate::validation::is_plannable;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Batch assignments in visiting order together with the distance they cover
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinTravelPlan {
+    /// Each worker's assignments are contiguous and in route order; an
+    /// assignment's cost is the leg travelled to reach and complete its task
+    pub assignments: Vec<Assignment>,
+    /// Sum of every worker's route distance
+    pub total_distance: f64,
+}
+
+/// Total distance travelled when each worker visits its tasks in assignment order
+///
+/// A route starts at the worker's location and runs through each pick location
+/// and its dropoff, if any. Assignments referring to unknown tasks or workers
+/// are ignored.
+pub fn route_distance(tasks: &[Task], workers: &[Worker], assignments: &[Assignment]) -> f64 {
+    let tasks: HashMap<TaskId, &Task> = tasks.iter().map(|task| (task.id, task)).collect();
+    let workers: HashMap<WorkerId, &Worker> = workers.iter().map(|worker| (worker.id, worker)).collect();
+
+    let mut positions: HashMap<WorkerId, Location> = HashMap::new();
+    let mut total = 0.0;
+    for assignment in assignments {
+        let (Some(task), Some(worker)) = (tasks.get(&assignment.task_id), workers.get(&assignment.worker_id)) else {
+            continue;
+        };
+        let position = positions.entry(worker.id).or_insert_with(|| worker.location.clone());
+        total += position.distance_to(&task.location) + task.dropoff_distance();
+        *position = end_of(task).clone();
+    }
+    total
+}
+
+/// Where a worker stands after completing `task`
+fn end_of(task: &Task) -> &Location {
+    task.dropoff.as_ref().unwrap_or(&task.location)
+}
+
+/// Batch planner that minimizes the combined route distance of all workers
+///
+/// Uses a Clarke-Wright style savings heuristic instead of picking the nearest
+/// worker per task:
+///
+/// 1. Every task starts as its own route from the nearest worker able to take it.
+/// 2. Routes are chained end-to-start in order of decreasing savings (the
+///    distance from the nearest worker to the next task, minus the distance
+///    from the previous task to it), as long as the chain stays within
+///    `max_tasks_per_worker`, contains no incompatible tasks and some worker
+///    can take all of it within its own `max_tasks`.
+/// 3. Chains go to the nearest free worker that can take them, highest
+///    priority chains first, one chain per worker.
+/// 4. Tasks from chains that found no worker are appended, highest priority
+///    first, wherever they add the least distance.
+///
+/// Availability, skills, capacity and incompatibilities are respected as in
+/// `GreedyBatchPlanner`. The result is an approximation, not an optimum, and
+/// priority only decides which tasks win when not everything can be assigned.
+#[derive(Debug, Clone)]
+pub struct MinTravelPlanner {
+    max_tasks_per_worker: usize,
+    now: Option<DateTime<Utc>>, // reference time for skill expiry; `None` uses the current time
+}
+
+impl MinTravelPlanner {
+    pub fn new(max_tasks_per_worker: usize) -> Self {
+        Self { max_tasks_per_worker, now: None }
+    }
+
+    /// Check skill expiry at `now` instead of the current time, for reproducible plans
+    pub fn with_now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Plan routes and report the total distance they cover
+    pub fn plan_routes(&self, tasks: &[Task], workers: &[Worker]) -> MinTravelPlan {
+        self.plan_routes_at(tasks, workers, self.now.unwrap_or_else(Utc::now))
+    }
+
+    fn plan_routes_at(&self, tasks: &[Task], workers: &[Worker], now: DateTime<Utc>) -> MinTravelPlan {
+        if !is_plannable(tasks, workers) {
+            return MinTravelPlan {
+                assignments: Vec::new(),
+                total_distance: 0.0,
+            };
+        }
+        let eligible: Vec<Vec<usize>> = tasks
+            .iter()
+            .map(|task| {
+                (0..workers.len())
+                    .filter(|&w| can_take(&workers[w], std::slice::from_ref(&task), now))
+                    .collect()
+            })
+            .collect();
+
+        // Distance from the nearest eligible worker, i.e. the cost of serving a task on its own
+        let base: Vec<f64> = tasks
+            .iter()
+            .zip(&eligible)
+            .map(|(task, eligible)| {
+                eligible
+                    .iter()
+                    .map(|&w| workers[w].location.distance_to(&task.location))
+                    .fold(f64::INFINITY, f64::min)
+            })
+            .collect();
+
+        let plannable: Vec<usize> = (0..tasks.len()).filter(|&t| !eligible[t].is_empty()).collect();
+        let chains = self.build_chains(tasks, workers, &plannable, &base, now);
+
+        let mut routes: Vec<Option<Vec<usize>>> = vec![None; workers.len()];
+        let leftovers = assign_chains(tasks, workers, chains, &mut routes, now);
+        self.place_leftovers(tasks, workers, leftovers, &mut routes, now);
+
+        let mut assignments = Vec::new();
+        let mut total_distance = 0.0;
+        let mut order: Vec<usize> = (0..workers.len()).collect();
+        order.sort_by_key(|&w| workers[w].id);
+        for w in order {
+            let Some(route) = &routes[w] else { continue };
+            let mut position = &workers[w].location;
+            for &t in route {
+                let leg = position.distance_to(&tasks[t].location) + tasks[t].dropoff_distance();
+                total_distance += leg;
+                assignments.push(Assignment::new(tasks[t].id, workers[w].id, leg));
+                position = end_of(&tasks[t]);
+            }
+        }
+
+        MinTravelPlan {
+            assignments,
+            total_distance,
+        }
+    }
+
+    /// Merge single-task chains by decreasing savings
+    fn build_chains(&self, tasks: &[Task], workers: &[Worker], plannable: &[usize], base: &[f64], now: DateTime<Utc>) -> Vec<Vec<usize>> {
+        let mut savings = Vec::new();
+        for &i in plannable {
+            for &j in plannable {
+                if i != j {
+                    let saving = base[j] - end_of(&tasks[i]).distance_to(&tasks[j].location);
+                    if saving > 0.0 {
+                        savings.push((saving, i, j));
+                    }
+                }
+            }
+        }
+        savings.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        // Chains are keyed by their first task; `chain_of` maps every task to that key
+        let mut chains: HashMap<usize, Vec<usize>> = plannable.iter().map(|&t| (t, vec![t])).collect();
+        let mut chain_of: HashMap<usize, usize> = plannable.iter().map(|&t| (t, t)).collect();
+
+        for (_, i, j) in savings {
+            let (head_i, head_j) = (chain_of[&i], chain_of[&j]);
+            // `j` must start a different chain that can follow the one `i` ends
+            if head_i == head_j || head_j != j || chains[&head_i].last() != Some(&i) {
+                continue;
+            }
+            let (first, second) = (&chains[&head_i], &chains[&head_j]);
+            if first.len() + second.len() > self.max_tasks_per_worker {
+                continue;
+            }
+            let compatible = first
+                .iter()
+                .all(|&a| second.iter().all(|&b| tasks[a].is_compatible_with(&tasks[b])));
+            if !compatible {
+                continue;
+            }
+            let merged: Vec<&Task> = first.iter().chain(second).map(|&t| &tasks[t]).collect();
+            if !workers.iter().any(|worker| can_take(worker, &merged, now)) {
+                continue;
+            }
+
+            let second = chains.remove(&head_j).unwrap();
+            for &t in &second {
+                chain_of.insert(t, head_i);
+            }
+            chains.get_mut(&head_i).unwrap().extend(second);
+        }
+
+        let mut chains: Vec<Vec<usize>> = chains.into_values().collect();
+        chains.sort_by_key(|chain| chain[0]);
+        chains
+    }
+
+    /// Append leftover tasks one at a time where they add the least distance
+    fn place_leftovers(&self, tasks: &[Task], workers: &[Worker], mut leftovers: Vec<usize>, routes: &mut [Option<Vec<usize>>], now: DateTime<Utc>) {
+        leftovers.sort_by(|&a, &b| {
+            tasks[b].priority.to_numeric().cmp(&tasks[a].priority.to_numeric()).then(a.cmp(&b))
+        });
+
+        for t in leftovers {
+            let task = &tasks[t];
+            let mut best: Option<(f64, usize)> = None;
+            for (w, worker) in workers.iter().enumerate() {
+                let route = routes[w].as_deref().unwrap_or(&[]);
+                if route.len() >= self.max_tasks_per_worker
+                    || route.iter().any(|&other| !task.is_compatible_with(&tasks[other]))
+                {
+                    continue;
+                }
+                let mut candidate: Vec<&Task> = route.iter().map(|&other| &tasks[other]).collect();
+                candidate.push(task);
+                if !can_take(worker, &candidate, now) {
+                    continue;
+                }
+                let from = route.last().map(|&last| end_of(&tasks[last])).unwrap_or(&worker.location);
+                let added = from.distance_to(&task.location);
+                if best.is_none_or(|(cost, _)| added < cost) {
+                    best = Some((added, w));
+                }
+            }
+            if let Some((_, w)) = best {
+                routes[w].get_or_insert_with(Vec::new).push(t);
+            }
+        }
+    }
+}
+
+/// Give each chain to the nearest free worker that can take it, highest
+/// priority first; returns the tasks of chains no worker could take
+fn assign_chains(tasks: &[Task], workers: &[Worker], mut chains: Vec<Vec<usize>>, routes: &mut [Option<Vec<usize>>], now: DateTime<Utc>) -> Vec<usize> {
+    let priority = |chain: &Vec<usize>| chain.iter().map(|&t| tasks[t].priority.to_numeric()).max().unwrap_or(0);
+    let mut leftovers = Vec::new();
+
+    while let Some(top) = chains.iter().map(priority).max() {
+        let mut best: Option<(f64, usize, usize)> = None;
+        for (c, chain) in chains.iter().enumerate().filter(|(_, chain)| priority(chain) == top) {
+            let members: Vec<&Task> = chain.iter().map(|&t| &tasks[t]).collect();
+            for (w, worker) in workers.iter().enumerate() {
+                if routes[w].is_some() || !can_take(worker, &members, now) {
+                    continue;
+                }
+                let distance = worker.location.distance_to(&members[0].location);
+                if best.is_none_or(|(cost, _, _)| distance < cost) {
+                    best = Some((distance, c, w));
+                }
+            }
+        }
+
+        match best {
+            Some((_, c, w)) => routes[w] = Some(chains.remove(c)),
+            None => {
+                let (stuck, rest): (Vec<_>, Vec<_>) = chains.into_iter().partition(|chain| priority(chain) == top);
+                leftovers.extend(stuck.into_iter().flatten());
+                chains = rest;
+            }
+        }
+    }
+    leftovers
+}
+
+/// Whether `worker` can take all of `tasks` at once, judging skills at `now`
+fn can_take(worker: &Worker, tasks: &[&Task], now: DateTime<Utc>) -> bool {
+    if !worker.can_accept_task() || tasks.len() > worker.max_tasks {
+        return false;
+    }
+    let mut used = 0.0;
+    for task in tasks {
+        if !worker.can_handle_at(task, now) || !worker.has_room_for(task, used) {
+            return false;
+        }
+        used += task.size;
+    }
+    true
+}
+
+impl TaskPlanner for MinTravelPlanner {
+    fn plan(&self, tasks: &[Task], workers: &[Worker]) -> Vec<Assignment> {
+        self.plan_routes(tasks, workers).assignments
+    }
+
+    fn plan_at(&self, tasks: &[Task], workers: &[Worker], now: DateTime<Utc>) -> Vec<Assignment> {
+        self.plan_routes_at(tasks, workers, now).assignments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planner::greedy::GreedyBatchPlanner;
+    use crate::types::{Priority, Proficiency};
+
+    fn task(id: TaskId, x: f64, y: f64) -> Task {
+        Task::new(id, Location::new(x, y), Priority::Medium)
+    }
+
+    #[test]
+    fn test_route_distance_follows_assignment_order() {
+        let tasks = vec![task(1, 3.0, 4.0), task(2, 3.0, 0.0).with_dropoff(Location::new(0.0, 0.0))];
+        let workers = vec![Worker::new(1, Location::new(0.0, 0.0), true)];
+        let assignments = vec![Assignment::new(1, 1, 0.0), Assignment::new(2, 1, 0.0)];
+        // 5 to the first pick, 4 to the second and 3 back to the dropoff
+        assert_eq!(route_distance(&tasks, &workers, &assignments), 12.0);
+    }
+
+    #[test]
+    fn test_clustered_instance_travels_less_than_greedy() {
+        // Two tight clusters either side of worker 1; worker 2 is far from both.
+        // Greedy hands worker 1 the nearest task of each cluster, sending both
+        // workers across the aisle.
+        let tasks = vec![
+            task(1, -10.0, 0.0),
+            task(2, 10.0, 0.0),
+            task(3, -11.0, 0.0),
+            task(4, 11.0, 0.0),
+        ];
+        let workers = vec![
+            Worker::new(1, Location::new(0.0, 0.0), true).with_max_tasks(2),
+            Worker::new(2, Location::new(0.0, 30.0), true).with_max_tasks(2),
+        ];
+
+        let greedy = GreedyBatchPlanner::new().plan_batch(&tasks, &workers, 2);
+        let greedy_distance = route_distance(&tasks, &workers, &greedy);
+
+        let plan = MinTravelPlanner::new(2).plan_routes(&tasks, &workers);
+        assert_eq!(plan.assignments.len(), 4);
+        assert!((plan.total_distance - route_distance(&tasks, &workers, &plan.assignments)).abs() < 1e-9);
+        assert!(
+            plan.total_distance < greedy_distance,
+            "min travel {} should beat greedy {}",
+            plan.total_distance,
+            greedy_distance
+        );
+
+        // Each worker clears one cluster
+        let worker_of = |task_id| plan.assignments.iter().find(|a| a.task_id == task_id).unwrap().worker_id;
+        assert_eq!(worker_of(1), worker_of(3));
+        assert_eq!(worker_of(2), worker_of(4));
+        assert_ne!(worker_of(1), worker_of(2));
+    }
+
+    #[test]
+    fn test_respects_skills_and_task_limit() {
+        let tasks = vec![
+            task(1, 1.0, 0.0).with_required_skill("forklift"),
+            task(2, 2.0, 0.0),
+            task(3, 3.0, 0.0),
+        ];
+        let workers = vec![
+            Worker::new(1, Location::new(0.0, 0.0), true).with_max_tasks(2),
+            Worker::new(2, Location::new(50.0, 0.0), true)
+                .with_max_tasks(5)
+                .with_skill("forklift", Proficiency::Competent),
+            Worker::new(3, Location::new(0.0, 0.0), false),
+        ];
+
+        let plan = MinTravelPlanner::new(2).plan_routes(&tasks, &workers);
+        assert_eq!(plan.assignments.len(), 3);
+        assert!(plan.assignments.iter().all(|a| a.worker_id != 3));
+        let forklift = plan.assignments.iter().find(|a| a.task_id == 1).unwrap();
+        assert_eq!(forklift.worker_id, 2);
+        assert!(plan.assignments.iter().filter(|a| a.worker_id == 2).count() <= 2);
+    }
+
+    #[test]
+    fn test_higher_priority_wins_when_slots_run_out() {
+        let tasks = vec![
+            task(1, 1.0, 0.0),
+            Task::new(2, Location::new(40.0, 0.0), Priority::Critical),
+        ];
+        let workers = vec![Worker::new(1, Location::new(0.0, 0.0), true)];
+
+        let assignments = MinTravelPlanner::new(1).plan(&tasks, &workers);
+        assert_eq!(assignments, vec![Assignment::new(2, 1, 40.0)]);
+    }
+}
@@ -1,6 +1,22 @@
 pub mod traits;
 pub mod greedy;
+pub mod observer;
+pub mod cache;
+pub mod learned;
+pub mod min_travel;
+pub mod per_worker;
+pub mod dependency;
+pub mod splitting;
+#[cfg(test)]
+mod properties;
 
 // Re-export for convenience
 pub use traits::*;
-pub use greedy::*;
\ No newline at end of file
+pub use greedy::*;
+pub use observer::*;
+pub use cache::*;
+pub use learned::*;
+pub use min_travel::*;
+pub use per_worker::*;
+pub use dependency::*;
+pub use splitting::*;
\ No newline at end of file
@@ -0,0 +1,80 @@
+use crate::types::{Assignment, TaskId, WorkerId};
+use serde::Serialize;
+
+/// Why a worker was not chosen for a task
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectionReason {
+    /// The worker is unavailable or fully loaded
+    WorkerUnavailable,
+    /// The worker lacks the skill the task requires
+    MissingSkill,
+    /// The worker already received its maximum number of tasks in this plan
+    AtCapacity,
+    /// The task is larger than the worker's remaining capacity units
+    InsufficientCapacity { size: f64, remaining: f64 },
+    /// The worker already carries a task that conflicts with this one
+    IncompatibleTask(TaskId),
+    /// The worker is held back for `Critical` tasks
+    Reserved,
+    /// The task is outside the zone of the worker's first task in this plan
+    OutsideZone,
+    /// The worker was cheaper than a team member chosen but stands more than
+    /// `radius` from the team's lead
+    NotColocated { radius: f64 },
+    /// The task needs `required` workers together but at most `available`
+    /// feasible ones stand within the team radius
+    TeamIncomplete { required: usize, available: usize },
+    /// The cost estimator ruled the assignment out with an infinite cost,
+    /// e.g. the worker would start outside the task's service window
+    Infeasible,
+    /// The assignment would cost more than the planner's `max_cost`
+    TooExpensive { cost: f64, max_cost: f64 },
+    /// The worker was feasible but another worker was cheaper
+    HigherCost { cost: f64, best_cost: f64 },
+}
+
+/// Hooks invoked by planners while they build a plan
+///
+/// All methods default to no-ops so observers only implement what they need.
+pub trait PlannerObserver {
+    /// A worker was considered for a task and not chosen
+    fn on_candidate_rejected(&mut self, _task_id: TaskId, _worker_id: WorkerId, _reason: RejectionReason) {}
+
+    /// An assignment was made
+    fn on_assignment(&mut self, _assignment: &Assignment) {}
+}
+
+/// Observer that ignores every event
+#[derive(Debug, Default)]
+pub struct NoopObserver;
+
+impl PlannerObserver for NoopObserver {}
+
+/// Observer recording assignments in the order the planner decided them
+///
+/// Steps are numbered from 0.
+#[derive(Debug, Default)]
+pub struct AssignmentLog {
+    steps: Vec<(usize, Assignment)>,
+}
+
+impl AssignmentLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn steps(&self) -> &[(usize, Assignment)] {
+        &self.steps
+    }
+
+    pub fn into_steps(self) -> Vec<(usize, Assignment)> {
+        self.steps
+    }
+}
+
+impl PlannerObserver for AssignmentLog {
+    fn on_assignment(&mut self, assignment: &Assignment) {
+        self.steps.push((self.steps.len(), assignment.clone()));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::planner::traits::CostEstimator;
+use crate::types::{Task, Worker, WorkerId};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Cost estimator that judges each worker with the model suited to it
+///
+/// The model for a worker is chosen by [`cost_estimator_for`](Self::cost_estimator_for):
+/// a model registered for that worker ID, otherwise the first registered skill
+/// model whose skill the worker has, even a lapsed one, otherwise the default. Costs from
+/// different models are compared directly, so they should use comparable
+/// units (e.g. all minutes).
+pub struct PerWorkerCostEstimator {
+    default: Box<dyn CostEstimator>,
+    by_worker: HashMap<WorkerId, Box<dyn CostEstimator>>,
+    by_skill: Vec<(String, Box<dyn CostEstimator>)>,
+}
+
+impl PerWorkerCostEstimator {
+    pub fn new(default: impl CostEstimator + 'static) -> Self {
+        Self {
+            default: Box::new(default),
+            by_worker: HashMap::new(),
+            by_skill: Vec::new(),
+        }
+    }
+
+    /// Use `estimator` for one specific worker
+    pub fn with_worker(mut self, worker_id: WorkerId, estimator: impl CostEstimator + 'static) -> Self {
+        self.by_worker.insert(worker_id, Box::new(estimator));
+        self
+    }
+
+    /// Use `estimator` for workers with `skill`, e.g. forklift drivers
+    ///
+    /// Skills are checked in the order they were added.
+    pub fn with_skill(mut self, skill: impl Into<String>, estimator: impl CostEstimator + 'static) -> Self {
+        self.by_skill.push((skill.into(), Box::new(estimator)));
+        self
+    }
+
+    /// The model that judges `worker`
+    pub fn cost_estimator_for(&self, worker: &Worker) -> &dyn CostEstimator {
+        if let Some(estimator) = self.by_worker.get(&worker.id) {
+            return estimator.as_ref();
+        }
+        self.by_skill
+            .iter()
+            .find(|(skill, _)| worker.skills.contains_key(skill))
+            .map(|(_, estimator)| estimator.as_ref())
+            .unwrap_or(self.default.as_ref())
+    }
+}
+
+impl fmt::Debug for PerWorkerCostEstimator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut workers: Vec<_> = self.by_worker.keys().collect();
+        workers.sort();
+        f.debug_struct("PerWorkerCostEstimator")
+            .field("workers", &workers)
+            .field("skills", &self.by_skill.iter().map(|(skill, _)| skill).collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CostEstimator for PerWorkerCostEstimator {
+    fn estimate(&self, task: &Task, worker: &Worker) -> f64 {
+        self.cost_estimator_for(worker).estimate(task, worker)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planner::greedy::GreedyPlanner;
+    use crate::planner::traits::{DistanceCostEstimator, TaskPlanner, TimeCostEstimator};
+    use crate::types::{Location, Priority, Proficiency};
+
+    /// Flat cost, to tell which model was used
+    struct Fixed(f64);
+
+    impl CostEstimator for Fixed {
+        fn estimate(&self, _task: &Task, _worker: &Worker) -> f64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_selects_worker_then_skill_then_default() {
+        let estimators = PerWorkerCostEstimator::new(Fixed(1.0))
+            .with_skill("forklift", Fixed(2.0))
+            .with_worker(3, Fixed(3.0));
+        let task = Task::new(1, Location::new(0.0, 0.0), Priority::Medium);
+        let picker = Worker::new(1, Location::new(0.0, 0.0), true);
+        let driver = Worker::new(2, Location::new(0.0, 0.0), true).with_skill("forklift", Proficiency::Expert);
+        let special = Worker::new(3, Location::new(0.0, 0.0), true).with_skill("forklift", Proficiency::Expert);
+
+        assert_eq!(estimators.estimate(&task, &picker), 1.0);
+        assert_eq!(estimators.estimate(&task, &driver), 2.0);
+        assert_eq!(estimators.estimate(&task, &special), 3.0);
+    }
+
+    #[test]
+    fn test_assignment_follows_each_workers_model() {
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::Medium).with_duration(20.0)];
+        let workers = vec![
+            // Closer, but forklift work is costed in time: 5 travel + 20 minutes
+            Worker::new(1, Location::new(3.0, 4.0), true).with_skill("forklift", Proficiency::Competent),
+            // Farther, costed by distance alone: 10
+            Worker::new(2, Location::new(6.0, 8.0), true),
+        ];
+
+        let global = GreedyPlanner::new().plan(&tasks, &workers);
+        assert_eq!(global[0].worker_id, 1);
+
+        let estimators = PerWorkerCostEstimator::new(DistanceCostEstimator)
+            .with_skill("forklift", TimeCostEstimator::default());
+        let per_worker = GreedyPlanner::with_cost_estimator(estimators).plan(&tasks, &workers);
+        assert_eq!(per_worker[0].worker_id, 2);
+        assert!((per_worker[0].estimated_cost - 10.0).abs() < 1e-9);
+    }
+}
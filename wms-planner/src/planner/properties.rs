@@ -0,0 +1,218 @@
+//! Property-based checks that every `TaskPlanner` must satisfy
+//!
+//! New planners get coverage by adding a `proptest!` case that calls
+//! [`assert_plan_invariants`].
+
+use crate::planner::cache::CachingPlanner;
+use crate::planner::dependency::DependencyPlanner;
+use crate::planner::greedy::GreedyPlanner;
+use crate::planner::min_travel::MinTravelPlanner;
+use crate::planner::splitting::SplittingPlanner;
+use crate::planner::traits::{TaskPlanner, TimeCostEstimator};
+use crate::types::{Assignment, Location, Priority, Proficiency, Task, TaskId, Worker};
+use proptest::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+const SKILLS: [&str; 2] = ["forklift", "reach-truck"];
+
+fn location() -> impl Strategy<Value = Location> {
+    (-100.0..100.0f64, -100.0..100.0f64).prop_map(|(x, y)| Location::new(x, y))
+}
+
+fn priority() -> impl Strategy<Value = Priority> {
+    prop_oneof![
+        Just(Priority::Low),
+        Just(Priority::Medium),
+        Just(Priority::High),
+        Just(Priority::Critical),
+    ]
+}
+
+fn proficiency() -> impl Strategy<Value = Proficiency> {
+    prop_oneof![
+        Just(Proficiency::Novice),
+        Just(Proficiency::Competent),
+        Just(Proficiency::Expert),
+    ]
+}
+
+/// Up to 12 tasks with unique IDs, optional skills, sizes, dropoffs, incompatibilities and dependencies
+fn tasks() -> impl Strategy<Value = Vec<Task>> {
+    let task = (
+        location(),
+        priority(),
+        proptest::option::of(1.0..60.0f64),
+        proptest::option::of(proptest::sample::select(&SKILLS[..])),
+        0.0..0.6f64,
+        proptest::option::weighted(0.3, location()),
+        proptest::collection::hash_set(1..=12 as TaskId, 0..3),
+        proptest::collection::vec(1..=12 as TaskId, 0..2),
+    );
+    proptest::collection::vec(task, 0..12).prop_map(|specs| {
+        specs
+            .into_iter()
+            .enumerate()
+            .map(|(i, (location, priority, duration, skill, size, dropoff, incompatible, depends_on))| {
+                let id = i as TaskId + 1;
+                let mut task = Task::new(id, location, priority)
+                    .with_size(size)
+                    .with_incompatible(incompatible.into_iter().filter(|&other| other != id));
+                task.estimated_duration = duration;
+                task.required_skill = skill.map(str::to_string);
+                task.dropoff = dropoff;
+                task.depends_on = depends_on;
+                task
+            })
+            .collect()
+    })
+}
+
+/// Up to 6 workers with unique IDs, random availability, load, limits, capacity and skills
+fn workers() -> impl Strategy<Value = Vec<Worker>> {
+    let worker = (
+        location(),
+        proptest::bool::weighted(0.8),
+        0.0..1.2f64,
+        1..5usize,
+        0.5..3.0f64,
+        proptest::collection::hash_map(proptest::sample::select(&SKILLS[..]), proficiency(), 0..=2),
+    );
+    proptest::collection::vec(worker, 0..6).prop_map(|specs| {
+        specs
+            .into_iter()
+            .enumerate()
+            .map(|(i, (location, available, load, max_tasks, capacity, skills))| {
+                skills.into_iter().fold(
+                    Worker::new(i as u32 + 1, location, available)
+                        .with_load(load)
+                        .with_max_tasks(max_tasks)
+                        .with_capacity(capacity),
+                    |worker, (skill, proficiency)| worker.with_skill(skill, proficiency),
+                )
+            })
+            .collect()
+    })
+}
+
+/// Invariants every plan must hold, whatever the planner
+fn assert_plan_invariants(tasks: &[Task], workers: &[Worker], assignments: &[Assignment]) -> Result<(), TestCaseError> {
+    let tasks: HashMap<TaskId, &Task> = tasks.iter().map(|task| (task.id, task)).collect();
+    let workers: HashMap<u32, &Worker> = workers.iter().map(|worker| (worker.id, worker)).collect();
+
+    let mut assigned = HashSet::new();
+    let mut per_worker: HashMap<u32, Vec<&Task>> = HashMap::new();
+    for assignment in assignments {
+        let task = tasks.get(&assignment.task_id);
+        let worker = workers.get(&assignment.worker_id);
+        prop_assert!(task.is_some(), "unknown task {}", assignment.task_id);
+        prop_assert!(worker.is_some(), "unknown worker {}", assignment.worker_id);
+        let (task, worker) = (task.unwrap(), worker.unwrap());
+
+        prop_assert!(assigned.insert(task.id), "task {} assigned twice", task.id);
+        prop_assert!(worker.can_accept_task(), "worker {} cannot accept tasks", worker.id);
+        prop_assert!(worker.can_handle(task), "worker {} cannot handle task {}", worker.id, task.id);
+        per_worker.entry(worker.id).or_default().push(task);
+    }
+
+    for (worker_id, assigned) in per_worker {
+        let worker = workers[&worker_id];
+        prop_assert!(
+            assigned.len() <= worker.max_tasks,
+            "worker {} has {} tasks, max {}",
+            worker_id,
+            assigned.len(),
+            worker.max_tasks
+        );
+        let used: f64 = assigned.iter().map(|task| task.size).sum();
+        prop_assert!(used <= worker.remaining_capacity() + 1e-9, "worker {} over capacity", worker_id);
+        for (i, a) in assigned.iter().enumerate() {
+            for b in &assigned[i + 1..] {
+                prop_assert!(a.is_compatible_with(b), "tasks {} and {} share worker {}", a.id, b.id, worker_id);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check(planner: &dyn TaskPlanner, tasks: &[Task], workers: &[Worker]) -> Result<(), TestCaseError> {
+    assert_plan_invariants(tasks, workers, &planner.plan(tasks, workers))
+}
+
+proptest! {
+    #[test]
+    fn greedy_planner_holds_invariants(tasks in tasks(), workers in workers()) {
+        check(&GreedyPlanner::new(), &tasks, &workers)?;
+    }
+
+    #[test]
+    fn time_greedy_planner_holds_invariants(tasks in tasks(), workers in workers()) {
+        check(&GreedyPlanner::with_cost_estimator(TimeCostEstimator::default()), &tasks, &workers)?;
+    }
+
+    #[test]
+    fn caching_planner_holds_invariants(tasks in tasks(), workers in workers()) {
+        let planner = CachingPlanner::new(GreedyPlanner::new());
+        check(&planner, &tasks, &workers)?;
+        // A cache hit must hold them too
+        check(&planner, &tasks, &workers)?;
+    }
+
+    #[test]
+    fn dependency_planner_holds_invariants(tasks in tasks(), workers in workers()) {
+        let assignments = DependencyPlanner::new().plan(&tasks, &workers);
+        assert_plan_invariants(&tasks, &workers, &assignments)?;
+        // Prerequisites in the plan come before the tasks that need them
+        let position: HashMap<TaskId, usize> = assignments.iter().enumerate().map(|(i, a)| (a.task_id, i)).collect();
+        for task in tasks.iter().filter(|task| position.contains_key(&task.id)) {
+            for prerequisite in &task.depends_on {
+                prop_assert!(position.get(prerequisite).is_some_and(|&p| p < position[&task.id]));
+            }
+        }
+    }
+
+    #[test]
+    fn splitting_planner_holds_invariants(tasks in tasks(), workers in workers(), splittable in proptest::collection::vec(any::<bool>(), 12)) {
+        let tasks: Vec<Task> = tasks
+            .into_iter()
+            .zip(splittable)
+            .map(|(mut task, splittable)| {
+                task.size *= 4.0;
+                task.splittable = splittable;
+                task
+            })
+            .collect();
+        let assignments = SplittingPlanner::new().plan(&tasks, &workers);
+
+        // Whole assignments must hold the shared invariants
+        let whole: Vec<Assignment> = assignments.iter().filter(|a| a.share.is_none()).cloned().collect();
+        assert_plan_invariants(&tasks, &workers, &whole)?;
+
+        let by_id: HashMap<TaskId, &Task> = tasks.iter().map(|task| (task.id, task)).collect();
+        let mut covered: HashMap<TaskId, f64> = HashMap::new();
+        let mut used: HashMap<u32, (usize, f64)> = HashMap::new();
+        for assignment in &assignments {
+            let task = by_id[&assignment.task_id];
+            let units = assignment.share.unwrap_or(task.size);
+            prop_assert!(assignment.share.is_none() || task.splittable, "task {} split but not splittable", task.id);
+            *covered.entry(task.id).or_default() += units;
+            let entry = used.entry(assignment.worker_id).or_default();
+            entry.0 += 1;
+            entry.1 += units;
+        }
+        // A split task's shares add up to exactly its size
+        for (task_id, units) in covered {
+            prop_assert!((units - by_id[&task_id].size).abs() < 1e-6, "task {} covered {} of {}", task_id, units, by_id[&task_id].size);
+        }
+        for worker in &workers {
+            if let Some(&(count, units)) = used.get(&worker.id) {
+                prop_assert!(count <= worker.max_tasks, "worker {} over max_tasks", worker.id);
+                prop_assert!(units <= worker.remaining_capacity() + 1e-6, "worker {} over capacity", worker.id);
+            }
+        }
+    }
+
+    #[test]
+    fn min_travel_planner_holds_invariants(tasks in tasks(), workers in workers(), limit in 1..6usize) {
+        check(&MinTravelPlanner::new(limit), &tasks, &workers)?;
+    }
+}
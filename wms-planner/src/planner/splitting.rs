@@ -0,0 +1,185 @@
+use crate::planner::traits::{CostEstimator, DistanceCostEstimator, TaskPlanner};
+use crate::types::{Assignment, Task, Worker, WorkerId};
+use crate::validation::is_plannable;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+
+/// Batch planner that shares large splittable tasks among several workers
+///
+/// Tasks are planned highest priority first. A task goes whole to the cheapest
+/// worker with room for it, as in the other batch planners. Only when no
+/// single worker can take a `splittable` task is its `size` divided: the
+/// cheapest workers with spare capacity each take as much as they can, until
+/// the size is covered, and each sub-assignment records its part in
+/// `Assignment.share`. A task that cannot be covered in full is left
+/// unassigned rather than half done.
+///
+/// Workers are limited by their `max_tasks`, with every share counting as a
+/// task, and by their remaining capacity. Each share carries the full
+/// estimated cost of the task for its worker, since each of them travels to it.
+#[derive(Debug)]
+pub struct SplittingPlanner<C = DistanceCostEstimator>
+where
+    C: CostEstimator,
+{
+    cost_estimator: C,
+    now: Option<DateTime<Utc>>, // reference time for skill expiry; `None` uses the current time
+}
+
+impl Default for SplittingPlanner<DistanceCostEstimator> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SplittingPlanner<DistanceCostEstimator> {
+    pub fn new() -> Self {
+        Self::with_cost_estimator(DistanceCostEstimator)
+    }
+}
+
+impl<C> SplittingPlanner<C>
+where
+    C: CostEstimator,
+{
+    pub fn with_cost_estimator(cost_estimator: C) -> Self {
+        Self { cost_estimator, now: None }
+    }
+
+    /// Check skill expiry at `now` instead of the current time, for reproducible plans
+    pub fn with_now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Workers able to take at least part of `task`, cheapest first, with the capacity they already use
+    fn candidates(
+        &self,
+        task: &Task,
+        workers: &[Worker],
+        held: &HashMap<WorkerId, Vec<(&Task, f64)>>,
+        now: DateTime<Utc>,
+    ) -> Vec<(usize, f64, f64)> {
+        let mut candidates: Vec<(usize, f64, f64)> = workers
+            .iter()
+            .enumerate()
+            .filter_map(|(index, worker)| {
+                let current = held.get(&worker.id).map(Vec::as_slice).unwrap_or(&[]);
+                let used: f64 = current.iter().map(|(_, units)| units).sum();
+                let feasible = worker.can_accept_task()
+                    && current.len() < worker.max_tasks
+                    && worker.can_handle_at(task, now)
+                    && current.iter().all(|(other, _)| task.is_compatible_with(other));
+                feasible.then(|| (index, self.cost_estimator.estimate(task, worker), used))
+            })
+            .filter(|&(_, cost, _)| cost.is_finite())
+            .collect();
+        candidates.sort_by(|a, b| a.1.total_cmp(&b.1));
+        candidates
+    }
+}
+
+impl<C> TaskPlanner for SplittingPlanner<C>
+where
+    C: CostEstimator,
+{
+    fn plan(&self, tasks: &[Task], workers: &[Worker]) -> Vec<Assignment> {
+        self.plan_at(tasks, workers, self.now.unwrap_or_else(Utc::now))
+    }
+
+    fn plan_at(&self, tasks: &[Task], workers: &[Worker], now: DateTime<Utc>) -> Vec<Assignment> {
+        if !is_plannable(tasks, workers) {
+            return Vec::new();
+        }
+        let mut ordered: Vec<&Task> = tasks.iter().collect();
+        ordered.sort_by_key(|task| std::cmp::Reverse(task.priority.to_numeric()));
+
+        let mut assignments = Vec::new();
+        let mut assigned = HashSet::new();
+        // Tasks each worker holds, with the capacity units they take
+        let mut held: HashMap<WorkerId, Vec<(&Task, f64)>> = HashMap::new();
+        for task in ordered {
+            if !assigned.insert(task.id) {
+                continue;
+            }
+            let candidates = self.candidates(task, workers, &held, now);
+
+            if let Some(&(index, cost, _)) = candidates.iter().find(|&&(index, _, used)| workers[index].has_room_for(task, used)) {
+                held.entry(workers[index].id).or_default().push((task, task.size));
+                assignments.push(Assignment::new(task.id, workers[index].id, cost));
+                continue;
+            }
+            if !task.splittable {
+                continue;
+            }
+
+            let mut remaining = task.size;
+            let mut shares = Vec::new();
+            for (index, cost, used) in candidates {
+                if remaining <= 0.0 {
+                    break;
+                }
+                let spare = workers[index].remaining_capacity() - used;
+                if spare <= 0.0 {
+                    continue;
+                }
+                let share = spare.min(remaining);
+                remaining -= share;
+                shares.push((workers[index].id, cost, share));
+            }
+            // Capacity comparisons elsewhere allow the same rounding slack
+            if remaining > 1e-9 {
+                continue;
+            }
+            for (worker_id, cost, share) in shares {
+                held.entry(worker_id).or_default().push((task, share));
+                assignments.push(Assignment::new(task.id, worker_id, cost).with_share(share));
+            }
+        }
+        assignments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Location, Priority};
+
+    fn worker(id: WorkerId, x: f64, capacity: f64) -> Worker {
+        Worker::new(id, Location::new(x, 0.0), true).with_capacity(capacity).with_max_tasks(3)
+    }
+
+    #[test]
+    fn test_large_task_split_across_two_workers() {
+        // 100 units to pick, but each worker carries at most 60
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::High).with_size(100.0).with_splittable()];
+        let workers = vec![worker(1, 1.0, 60.0), worker(2, 2.0, 60.0), worker(3, 9.0, 60.0)];
+
+        let assignments = SplittingPlanner::new().plan(&tasks, &workers);
+        let shares: Vec<(WorkerId, Option<f64>)> = assignments.iter().map(|a| (a.worker_id, a.share)).collect();
+        // The two nearest workers share it, the nearer one taking all it can
+        assert_eq!(shares, [(1, Some(60.0)), (2, Some(40.0))]);
+    }
+
+    #[test]
+    fn test_whole_assignment_preferred_and_unsplittable_left_alone() {
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High).with_size(50.0).with_splittable(),
+            Task::new(2, Location::new(0.0, 0.0), Priority::Low).with_size(100.0),
+        ];
+        let workers = vec![worker(1, 1.0, 60.0), worker(2, 2.0, 60.0)];
+
+        let assignments = SplittingPlanner::new().plan(&tasks, &workers);
+        // Task 1 fits worker 1 whole; task 2 fits nobody and may not be split
+        assert_eq!(assignments.len(), 1);
+        assert_eq!((assignments[0].task_id, assignments[0].worker_id, assignments[0].share), (1, 1, None));
+    }
+
+    #[test]
+    fn test_task_left_unassigned_when_capacity_falls_short() {
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::High).with_size(150.0).with_splittable()];
+        let workers = vec![worker(1, 1.0, 60.0), worker(2, 2.0, 60.0)];
+
+        assert!(SplittingPlanner::new().plan(&tasks, &workers).is_empty());
+    }
+}
@@ -1,8 +1,15 @@
+use crate::planner::observer::{AssignmentLog, PlannerObserver};
 use crate::types::{Assignment, Task, Worker};
+use crate::validation::{validate_input, InputError};
+use chrono::{DateTime, Utc};
 
 /// Core trait for task planning algorithms
 pub trait TaskPlanner {
     /// Plan task assignments given a set of tasks and workers
+    ///
+    /// The planners in this crate assign nothing when the input has a problem
+    /// [`validate_input`] rejects, other than a dependency cycle; use
+    /// [`try_plan`](Self::try_plan) to find out what is wrong.
     /// 
     /// # Arguments
     /// * `tasks` - List of tasks to be assigned
@@ -11,6 +18,79 @@ pub trait TaskPlanner {
     /// # Returns
     /// Vector of assignments mapping tasks to workers
     fn plan(&self, tasks: &[Task], workers: &[Worker]) -> Vec<Assignment>;
+
+    /// Plan as of `now`, checking skill expiry against it instead of the current time
+    ///
+    /// Planners whose decisions depend on the time override this; the default
+    /// ignores `now` and calls [`plan`](Self::plan).
+    fn plan_at(&self, tasks: &[Task], workers: &[Worker], _now: DateTime<Utc>) -> Vec<Assignment> {
+        self.plan(tasks, workers)
+    }
+
+    /// Validate the input with [`validate_input`], then plan
+    ///
+    /// Prefer this over `plan` for input from outside the program, where
+    /// duplicate IDs or NaN coordinates would otherwise just leave every task
+    /// unassigned.
+    fn try_plan(&self, tasks: &[Task], workers: &[Worker]) -> Result<Vec<Assignment>, InputError> {
+        validate_input(tasks, workers)?;
+        Ok(self.plan(tasks, workers))
+    }
+
+    /// Plan with each worker placed where it will be at `now`
+    ///
+    /// Workers en route (with a velocity) are moved along their heading to
+    /// [`Worker::projected_location_at`] before planning; stationary workers
+    /// are unaffected. The plan is then made with [`plan_at`](Self::plan_at).
+    fn plan_projected(&self, tasks: &[Task], workers: &[Worker], now: DateTime<Utc>) -> Vec<Assignment> {
+        let projected: Vec<Worker> = workers
+            .iter()
+            .map(|worker| Worker {
+                location: worker.projected_location_at(now),
+                ..worker.clone()
+            })
+            .collect();
+        self.plan_at(tasks, &projected, now)
+    }
+
+    /// Plan task assignments, reporting decisions to `observer` along the way
+    ///
+    /// The default implementation only reports the final assignments; planners
+    /// override it to also report rejected candidates.
+    fn plan_with_observer(
+        &self,
+        tasks: &[Task],
+        workers: &[Worker],
+        observer: &mut dyn PlannerObserver,
+    ) -> Vec<Assignment> {
+        let assignments = self.plan(tasks, workers);
+        for assignment in &assignments {
+            observer.on_assignment(assignment);
+        }
+        assignments
+    }
+
+    /// Plan task assignments and return them numbered in decision order
+    fn plan_with_log(&self, tasks: &[Task], workers: &[Worker]) -> Vec<(usize, Assignment)> {
+        let mut log = AssignmentLog::new();
+        self.plan_with_observer(tasks, workers, &mut log);
+        log.into_steps()
+    }
+
+    /// Plan tasks in pick waves of at most `wave_size` tasks
+    ///
+    /// Tasks are ordered by priority (highest first), keeping arrival order
+    /// within a priority, and split into consecutive waves. Each wave is planned
+    /// independently against the same workers, so per-plan worker capacity
+    /// resets between waves. A `wave_size` of 0 is treated as 1.
+    fn plan_waves(&self, tasks: &[Task], workers: &[Worker], wave_size: usize) -> Vec<Vec<Assignment>> {
+        let mut ordered = tasks.to_vec();
+        ordered.sort_by_key(|task| std::cmp::Reverse(task.priority.to_numeric()));
+        ordered
+            .chunks(wave_size.max(1))
+            .map(|wave| self.plan(wave, workers))
+            .collect()
+    }
 }
 
 /// Trait for estimating the cost of assigning a task to a worker
@@ -45,14 +125,28 @@ pub trait BatchPlanner {
     ) -> Vec<Assignment>;
 }
 
+/// Cost multiplier applied when a task is assigned to its preferred worker
+///
+/// Small enough that the preference only decides ties and close calls.
+pub const PREFERRED_WORKER_DISCOUNT: f64 = 0.9;
+
+/// Discount multiplier for `worker` taking `task`, 1.0 unless it is the preferred worker
+fn preference_multiplier(task: &Task, worker: &Worker) -> f64 {
+    if task.preferred_worker == Some(worker.id) {
+        PREFERRED_WORKER_DISCOUNT
+    } else {
+        1.0
+    }
+}
+
 /// Basic distance-based cost estimator
 #[derive(Debug, Default)]
 pub struct DistanceCostEstimator;
 
 impl CostEstimator for DistanceCostEstimator {
     fn estimate(&self, task: &Task, worker: &Worker) -> f64 {
-        // Simple Euclidean distance as base cost
-        let distance = worker.location.distance_to(&task.location);
+        // Euclidean distance to the pick location, plus the dropoff leg if any
+        let distance = worker.location.distance_to(&task.location) + task.dropoff_distance();
         
         // Factor in worker load (higher load = higher cost)
         let load_penalty = worker.current_load * 10.0;
@@ -65,35 +159,93 @@ impl CostEstimator for DistanceCostEstimator {
             crate::types::Priority::Low => 1.5,
         };
         
-        (distance + load_penalty) * priority_multiplier
+        (distance + load_penalty) * priority_multiplier * preference_multiplier(task, worker)
     }
 }
 
 /// Time-based cost estimator that includes travel time and task duration
+///
+/// A worker would start a task once it has finished its current one and
+/// travelled there. If the task has a `service_window` and that start falls
+/// outside it, too early or too late, the cost is infinite and planners leave
+/// the pairing out.
 #[derive(Debug)]
 pub struct TimeCostEstimator {
-    pub travel_speed: f64, // units per minute
+    pub travel_speed: f64, // units per minute, for workers without their own `travel_speed`
+    pub now: Option<DateTime<Utc>>, // reference time for worker wait; `None` uses the current time
+    pub default_duration: f64, // minutes assumed for tasks without an `estimated_duration`
 }
 
 impl Default for TimeCostEstimator {
     fn default() -> Self {
         Self {
             travel_speed: 1.0, // 1 unit per minute
+            now: None,
+            default_duration: 30.0,
         }
     }
 }
 
+impl TimeCostEstimator {
+    /// Travel at `travel_speed` units per minute, for workers without their own `travel_speed`
+    ///
+    /// Panics unless `travel_speed` is positive and finite.
+    pub fn with_travel_speed(mut self, travel_speed: f64) -> Self {
+        assert!(
+            travel_speed.is_finite() && travel_speed > 0.0,
+            "travel speed must be positive and finite, got {travel_speed}"
+        );
+        self.travel_speed = travel_speed;
+        self
+    }
+
+    /// Assume `minutes` for tasks that have no `estimated_duration`
+    pub fn with_default_duration(mut self, minutes: f64) -> Self {
+        self.default_duration = minutes;
+        self
+    }
+
+    /// Evaluate worker wait times against a fixed reference time
+    pub fn with_now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+}
+
 impl CostEstimator for TimeCostEstimator {
     fn estimate(&self, task: &Task, worker: &Worker) -> f64 {
-        // Travel time based on distance and speed
-        let distance = worker.location.distance_to(&task.location);
-        let travel_time = distance / self.travel_speed;
+        // Travel time based on distance (including any dropoff leg) and speed
+        let distance = worker.location.distance_to(&task.location) + task.dropoff_distance();
+        let travel_time = distance / worker.travel_speed.unwrap_or(self.travel_speed);
         
-        // Task execution time
-        let execution_time = task.estimated_duration.unwrap_or(30.0); // default 30 minutes
+        // Task execution time, faster for workers more proficient in the required skill
+        let proficiency_factor = worker
+            .proficiency_for(task)
+            .map(|proficiency| proficiency.execution_time_factor())
+            .unwrap_or(1.0);
+        let execution_time = task.estimated_duration.unwrap_or(self.default_duration) * proficiency_factor;
+
+        // Time until the worker finishes its current task
+        let now = self.now.unwrap_or_else(Utc::now);
+        let wait_time = worker.wait_minutes(now);
+
+        // A worker that never gets there, e.g. at zero travel speed, can't take the task
+        let until_start = wait_time + travel_time;
+        if !until_start.is_finite() {
+            return f64::INFINITY;
+        }
+        if let Some((opens, closes)) = task.service_window {
+            // Starts too far off to represent fall outside any window
+            let start = chrono::Duration::try_milliseconds((until_start * 60_000.0) as i64)
+                .and_then(|offset| now.checked_add_signed(offset));
+            match start {
+                Some(start) if start >= opens && start <= closes => {}
+                _ => return f64::INFINITY,
+            }
+        }
         
         // Total time cost
-        let total_time = travel_time + execution_time;
+        let total_time = wait_time + travel_time + execution_time;
         
         // Factor in worker load and task priority similar to distance estimator
         let load_penalty = worker.current_load * total_time * 0.5;
@@ -104,14 +256,89 @@ impl CostEstimator for TimeCostEstimator {
             crate::types::Priority::Low => 1.5,
         };
         
-        (total_time + load_penalty) * priority_multiplier
+        (total_time + load_penalty) * priority_multiplier * preference_multiplier(task, worker)
+    }
+}
+
+/// Great-circle cost estimator for geo-coordinates
+///
+/// Interprets `Location.x` as longitude and `Location.y` as latitude (degrees)
+/// and uses the haversine distance in meters as the base cost, for routing
+/// tasks across facilities.
+#[derive(Debug)]
+pub struct HaversineCostEstimator {
+    pub load_penalty: f64, // meters added at full worker load
+}
+
+impl Default for HaversineCostEstimator {
+    fn default() -> Self {
+        Self {
+            load_penalty: 1000.0, // full load costs as much as an extra kilometer
+        }
+    }
+}
+
+impl CostEstimator for HaversineCostEstimator {
+    fn estimate(&self, task: &Task, worker: &Worker) -> f64 {
+        let distance = worker.location.haversine_distance_to(&task.location) + task.dropoff_haversine_distance();
+        let load_penalty = worker.current_load * self.load_penalty;
+        let priority_multiplier = match task.priority {
+            crate::types::Priority::Critical => 0.5,
+            crate::types::Priority::High => 0.7,
+            crate::types::Priority::Medium => 1.0,
+            crate::types::Priority::Low => 1.5,
+        };
+
+        (distance + load_penalty) * priority_multiplier * preference_multiplier(task, worker)
+    }
+}
+
+/// Cost estimator for workers stationed at fixed conveyor positions
+///
+/// Tasks flow to the stations, so travel hardly matters; the cost is the depth
+/// of the station's queue, taken from `Worker.current_load`. Distance only
+/// counts if `distance_weight` is set, e.g. to break ties between empty stations.
+#[derive(Debug)]
+pub struct StationCostEstimator {
+    pub full_queue_cost: f64, // cost of a station whose queue is full (load 1.0)
+    pub distance_weight: f64, // cost per unit of distance between station and task
+}
+
+impl Default for StationCostEstimator {
+    fn default() -> Self {
+        Self {
+            full_queue_cost: 100.0,
+            distance_weight: 0.0, // geometry ignored
+        }
+    }
+}
+
+impl StationCostEstimator {
+    pub fn with_distance_weight(mut self, distance_weight: f64) -> Self {
+        self.distance_weight = distance_weight;
+        self
+    }
+}
+
+impl CostEstimator for StationCostEstimator {
+    fn estimate(&self, task: &Task, worker: &Worker) -> f64 {
+        let queue_cost = worker.current_load * self.full_queue_cost;
+        let distance_cost = worker.location.distance_to(&task.location) * self.distance_weight;
+        let priority_multiplier = match task.priority {
+            crate::types::Priority::Critical => 0.5,
+            crate::types::Priority::High => 0.7,
+            crate::types::Priority::Medium => 1.0,
+            crate::types::Priority::Low => 1.5,
+        };
+
+        (queue_cost + distance_cost) * priority_multiplier * preference_multiplier(task, worker)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{Location, Priority};
+    use crate::types::{Location, Priority, Proficiency};
 
     #[test]
     fn test_distance_cost_estimator() {
@@ -137,4 +364,204 @@ mod tests {
         // Total: 25.0 * priority multiplier (1.0) = 25.0
         assert!((cost - 25.0).abs() < 0.01);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_time_cost_estimator_prefers_expert() {
+        let estimator = TimeCostEstimator::default();
+        let task = Task::new(1, Location::new(0.0, 0.0), Priority::Medium)
+            .with_duration(20.0)
+            .with_required_skill("picking");
+
+        // Equal distance (5.0) from the task
+        let novice = Worker::new(1, Location::new(3.0, 4.0), true).with_skill("picking", Proficiency::Novice);
+        let expert = Worker::new(2, Location::new(-3.0, -4.0), true).with_skill("picking", Proficiency::Expert);
+
+        // 5 travel + 20 * 1.5 = 35 vs 5 travel + 20 * 0.7 = 19
+        assert!((estimator.estimate(&task, &novice) - 35.0).abs() < 0.01);
+        assert!((estimator.estimate(&task, &expert) - 19.0).abs() < 0.01);
+
+        let planner = crate::planner::greedy::GreedyPlanner::with_cost_estimator(estimator);
+        let assignments = planner.plan(&[task], &[novice, expert]);
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].worker_id, 2);
+    }
+
+    #[test]
+    fn test_dropoff_leg_adds_cost() {
+        let worker = Worker::new(1, Location::new(3.0, 4.0), true);
+        let pick_only = Task::new(1, Location::new(0.0, 0.0), Priority::Medium).with_duration(20.0);
+        let pick_and_drop = pick_only.clone().with_dropoff(Location::new(0.0, 10.0));
+
+        // Worker→pick is 5.0; pick→dropoff adds 10.0
+        assert!((DistanceCostEstimator.estimate(&pick_only, &worker) - 5.0).abs() < 0.01);
+        assert!((DistanceCostEstimator.estimate(&pick_and_drop, &worker) - 15.0).abs() < 0.01);
+
+        let estimator = TimeCostEstimator::default();
+        assert!((estimator.estimate(&pick_only, &worker) - 25.0).abs() < 0.01);
+        assert!((estimator.estimate(&pick_and_drop, &worker) - 35.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_preferred_worker_breaks_tie() {
+        // Both workers are 5.0 away, so the first one would win the tie
+        let first = Worker::new(1, Location::new(3.0, 4.0), true);
+        let second = Worker::new(2, Location::new(-3.0, -4.0), true);
+        let task = Task::new(1, Location::new(0.0, 0.0), Priority::Medium);
+
+        let planner = crate::planner::greedy::GreedyPlanner::new();
+        let workers = [first, second];
+        let assignments = planner.plan(std::slice::from_ref(&task), &workers);
+        assert_eq!(assignments[0].worker_id, 1);
+
+        let preferring = [task.with_preferred_worker(2)];
+        assert!((DistanceCostEstimator.estimate(&preferring[0], &workers[1]) - 4.5).abs() < 0.01);
+        let assignments = planner.plan(&preferring, &workers);
+        assert_eq!(assignments[0].worker_id, 2);
+
+        // A clearly farther preferred worker does not win
+        let near = Worker::new(1, Location::new(1.0, 0.0), true);
+        let far = Worker::new(2, Location::new(10.0, 0.0), true);
+        let assignments = planner.plan(&preferring, &[near, far]);
+        assert_eq!(assignments[0].worker_id, 1);
+    }
+
+    #[test]
+    fn test_haversine_cost_estimator() {
+        let estimator = HaversineCostEstimator::default();
+        // Task in London, worker in Paris
+        let task = Task::new(1, Location::new(-0.1278, 51.5074), Priority::Medium);
+        let worker = Worker::new(1, Location::new(2.3522, 48.8566), true);
+
+        let cost = estimator.estimate(&task, &worker);
+        // Great-circle distance of ~343.5 km at priority multiplier 1.0
+        assert!((cost - 343_556.0).abs() < 1_000.0);
+
+        // Euclidean distance on raw degrees would be meaningless here
+        assert!(DistanceCostEstimator.estimate(&task, &worker) < 10.0);
+    }
+
+    #[test]
+    fn test_time_cost_estimator_uses_worker_travel_speed() {
+        let now = DateTime::parse_from_rfc3339("2024-01-15T10:00:00Z").unwrap().with_timezone(&Utc);
+        let estimator = TimeCostEstimator::default().with_now(now);
+        let task = Task::new(1, Location::new(0.0, 0.0), Priority::Medium).with_duration(20.0);
+
+        // 5 units away at the default 1 unit per minute, vs 15 away at 5 per minute
+        let walker = Worker::new(1, Location::new(3.0, 4.0), true);
+        let driver = Worker::new(2, Location::new(9.0, 12.0), true).with_travel_speed(5.0);
+        assert!((estimator.estimate(&task, &walker) - 25.0).abs() < 0.01);
+        assert!((estimator.estimate(&task, &driver) - 23.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_time_cost_estimator_custom_default_duration() {
+        let now = DateTime::parse_from_rfc3339("2024-01-15T10:00:00Z").unwrap().with_timezone(&Utc);
+        let task = Task::new(1, Location::new(0.0, 0.0), Priority::Medium);
+        let worker = Worker::new(1, Location::new(3.0, 4.0), true);
+
+        // 5 travel + the 30 minute default
+        let standard = TimeCostEstimator::default().with_now(now);
+        assert!((standard.estimate(&task, &worker) - 35.0).abs() < 0.01);
+
+        // 5 travel + 10 minutes assumed for a quick pick
+        let quick = TimeCostEstimator::default().with_now(now).with_default_duration(10.0);
+        assert!((quick.estimate(&task, &worker) - 15.0).abs() < 0.01);
+        // A known duration still wins
+        assert!((quick.estimate(&task.with_duration(20.0), &worker) - 25.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_time_cost_estimator_busy_worker_wait() {
+        let now = DateTime::parse_from_rfc3339("2024-01-15T10:00:00Z").unwrap().with_timezone(&Utc);
+        let estimator = TimeCostEstimator::default().with_now(now);
+        let task = Task::new(1, Location::new(0.0, 0.0), Priority::Medium)
+            .with_duration(20.0);
+
+        // Nearer worker (5 units away) busy for another 20 minutes
+        let near_busy = Worker::new(1, Location::new(3.0, 4.0), true)
+            .with_busy_until(now + chrono::Duration::minutes(20));
+        // Farther worker (15 units away) free right now
+        let far_free = Worker::new(2, Location::new(9.0, 12.0), true);
+
+        let near_cost = estimator.estimate(&task, &near_busy);
+        let far_cost = estimator.estimate(&task, &far_free);
+        // 20 wait + 5 travel + 20 execution = 45 vs 15 travel + 20 execution = 35
+        assert!((near_cost - 45.0).abs() < 0.01);
+        assert!((far_cost - 35.0).abs() < 0.01);
+
+        let planner = crate::planner::greedy::GreedyPlanner::with_cost_estimator(estimator);
+        let assignments = planner.plan(&[task], &[near_busy, far_free]);
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].worker_id, 2);
+    }
+
+    #[test]
+    fn test_service_window_rules_out_early_and_late_arrivals() {
+        let now = DateTime::parse_from_rfc3339("2024-01-15T14:00:00Z").unwrap().with_timezone(&Utc);
+        let estimator = TimeCostEstimator::default().with_now(now);
+        // Dock door free from 14:10 to 14:20
+        let task = Task::new(1, Location::new(0.0, 0.0), Priority::Medium)
+            .with_duration(5.0)
+            .with_service_window(now + chrono::Duration::minutes(10), now + chrono::Duration::minutes(20));
+
+        let early = Worker::new(1, Location::new(3.0, 4.0), true);
+        let on_time = Worker::new(2, Location::new(9.0, 12.0), true);
+        let late = Worker::new(3, Location::new(15.0, 20.0), true);
+        // Arriving after 5, 15 and 25 minutes
+        assert_eq!(estimator.estimate(&task, &early), f64::INFINITY);
+        assert!((estimator.estimate(&task, &on_time) - 20.0).abs() < 0.01);
+        assert_eq!(estimator.estimate(&task, &late), f64::INFINITY);
+
+        // A wait for the current task counts towards the start
+        let busy = early.clone().with_busy_until(now + chrono::Duration::minutes(7));
+        assert!(estimator.estimate(&task, &busy).is_finite());
+
+        let planner = crate::planner::greedy::GreedyPlanner::with_cost_estimator(estimator);
+        assert!(planner.plan(std::slice::from_ref(&task), &[early.clone(), late.clone()]).is_empty());
+        let assignments = planner.plan(&[task], &[early, on_time, late]);
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].worker_id, 2);
+        assert_eq!(assignments[0].margin, None);
+    }
+
+    #[test]
+    fn test_service_window_with_unreachable_start_is_infeasible() {
+        let now = DateTime::parse_from_rfc3339("2024-01-15T14:00:00Z").unwrap().with_timezone(&Utc);
+        let estimator = TimeCostEstimator::default().with_now(now);
+        let window = (now, now + chrono::Duration::minutes(20));
+        let task = Task::new(1, Location::new(0.0, 0.0), Priority::Medium).with_service_window(window.0, window.1);
+        let distant = Task::new(2, Location::new(1e300, 0.0), Priority::Medium).with_service_window(window.0, window.1);
+
+        let stalled = Worker::new(1, Location::new(3.0, 4.0), true).with_travel_speed(0.0);
+        let nearby = Worker::new(2, Location::new(3.0, 4.0), true);
+        assert_eq!(estimator.estimate(&task, &stalled), f64::INFINITY);
+        assert_eq!(estimator.estimate(&distant, &nearby), f64::INFINITY);
+
+        let planner = crate::planner::greedy::GreedyPlanner::with_cost_estimator(estimator);
+        assert!(planner.plan(std::slice::from_ref(&task), &[stalled]).is_empty());
+        assert!(planner.plan(&[distant], std::slice::from_ref(&nearby)).is_empty());
+        assert_eq!(planner.plan(&[task], &[nearby]).len(), 1);
+    }
+
+    #[test]
+    fn test_station_cost_prefers_shortest_queue() {
+        use crate::planner::greedy::GreedyPlanner;
+
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::Medium)];
+        let workers = vec![
+            // Station right next to the task, but with a long queue
+            Worker::new(1, Location::new(1.0, 0.0), true).with_load(0.8),
+            // Station at the far end of the conveyor with a short queue
+            Worker::new(2, Location::new(500.0, 500.0), true).with_load(0.2),
+        ];
+
+        let assignments = GreedyPlanner::with_cost_estimator(StationCostEstimator::default()).plan(&tasks, &workers);
+        assert_eq!(assignments[0].worker_id, 2);
+        assert!((assignments[0].estimated_cost - 20.0).abs() < 1e-9);
+
+        // Distance can still break a tie between equally loaded stations
+        let estimator = StationCostEstimator::default().with_distance_weight(0.01);
+        let idle = [workers[0].clone().with_load(0.0), workers[1].clone().with_load(0.0)];
+        assert!(estimator.estimate(&tasks[0], &idle[0]) < estimator.estimate(&tasks[0], &idle[1]));
+    }
+}
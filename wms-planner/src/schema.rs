@@ -0,0 +1,63 @@
+//! JSON Schema for the planner's wire format
+
+use schemars::generate::SchemaSettings;
+use serde_json::{json, Value};
+
+use crate::types::{Assignment, Task, Worker};
+
+/// JSON Schema (draft 2020-12) for `Task`, `Worker` and `Assignment` as serialized
+///
+/// Each type is a definition under `$defs`, next to the types it contains such
+/// as `Location` and `Priority`; refer to one with e.g. `#/$defs/Task`. Fields
+/// with serde defaults are optional in the schema.
+pub fn schema() -> Value {
+    let mut generator = SchemaSettings::draft2020_12().into_generator();
+    generator.subschema_for::<Task>();
+    generator.subschema_for::<Worker>();
+    generator.subschema_for::<Assignment>();
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "wms-planner wire format",
+        "$defs": generator.take_definitions(true),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn property_names(schema: &Value, definition: &str) -> Vec<String> {
+        let mut names: Vec<String> = schema["$defs"][definition]["properties"]
+            .as_object()
+            .unwrap_or_else(|| panic!("{} has no properties", definition))
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_schema_describes_wire_types() {
+        let schema = schema();
+
+        let task = property_names(&schema, "Task");
+        for field in ["id", "location", "priority", "estimated_duration", "required_skill", "depends_on"] {
+            assert!(task.contains(&field.to_string()), "Task lacks {}", field);
+        }
+        let worker = property_names(&schema, "Worker");
+        for field in ["id", "location", "is_available", "current_load", "max_tasks", "skills"] {
+            assert!(worker.contains(&field.to_string()), "Worker lacks {}", field);
+        }
+        assert_eq!(
+            property_names(&schema, "Assignment"),
+            ["estimated_cost", "explanation", "margin", "share", "task_id", "worker_id"]
+        );
+        assert_eq!(property_names(&schema, "Location"), ["x", "y"]);
+
+        // Only fields without a serde default must be present
+        let required = schema["$defs"]["Assignment"]["required"].as_array().unwrap();
+        assert_eq!(required.len(), 3, "{:?}", required);
+        assert_eq!(schema["$defs"]["Task"]["properties"]["location"]["$ref"], "#/$defs/Location");
+    }
+}
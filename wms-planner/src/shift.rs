@@ -0,0 +1,154 @@
+use crate::planner::traits::TaskPlanner;
+use crate::types::{Assignment, Task, Worker};
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+
+/// Clear the load carried by each worker, e.g. at the start of a shift
+pub fn reset_worker_loads(workers: &mut [Worker]) {
+    for worker in workers {
+        worker.current_load = 0.0;
+    }
+}
+
+/// Daily shift start times, in UTC
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShiftSchedule {
+    starts: Vec<NaiveTime>,
+}
+
+impl ShiftSchedule {
+    /// Create a schedule from the times shifts begin each day
+    ///
+    /// An empty schedule is treated as a single shift starting at midnight.
+    pub fn new(starts: impl IntoIterator<Item = NaiveTime>) -> Self {
+        let mut starts: Vec<_> = starts.into_iter().collect();
+        if starts.is_empty() {
+            starts.push(NaiveTime::MIN);
+        }
+        starts.sort();
+        starts.dedup();
+        Self { starts }
+    }
+
+    /// Start of the shift in progress at `at`
+    pub fn shift_start(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let date = at.date_naive();
+        let time = at.time();
+        match self.starts.iter().rev().find(|start| **start <= time) {
+            Some(start) => date.and_time(*start).and_utc(),
+            // Before the first start today: still in yesterday's last shift
+            None => (date - Duration::days(1))
+                .and_time(*self.starts.last().expect("schedule is never empty"))
+                .and_utc(),
+        }
+    }
+}
+
+/// Planner wrapper that zeroes worker loads whenever a new shift begins
+///
+/// The first run only records the current shift; loads passed in then are
+/// assumed to belong to it.
+#[derive(Debug)]
+pub struct ShiftPlanner<P> {
+    planner: P,
+    schedule: ShiftSchedule,
+    current_shift: Option<DateTime<Utc>>,
+}
+
+impl<P: TaskPlanner> ShiftPlanner<P> {
+    pub fn new(planner: P, schedule: ShiftSchedule) -> Self {
+        Self {
+            planner,
+            schedule,
+            current_shift: None,
+        }
+    }
+
+    /// Plan at time `now`, first resetting loads if a shift boundary was crossed
+    /// since the previous run
+    ///
+    /// The wrapped planner also plans as of `now`, see [`TaskPlanner::plan_at`].
+    pub fn plan_at(&mut self, tasks: &[Task], workers: &mut [Worker], now: DateTime<Utc>) -> Vec<Assignment> {
+        let shift = self.schedule.shift_start(now);
+        if self.current_shift.is_some_and(|current| current != shift) {
+            reset_worker_loads(workers);
+        }
+        self.current_shift = Some(shift);
+        self.planner.plan_at(tasks, workers, now)
+    }
+
+    /// Plan at the current time
+    pub fn plan(&mut self, tasks: &[Task], workers: &mut [Worker]) -> Vec<Assignment> {
+        self.plan_at(tasks, workers, Utc::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planner::greedy::GreedyPlanner;
+    use crate::types::{Location, Priority, Proficiency};
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time).unwrap().with_timezone(&Utc)
+    }
+
+    fn schedule() -> ShiftSchedule {
+        ShiftSchedule::new([
+            NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+        ])
+    }
+
+    #[test]
+    fn test_shift_start() {
+        let schedule = schedule();
+        assert_eq!(schedule.shift_start(at("2024-01-15T13:59:00Z")), at("2024-01-15T06:00:00Z"));
+        assert_eq!(schedule.shift_start(at("2024-01-15T14:00:00Z")), at("2024-01-15T14:00:00Z"));
+        // The night shift runs past midnight
+        assert_eq!(schedule.shift_start(at("2024-01-16T03:00:00Z")), at("2024-01-15T22:00:00Z"));
+    }
+
+    #[test]
+    fn test_loads_reset_at_shift_boundary() {
+        let mut planner = ShiftPlanner::new(GreedyPlanner::new(), schedule());
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::Medium)];
+        let mut workers = vec![
+            Worker::new(1, Location::new(1.0, 0.0), true).with_load(0.9),
+            Worker::new(2, Location::new(2.0, 0.0), true).with_load(0.2),
+        ];
+
+        planner.plan_at(&tasks, &mut workers, at("2024-01-15T12:00:00Z"));
+        planner.plan_at(&tasks, &mut workers, at("2024-01-15T13:59:00Z"));
+        assert_eq!(workers[0].current_load, 0.9);
+
+        // First run of the afternoon shift starts everyone fresh
+        let assignments = planner.plan_at(&tasks, &mut workers, at("2024-01-15T14:05:00Z"));
+        assert!(workers.iter().all(|w| w.current_load == 0.0));
+        assert_eq!(assignments[0].worker_id, 1);
+
+        // Load recorded during the shift is kept until the next boundary
+        workers[0].current_load = 0.5;
+        planner.plan_at(&tasks, &mut workers, at("2024-01-15T15:00:00Z"));
+        assert_eq!(workers[0].current_load, 0.5);
+    }
+
+    #[test]
+    fn test_wrapped_planner_plans_at_given_time() {
+        let mut planner = ShiftPlanner::new(GreedyPlanner::new(), schedule());
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::Medium).with_required_skill("forklift")];
+        let mut workers = vec![Worker::new(1, Location::new(1.0, 0.0), true)
+            .with_skill_expiring("forklift", Proficiency::Expert, at("2024-01-15T14:00:00Z"))];
+
+        assert_eq!(planner.plan_at(&tasks, &mut workers, at("2024-01-15T12:00:00Z")).len(), 1);
+        assert!(planner.plan_at(&tasks, &mut workers, at("2024-01-15T15:00:00Z")).is_empty());
+    }
+
+    #[test]
+    fn test_reset_worker_loads() {
+        let mut workers = vec![Worker::new(1, Location::new(0.0, 0.0), true).with_load(1.0)];
+        assert!(!workers[0].can_accept_task());
+        reset_worker_loads(&mut workers);
+        assert!(workers[0].can_accept_task());
+    }
+}
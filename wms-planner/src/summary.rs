@@ -0,0 +1,106 @@
+use crate::types::{Assignment, Task, TaskId, WorkerId};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Per-worker totals within a plan
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkerSummary {
+    pub assignment_count: usize,
+    pub total_cost: f64,
+}
+
+/// Aggregate cost figures for a plan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanSummary {
+    pub assignment_count: usize,
+    pub unassigned_tasks: Vec<TaskId>,
+    pub total_cost: f64,
+    pub per_worker: BTreeMap<WorkerId, WorkerSummary>,
+}
+
+impl PlanSummary {
+    /// Summarize `assignments` made for `tasks`
+    pub fn new(tasks: &[Task], assignments: &[Assignment]) -> Self {
+        let mut per_worker: BTreeMap<WorkerId, WorkerSummary> = BTreeMap::new();
+        for assignment in assignments {
+            let worker = per_worker.entry(assignment.worker_id).or_default();
+            worker.assignment_count += 1;
+            worker.total_cost += assignment.estimated_cost;
+        }
+
+        let assigned: HashSet<TaskId> = assignments.iter().map(|a| a.task_id).collect();
+        let unassigned_tasks = tasks
+            .iter()
+            .map(|task| task.id)
+            .filter(|id| !assigned.contains(id))
+            .collect();
+
+        Self {
+            assignment_count: assignments.len(),
+            unassigned_tasks,
+            total_cost: assignments.iter().map(|a| a.estimated_cost).sum(),
+            per_worker,
+        }
+    }
+
+    pub fn unassigned_count(&self) -> usize {
+        self.unassigned_tasks.len()
+    }
+}
+
+/// Gini coefficient of `values`, e.g. per-worker workloads
+///
+/// 0.0 means perfectly even, values towards 1.0 mean a few take nearly
+/// everything. Empty input or an all-zero total gives 0.0.
+pub fn gini_coefficient(values: &[f64]) -> f64 {
+    let total: f64 = values.iter().sum();
+    if values.is_empty() || total <= 0.0 {
+        return 0.0;
+    }
+    let differences: f64 = values
+        .iter()
+        .flat_map(|a| values.iter().map(move |b| (a - b).abs()))
+        .sum();
+    differences / (2.0 * values.len() as f64 * total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Location, Priority};
+
+    #[test]
+    fn test_plan_summary_totals() {
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::High),
+            Task::new(2, Location::new(1.0, 0.0), Priority::High),
+            Task::new(3, Location::new(2.0, 0.0), Priority::High),
+        ];
+        let assignments = vec![
+            Assignment::new(1, 10, 2.0),
+            Assignment::new(2, 10, 3.0),
+            Assignment::new(3, 20, 1.5),
+        ];
+
+        let summary = PlanSummary::new(&tasks[..], &assignments);
+        assert_eq!(summary.assignment_count, 3);
+        assert_eq!(summary.unassigned_count(), 0);
+        assert!((summary.total_cost - 6.5).abs() < 1e-9);
+        assert_eq!(summary.per_worker[&10].assignment_count, 2);
+        assert!((summary.per_worker[&10].total_cost - 5.0).abs() < 1e-9);
+        assert_eq!(summary.per_worker[&20].assignment_count, 1);
+
+        let partial = PlanSummary::new(&tasks, &assignments[..1]);
+        assert_eq!(partial.unassigned_tasks, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_gini_coefficient() {
+        assert_eq!(gini_coefficient(&[]), 0.0);
+        assert_eq!(gini_coefficient(&[0.0, 0.0]), 0.0);
+        assert!(gini_coefficient(&[4.0, 4.0, 4.0]).abs() < 1e-9);
+        // One of four workers does everything
+        assert!((gini_coefficient(&[0.0, 0.0, 0.0, 8.0]) - 0.75).abs() < 1e-9);
+        assert!((gini_coefficient(&[1.0, 3.0]) - 0.25).abs() < 1e-9);
+    }
+}
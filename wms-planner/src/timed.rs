@@ -0,0 +1,276 @@
+use crate::planner::greedy::GreedyBatchPlanner;
+use crate::planner::traits::{CostEstimator, DistanceCostEstimator};
+use crate::types::{Assignment, Location, Task, TaskId, Worker, WorkerId};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+/// Assignments together with when each assigned worker is expected to finish
+///
+/// A worker that would never finish, see [`worker_finish_times`], has
+/// assignments but no finish time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedPlan {
+    pub assignments: Vec<Assignment>,
+    pub worker_finish_times: HashMap<WorkerId, DateTime<Utc>>,
+}
+
+/// Expected finish time of every worker with at least one assignment
+///
+/// Each worker starts at `shift_start`, or when its current task completes if
+/// later, then works through its tasks in assignment order: travel to the pick
+/// location at the worker's own `travel_speed`, or `travel_speed` units per
+/// minute if it has none, perform the task (scaled by proficiency,
+/// `default_duration` minutes if no duration is set) and carry it to the
+/// dropoff, if any, which becomes the starting point for the next task.
+///
+/// A worker whose finish time can't be represented, e.g. one crawling along
+/// at a tiny travel speed, is left out rather than given a made-up time.
+pub fn worker_finish_times(
+    tasks: &[Task],
+    workers: &[Worker],
+    assignments: &[Assignment],
+    shift_start: DateTime<Utc>,
+    travel_speed: f64,
+    default_duration: f64,
+) -> HashMap<WorkerId, DateTime<Utc>> {
+    worker_minutes(tasks, workers, assignments, shift_start, travel_speed, default_duration)
+        .into_iter()
+        .filter(|(_, minutes)| minutes.is_finite())
+        .filter_map(|(worker_id, minutes)| {
+            let finish = Duration::try_milliseconds((minutes * 60_000.0).round() as i64)
+                .and_then(|offset| shift_start.checked_add_signed(offset))?;
+            Some((worker_id, finish))
+        })
+        .collect()
+}
+
+/// Minutes from `shift_start` until each assigned worker finishes, as chained by [`worker_finish_times`]
+fn worker_minutes(
+    tasks: &[Task],
+    workers: &[Worker],
+    assignments: &[Assignment],
+    shift_start: DateTime<Utc>,
+    travel_speed: f64,
+    default_duration: f64,
+) -> HashMap<WorkerId, f64> {
+    let tasks: HashMap<TaskId, &Task> = tasks.iter().map(|task| (task.id, task)).collect();
+    let workers: HashMap<WorkerId, &Worker> = workers.iter().map(|worker| (worker.id, worker)).collect();
+
+    let mut progress: HashMap<WorkerId, (f64, Location)> = HashMap::new();
+    for assignment in assignments {
+        let (Some(task), Some(worker)) = (tasks.get(&assignment.task_id), workers.get(&assignment.worker_id)) else {
+            continue;
+        };
+        let (minutes, location) = progress
+            .entry(worker.id)
+            .or_insert_with(|| (worker.wait_minutes(shift_start), worker.location.clone()));
+
+        let proficiency_factor = worker
+            .proficiency_for(task)
+            .map(|proficiency| proficiency.execution_time_factor())
+            .unwrap_or(1.0);
+        let speed = worker.travel_speed.unwrap_or(travel_speed);
+        *minutes += location.distance_to(&task.location) / speed
+            + task.estimated_duration.unwrap_or(default_duration) * proficiency_factor
+            + task.dropoff_distance() / speed;
+        *location = task.dropoff.clone().unwrap_or_else(|| task.location.clone());
+    }
+
+    progress.into_iter().map(|(worker_id, (minutes, _))| (worker_id, minutes)).collect()
+}
+
+/// Estimated minutes from `start` for the whole plan to complete: the busiest worker's total
+///
+/// Each worker's tasks are timed as in [`worker_finish_times`]: any wait for
+/// its current task once, then travel, work and dropoff for each task in
+/// turn. Returns the largest total, 0.0 for an empty plan, or infinity if
+/// some worker would never finish.
+pub fn estimate_makespan(
+    assignments: &[Assignment],
+    tasks: &[Task],
+    workers: &[Worker],
+    start: DateTime<Utc>,
+    travel_speed: f64,
+    default_duration: f64,
+) -> f64 {
+    worker_minutes(tasks, workers, assignments, start, travel_speed, default_duration)
+        .into_values()
+        .map(|minutes| if minutes.is_nan() { f64::INFINITY } else { minutes })
+        .fold(0.0, f64::max)
+}
+
+/// Batch planner that also reports when each worker will be done
+#[derive(Debug)]
+pub struct TimeAwarePlanner<C = DistanceCostEstimator>
+where
+    C: CostEstimator,
+{
+    planner: GreedyBatchPlanner<C>,
+    max_tasks_per_worker: usize,
+    travel_speed: f64, // units per minute
+    default_duration: f64, // minutes assumed for tasks without an `estimated_duration`
+}
+
+impl<C> TimeAwarePlanner<C>
+where
+    C: CostEstimator,
+{
+    pub fn new(planner: GreedyBatchPlanner<C>, max_tasks_per_worker: usize) -> Self {
+        Self {
+            planner,
+            max_tasks_per_worker,
+            travel_speed: 1.0, // 1 unit per minute
+            default_duration: 30.0,
+        }
+    }
+
+    /// Travel at `travel_speed` units per minute, for workers without their own `travel_speed`
+    ///
+    /// Panics unless `travel_speed` is positive and finite.
+    pub fn with_travel_speed(mut self, travel_speed: f64) -> Self {
+        assert!(
+            travel_speed.is_finite() && travel_speed > 0.0,
+            "travel speed must be positive and finite, got {travel_speed}"
+        );
+        self.travel_speed = travel_speed;
+        self
+    }
+
+    /// Assume `minutes` for tasks that have no `estimated_duration`
+    pub fn with_default_duration(mut self, minutes: f64) -> Self {
+        self.default_duration = minutes;
+        self
+    }
+
+    /// Plan a shift starting at `shift_start` and chain each worker's tasks into a finish time
+    pub fn plan(&self, tasks: &[Task], workers: &[Worker], shift_start: DateTime<Utc>) -> TimedPlan {
+        let assignments = self.planner.plan_batch(tasks, workers, self.max_tasks_per_worker);
+        let worker_finish_times = worker_finish_times(tasks, workers, &assignments, shift_start, self.travel_speed, self.default_duration);
+        TimedPlan {
+            assignments,
+            worker_finish_times,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    fn shift_start() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-01-15T06:00:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_finish_time_chains_travel_and_durations() {
+        let tasks = vec![
+            Task::new(1, Location::new(3.0, 4.0), Priority::High).with_duration(10.0),
+            // Starts where task 1 ended, then carries goods back to the origin
+            Task::new(2, Location::new(3.0, 4.0), Priority::High)
+                .with_duration(5.0)
+                .with_dropoff(Location::new(0.0, 0.0)),
+        ];
+        let workers = vec![Worker::new(1, Location::new(0.0, 0.0), true)];
+        let assignments = vec![Assignment::new(1, 1, 0.0), Assignment::new(2, 1, 0.0)];
+
+        let finish = worker_finish_times(&tasks, &workers, &assignments, shift_start(), 1.0, 30.0);
+        // 5 travel + 10 work + 0 travel + 5 work + 5 dropoff
+        assert_eq!(finish[&1], shift_start() + Duration::minutes(25));
+    }
+
+    #[test]
+    fn test_finish_times_increase_with_more_tasks() {
+        let workers = vec![
+            Worker::new(1, Location::new(0.0, 0.0), true).with_max_tasks(5),
+            Worker::new(2, Location::new(100.0, 0.0), true).with_max_tasks(5),
+        ];
+        let task_at = |id, x| Task::new(id, Location::new(x, 0.0), Priority::Medium).with_duration(10.0);
+        let planner = TimeAwarePlanner::new(GreedyBatchPlanner::new(), 5);
+
+        let light = planner.plan(&[task_at(1, 1.0), task_at(2, 99.0)], &workers, shift_start());
+        let heavy = planner.plan(
+            &[task_at(1, 1.0), task_at(2, 99.0), task_at(3, 2.0), task_at(4, 3.0)],
+            &workers,
+            shift_start(),
+        );
+
+        assert_eq!(heavy.assignments.iter().filter(|a| a.worker_id == 1).count(), 3);
+        assert!(light.worker_finish_times[&1] > shift_start());
+        assert!(heavy.worker_finish_times[&1] > light.worker_finish_times[&1]);
+        assert_eq!(heavy.worker_finish_times[&2], light.worker_finish_times[&2]);
+    }
+
+    #[test]
+    fn test_busy_worker_starts_when_current_task_ends() {
+        let tasks = vec![Task::new(1, Location::new(0.0, 0.0), Priority::High).with_duration(10.0)];
+        let workers = vec![
+            Worker::new(1, Location::new(0.0, 0.0), true).with_busy_until(shift_start() + Duration::minutes(15)),
+        ];
+        let finish = worker_finish_times(&tasks, &workers, &[Assignment::new(1, 1, 0.0)], shift_start(), 1.0, 30.0);
+        assert_eq!(finish[&1], shift_start() + Duration::minutes(25));
+    }
+
+    #[test]
+    fn test_unreachable_worker_has_no_finish_time() {
+        let tasks = vec![Task::new(1, Location::new(100.0, 0.0), Priority::High).with_duration(10.0)];
+        let workers = vec![
+            Worker::new(1, Location::new(0.0, 0.0), true).with_travel_speed(1e-15),
+            Worker::new(2, Location::new(0.0, 0.0), true).with_travel_speed(0.0),
+        ];
+        let assignments = vec![Assignment::new(1, 1, 0.0), Assignment::new(1, 2, 0.0)];
+
+        // Far beyond any representable time, and never at all
+        assert!(worker_finish_times(&tasks, &workers, &assignments, shift_start(), 1.0, 30.0).is_empty());
+
+        let planner = TimeAwarePlanner::new(GreedyBatchPlanner::new(), 5);
+        let plan = planner.plan(&tasks, &workers[..1], shift_start());
+        assert_eq!(plan.assignments.len(), 1);
+        assert!(plan.worker_finish_times.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "travel speed must be positive and finite")]
+    fn test_zero_travel_speed_rejected() {
+        let _ = TimeAwarePlanner::new(GreedyBatchPlanner::new(), 5).with_travel_speed(0.0);
+    }
+
+    #[test]
+    fn test_makespan_is_busier_workers_total() {
+        let tasks = vec![
+            Task::new(1, Location::new(0.0, 0.0), Priority::Medium).with_duration(10.0),
+            Task::new(2, Location::new(0.0, 0.0), Priority::Medium).with_duration(25.0),
+            Task::new(3, Location::new(50.0, 0.0), Priority::Medium).with_duration(20.0),
+        ];
+        let workers = vec![
+            Worker::new(1, Location::new(0.0, 0.0), true),
+            Worker::new(2, Location::new(50.0, 0.0), true),
+        ];
+        let assignments = vec![Assignment::new(1, 1, 0.0), Assignment::new(2, 1, 0.0), Assignment::new(3, 2, 0.0)];
+
+        // Worker 1: 10 + 25 minutes on the spot; worker 2: 20 minutes
+        let makespan = estimate_makespan(&assignments, &tasks, &workers, shift_start(), 1.0, 30.0);
+        assert!((makespan - 35.0).abs() < 1e-9);
+        assert_eq!(estimate_makespan(&[], &tasks, &workers, shift_start(), 1.0, 30.0), 0.0);
+    }
+
+    #[test]
+    fn test_makespan_is_raw_minutes() {
+        let tasks = vec![
+            Task::new(1, Location::new(3.0, 4.0), Priority::Critical).with_duration(10.0),
+            Task::new(2, Location::new(3.0, 4.0), Priority::Low).with_duration(5.0),
+        ];
+        // Busy for 15 more minutes and half loaded; neither inflates the time
+        let workers = vec![Worker::new(1, Location::new(0.0, 0.0), true)
+            .with_load(0.5)
+            .with_busy_until(shift_start() + Duration::minutes(15))];
+        let assignments = vec![Assignment::new(1, 1, 0.0), Assignment::new(2, 1, 0.0)];
+
+        // 15 waiting once, 5 travel, 10 + 5 work
+        let makespan = estimate_makespan(&assignments, &tasks, &workers, shift_start(), 1.0, 30.0);
+        assert!((makespan - 35.0).abs() < 1e-9);
+
+        let stalled = vec![Worker::new(1, Location::new(0.0, 0.0), true).with_travel_speed(0.0)];
+        assert_eq!(estimate_makespan(&assignments, &tasks, &stalled, shift_start(), 1.0, 30.0), f64::INFINITY);
+    }
+}
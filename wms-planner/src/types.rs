@@ -1,9 +1,15 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 pub type TaskId = u32;
 pub type WorkerId = u32;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Mean Earth radius in meters, used for great-circle distances
+pub const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Location {
     pub x: f64,
     pub y: f64,
@@ -20,9 +26,39 @@ impl Location {
         let dy = self.y - other.y;
         (dx * dx + dy * dy).sqrt()
     }
+
+    /// Calculate great-circle distance in meters to another location,
+    /// interpreting `x` as longitude and `y` as latitude in degrees
+    pub fn haversine_distance_to(&self, other: &Location) -> f64 {
+        let lat1 = self.y.to_radians();
+        let lat2 = other.y.to_radians();
+        let dlat = lat2 - lat1;
+        let dlon = (other.x - self.x).to_radians();
+
+        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_METERS * a.sqrt().asin()
+    }
+}
+
+/// Euclidean length of the path visiting `points` in order, 0.0 for fewer than two
+pub fn path_length(points: &[Location]) -> f64 {
+    points.windows(2).map(|leg| leg[0].distance_to(&leg[1])).sum()
+}
+
+/// Rate of movement in location units per minute
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Velocity {
+    pub dx: f64,
+    pub dy: f64,
+}
+
+impl Velocity {
+    pub fn new(dx: f64, dy: f64) -> Self {
+        Self { dx, dy }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum Priority {
     Low,
     Medium,
@@ -42,12 +78,108 @@ impl Priority {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// How well a worker performs a skill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+pub enum Proficiency {
+    Novice,
+    Competent,
+    Expert,
+}
+
+impl Proficiency {
+    /// Multiplier applied to a task's execution time (lower is faster)
+    pub fn execution_time_factor(&self) -> f64 {
+        match self {
+            Proficiency::Novice => 1.5,
+            Proficiency::Competent => 1.0,
+            Proficiency::Expert => 0.7,
+        }
+    }
+}
+
+/// A skill a worker holds: how well, and until when
+///
+/// Serialized as the bare proficiency when the skill never lapses, e.g.
+/// `"Expert"`, and as `{"proficiency": "Expert", "expires_at": "..."}` otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+#[serde(from = "SkillRepr", into = "SkillRepr")]
+pub struct Skill {
+    pub proficiency: Proficiency,
+    pub expires_at: Option<DateTime<Utc>>, // when the qualification lapses, `None` if it never does
+}
+
+impl Skill {
+    pub fn new(proficiency: Proficiency) -> Self {
+        Self { proficiency, expires_at: None }
+    }
+
+    /// A qualification that lapses at `expires_at`
+    pub fn expiring(proficiency: Proficiency, expires_at: DateTime<Utc>) -> Self {
+        Self { proficiency, expires_at: Some(expires_at) }
+    }
+
+    /// Whether the qualification is still valid at `now`
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+}
+
+/// Wire form of [`Skill`], accepting the bare proficiency older scenarios use
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum SkillRepr {
+    Level(Proficiency),
+    Expiring {
+        proficiency: Proficiency,
+        #[serde(default)]
+        expires_at: Option<DateTime<Utc>>,
+    },
+}
+
+impl From<SkillRepr> for Skill {
+    fn from(repr: SkillRepr) -> Self {
+        match repr {
+            SkillRepr::Level(proficiency) => Skill::new(proficiency),
+            SkillRepr::Expiring { proficiency, expires_at } => Skill { proficiency, expires_at },
+        }
+    }
+}
+
+impl From<Skill> for SkillRepr {
+    fn from(skill: Skill) -> Self {
+        match skill.expires_at {
+            None => SkillRepr::Level(skill.proficiency),
+            expires_at => SkillRepr::Expiring { proficiency: skill.proficiency, expires_at },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Task {
     pub id: TaskId,
     pub location: Location,
     pub priority: Priority,
     pub estimated_duration: Option<f64>, // in minutes
+    #[serde(default)]
+    pub incompatible_with: HashSet<TaskId>, // tasks that may not share a worker with this one
+    #[serde(default)]
+    pub required_skill: Option<String>, // skill a worker must have to perform this task
+    #[serde(default)]
+    pub size: f64, // capacity units the task occupies; 0.0 fits any worker that can accept tasks
+    #[serde(default)]
+    pub preferred_worker: Option<WorkerId>, // worker to favor when costs are close, not required
+    #[serde(default)]
+    pub dropoff: Option<Location>, // where picked goods are delivered, if not done in place
+    #[serde(default)]
+    pub depends_on: Vec<TaskId>, // tasks that must be assigned or completed before this one
+    #[serde(default)]
+    pub zone: Option<String>, // warehouse zone the task is in, e.g. "A" or "cold-store"
+    #[serde(default)]
+    pub splittable: bool, // whether SplittingPlanner may divide `size` among several workers
+    #[serde(default = "default_required_workers")]
+    pub required_workers: usize, // workers needed together, e.g. 2 for a heavy lift
+    #[serde(default)]
+    pub service_window: Option<(DateTime<Utc>, DateTime<Utc>)>, // when work may start, e.g. while a dock door is free
 }
 
 impl Task {
@@ -57,22 +189,128 @@ impl Task {
             location,
             priority,
             estimated_duration: None,
+            incompatible_with: HashSet::new(),
+            required_skill: None,
+            size: 0.0,
+            preferred_worker: None,
+            dropoff: None,
+            depends_on: Vec::new(),
+            zone: None,
+            splittable: false,
+            required_workers: 1,
+            service_window: None,
         }
     }
 
+    /// Only allow work on the task to start between `opens` and `closes`
+    pub fn with_service_window(mut self, opens: DateTime<Utc>, closes: DateTime<Utc>) -> Self {
+        self.service_window = Some((opens, closes));
+        self
+    }
+
     pub fn with_duration(mut self, duration: f64) -> Self {
         self.estimated_duration = Some(duration);
         self
     }
+
+    pub fn with_incompatible(mut self, task_ids: impl IntoIterator<Item = TaskId>) -> Self {
+        self.incompatible_with.extend(task_ids);
+        self
+    }
+
+    pub fn with_required_skill(mut self, skill: impl Into<String>) -> Self {
+        self.required_skill = Some(skill.into());
+        self
+    }
+
+    pub fn with_size(mut self, size: f64) -> Self {
+        self.size = size.max(0.0);
+        self
+    }
+
+    pub fn with_preferred_worker(mut self, worker_id: WorkerId) -> Self {
+        self.preferred_worker = Some(worker_id);
+        self
+    }
+
+    pub fn with_dropoff(mut self, dropoff: Location) -> Self {
+        self.dropoff = Some(dropoff);
+        self
+    }
+
+    pub fn with_depends_on(mut self, task_ids: impl IntoIterator<Item = TaskId>) -> Self {
+        self.depends_on.extend(task_ids);
+        self
+    }
+
+    pub fn with_zone(mut self, zone: impl Into<String>) -> Self {
+        self.zone = Some(zone.into());
+        self
+    }
+
+    /// Need `count` workers on the task at once (honored by batch planners with teams enabled)
+    pub fn with_required_workers(mut self, count: usize) -> Self {
+        self.required_workers = count;
+        self
+    }
+
+    /// Allow the task's `size` to be shared among several workers
+    pub fn with_splittable(mut self) -> Self {
+        self.splittable = true;
+        self
+    }
+
+    /// Euclidean length of the pick→dropoff leg, 0.0 without a dropoff
+    pub fn dropoff_distance(&self) -> f64 {
+        self.dropoff.as_ref().map_or(0.0, |dropoff| self.location.distance_to(dropoff))
+    }
+
+    /// Great-circle length of the pick→dropoff leg in meters, 0.0 without a dropoff
+    pub fn dropoff_haversine_distance(&self) -> f64 {
+        self.dropoff
+            .as_ref()
+            .map_or(0.0, |dropoff| self.location.haversine_distance_to(dropoff))
+    }
+
+    /// Whether this task may be carried by the same worker as `other`
+    ///
+    /// Incompatibility is symmetric: it is enough for either task to list the other.
+    pub fn is_compatible_with(&self, other: &Task) -> bool {
+        !self.incompatible_with.contains(&other.id) && !other.incompatible_with.contains(&self.id)
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Worker {
     pub id: WorkerId,
     pub location: Location,
     pub is_available: bool,
     pub current_load: f64, // 0.0 to 1.0, where 1.0 is fully loaded
     pub max_tasks: usize,  // Maximum number of tasks this worker can handle
+    pub busy_until: Option<DateTime<Utc>>, // when the worker's current task completes
+    #[serde(default)]
+    pub skills: HashMap<String, Skill>, // skill name -> proficiency level and expiry
+    #[serde(default = "default_capacity")]
+    pub capacity: f64, // capacity units available when the worker is unloaded
+    #[serde(default)]
+    pub velocity: Option<Velocity>, // movement of a worker en route, `None` if standing still
+    #[serde(default)]
+    pub located_at: Option<DateTime<Utc>>, // when `location` was recorded, the origin for `velocity`
+    #[serde(default)]
+    pub last_assigned_at: Option<DateTime<Utc>>, // when the worker last received a task, `None` if never
+    #[serde(default)]
+    pub travel_speed: Option<f64>, // units per minute, overriding the estimator's speed for this worker
+}
+
+/// Tolerance when comparing task sizes against remaining capacity
+const CAPACITY_EPSILON: f64 = 1e-9;
+
+fn default_capacity() -> f64 {
+    1.0
+}
+
+fn default_required_workers() -> usize {
+    1
 }
 
 impl Worker {
@@ -83,6 +321,13 @@ impl Worker {
             is_available,
             current_load: 0.0,
             max_tasks: 1,
+            busy_until: None,
+            skills: HashMap::new(),
+            capacity: default_capacity(),
+            velocity: None,
+            located_at: None,
+            last_assigned_at: None,
+            travel_speed: None,
         }
     }
 
@@ -96,16 +341,134 @@ impl Worker {
         self
     }
 
+    pub fn with_busy_until(mut self, busy_until: DateTime<Utc>) -> Self {
+        self.busy_until = Some(busy_until);
+        self
+    }
+
+    /// Travel at `travel_speed` units per minute instead of the estimator's default
+    pub fn with_travel_speed(mut self, travel_speed: f64) -> Self {
+        self.travel_speed = Some(travel_speed);
+        self
+    }
+
+    pub fn with_last_assigned_at(mut self, last_assigned_at: DateTime<Utc>) -> Self {
+        self.last_assigned_at = Some(last_assigned_at);
+        self
+    }
+
+    /// Mark the worker as moving at `velocity` since it was at `location` at `located_at`
+    pub fn with_velocity(mut self, velocity: Velocity, located_at: DateTime<Utc>) -> Self {
+        self.velocity = Some(velocity);
+        self.located_at = Some(located_at);
+        self
+    }
+
+    /// Where the worker will be at `now` if it keeps its velocity
+    ///
+    /// Without a velocity or a `located_at` timestamp, or for a `now` before
+    /// `located_at`, this is the recorded location.
+    pub fn projected_location_at(&self, now: DateTime<Utc>) -> Location {
+        match (self.velocity, self.located_at) {
+            (Some(velocity), Some(located_at)) if now > located_at => {
+                let minutes = (now - located_at).num_milliseconds() as f64 / 60_000.0;
+                Location::new(
+                    self.location.x + velocity.dx * minutes,
+                    self.location.y + velocity.dy * minutes,
+                )
+            }
+            _ => self.location.clone(),
+        }
+    }
+
+    /// Minutes from `now` until the worker finishes its current task (0 if already free)
+    pub fn wait_minutes(&self, now: DateTime<Utc>) -> f64 {
+        match self.busy_until {
+            Some(busy_until) if busy_until > now => {
+                (busy_until - now).num_milliseconds() as f64 / 60_000.0
+            }
+            _ => 0.0,
+        }
+    }
+
+    pub fn with_skill(mut self, skill: impl Into<String>, proficiency: Proficiency) -> Self {
+        self.skills.insert(skill.into(), Skill::new(proficiency));
+        self
+    }
+
+    /// Add a qualification that lapses at `expires_at`, e.g. a forklift license due for renewal
+    pub fn with_skill_expiring(mut self, skill: impl Into<String>, proficiency: Proficiency, expires_at: DateTime<Utc>) -> Self {
+        self.skills.insert(skill.into(), Skill::expiring(proficiency, expires_at));
+        self
+    }
+
+    /// Whether the worker holds `skill` at `now`, treating an expired qualification as absent
+    pub fn has_skill_at(&self, skill: &str, now: DateTime<Utc>) -> bool {
+        self.skills.get(skill).is_some_and(|held| held.is_valid_at(now))
+    }
+
+    /// Proficiency in the skill a task requires, `None` if the task needs no skill
+    /// or the worker lacks it
+    pub fn proficiency_for(&self, task: &Task) -> Option<Proficiency> {
+        task.required_skill
+            .as_ref()
+            .and_then(|skill| self.skills.get(skill))
+            .map(|skill| skill.proficiency)
+    }
+
+    /// Whether the worker holds the skill the task requires at the current time
+    ///
+    /// Planners use [`can_handle_at`](Self::can_handle_at) with their own
+    /// reference time instead.
+    pub fn can_handle(&self, task: &Task) -> bool {
+        self.can_handle_at(task, Utc::now())
+    }
+
+    /// Whether the worker holds the skill the task requires at `now`
+    pub fn can_handle_at(&self, task: &Task, now: DateTime<Utc>) -> bool {
+        match &task.required_skill {
+            Some(skill) => self.has_skill_at(skill, now),
+            None => true,
+        }
+    }
+
+    /// Whether the worker has nothing on at `now`: no load, not on the move and not busy with a task
+    pub fn is_idle(&self, now: DateTime<Utc>) -> bool {
+        self.current_load == 0.0 && self.velocity.is_none() && self.wait_minutes(now) == 0.0
+    }
+
     pub fn can_accept_task(&self) -> bool {
         self.is_available && self.current_load < 1.0
     }
+
+    pub fn with_capacity(mut self, capacity: f64) -> Self {
+        self.capacity = capacity.max(0.0);
+        self
+    }
+
+    /// Capacity units left after the worker's current load
+    pub fn remaining_capacity(&self) -> f64 {
+        self.capacity * (1.0 - self.current_load)
+    }
+
+    /// Whether `task` fits in the remaining capacity once `used` units are
+    /// already taken by tasks assigned in the current plan
+    pub fn has_room_for(&self, task: &Task, used: f64) -> bool {
+        task.size <= self.remaining_capacity() - used + CAPACITY_EPSILON
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Assignment {
     pub task_id: TaskId,
     pub worker_id: WorkerId,
     pub estimated_cost: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub margin: Option<f64>, // runner-up cost minus chosen cost, `None` if no other worker was feasible
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub share: Option<f64>, // capacity units covered when a split task is shared, `None` for the whole task
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>, // why this worker was chosen, from planners with explanations enabled
 }
 
 impl Assignment {
@@ -114,8 +477,27 @@ impl Assignment {
             task_id,
             worker_id,
             estimated_cost,
+            margin: None,
+            share: None,
+            explanation: None,
         }
     }
+
+    pub fn with_margin(mut self, margin: f64) -> Self {
+        self.margin = Some(margin);
+        self
+    }
+
+    /// Cover only `share` capacity units of a task split across workers
+    pub fn with_share(mut self, share: f64) -> Self {
+        self.share = Some(share);
+        self
+    }
+
+    /// Whether a runner-up came within `threshold` of the chosen worker's cost
+    pub fn is_contested(&self, threshold: f64) -> bool {
+        self.margin.is_some_and(|margin| margin <= threshold)
+    }
 }
 
 #[cfg(test)]
@@ -129,6 +511,40 @@ mod tests {
         assert_eq!(loc1.distance_to(&loc2), 5.0);
     }
 
+    #[test]
+    fn test_path_length() {
+        assert_eq!(path_length(&[]), 0.0);
+        assert_eq!(path_length(&[Location::new(3.0, 4.0)]), 0.0);
+
+        // Straight line with an intermediate stop
+        let line = [Location::new(0.0, 0.0), Location::new(2.0, 0.0), Location::new(5.0, 0.0)];
+        assert_eq!(path_length(&line), 5.0);
+
+        // 3-4-5 triangle walked back to the start
+        let triangle = [
+            Location::new(0.0, 0.0),
+            Location::new(3.0, 0.0),
+            Location::new(3.0, 4.0),
+            Location::new(0.0, 0.0),
+        ];
+        assert_eq!(path_length(&triangle), 12.0);
+    }
+
+    #[test]
+    fn test_location_haversine_distance() {
+        // Paris to London, roughly 343.5 km
+        let paris = Location::new(2.3522, 48.8566);
+        let london = Location::new(-0.1278, 51.5074);
+        let distance = paris.haversine_distance_to(&london);
+        assert!((distance - 343_556.0).abs() < 1_000.0);
+        assert!((london.haversine_distance_to(&paris) - distance).abs() < 1e-6);
+
+        // One degree of longitude along the equator
+        let origin = Location::new(0.0, 0.0);
+        let east = Location::new(1.0, 0.0);
+        assert!((origin.haversine_distance_to(&east) - 111_195.0).abs() < 10.0);
+    }
+
     #[test]
     fn test_priority_ordering() {
         assert!(Priority::High.to_numeric() > Priority::Medium.to_numeric());
@@ -146,4 +562,99 @@ mod tests {
         let unavailable_worker = Worker::new(3, Location::new(0.0, 0.0), false);
         assert!(!unavailable_worker.can_accept_task());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_worker_fractional_capacity() {
+        let nearly_full = Worker::new(1, Location::new(0.0, 0.0), true).with_load(0.5);
+        let small = Task::new(1, Location::new(0.0, 0.0), Priority::Medium).with_size(0.5);
+        let large = Task::new(2, Location::new(0.0, 0.0), Priority::Medium).with_size(1.0);
+
+        assert!((nearly_full.remaining_capacity() - 0.5).abs() < 1e-9);
+        assert!(nearly_full.has_room_for(&small, 0.0));
+        assert!(!nearly_full.has_room_for(&large, 0.0));
+        assert!(!nearly_full.has_room_for(&small, 0.25));
+
+        // Unsized tasks keep the old behavior
+        let unsized_task = Task::new(3, Location::new(0.0, 0.0), Priority::Medium);
+        assert!(nearly_full.has_room_for(&unsized_task, 0.5));
+
+        let big_worker = Worker::new(2, Location::new(0.0, 0.0), true).with_capacity(4.0).with_load(0.5);
+        assert!(big_worker.has_room_for(&large, 0.5));
+    }
+
+    #[test]
+    fn test_task_compatibility_is_symmetric() {
+        let raw_meat = Task::new(1, Location::new(0.0, 0.0), Priority::Medium).with_incompatible([2]);
+        let ready_to_eat = Task::new(2, Location::new(0.0, 0.0), Priority::Medium);
+        let other = Task::new(3, Location::new(0.0, 0.0), Priority::Medium);
+
+        assert!(!raw_meat.is_compatible_with(&ready_to_eat));
+        assert!(!ready_to_eat.is_compatible_with(&raw_meat));
+        assert!(raw_meat.is_compatible_with(&other));
+    }
+
+    #[test]
+    fn test_worker_skills() {
+        let task = Task::new(1, Location::new(0.0, 0.0), Priority::Medium).with_required_skill("forklift");
+        let untrained = Worker::new(1, Location::new(0.0, 0.0), true);
+        let expert = Worker::new(2, Location::new(0.0, 0.0), true).with_skill("forklift", Proficiency::Expert);
+
+        assert!(!untrained.can_handle(&task));
+        assert!(expert.can_handle(&task));
+        assert_eq!(expert.proficiency_for(&task), Some(Proficiency::Expert));
+        assert!(untrained.can_handle(&Task::new(2, Location::new(0.0, 0.0), Priority::Low)));
+        assert!(Proficiency::Expert > Proficiency::Novice);
+    }
+
+    #[test]
+    fn test_expired_skill_treated_as_absent() {
+        let now = DateTime::parse_from_rfc3339("2024-01-15T10:00:00Z").unwrap().with_timezone(&Utc);
+        let task = Task::new(1, Location::new(0.0, 0.0), Priority::Medium).with_required_skill("forklift");
+        let worker = Worker::new(1, Location::new(0.0, 0.0), true)
+            .with_skill_expiring("forklift", Proficiency::Expert, now + chrono::Duration::days(1));
+
+        assert!(worker.can_handle_at(&task, now));
+        assert!(!worker.can_handle_at(&task, now + chrono::Duration::days(1)));
+        assert!(!worker.can_handle_at(&task, now + chrono::Duration::days(30)));
+    }
+
+    #[test]
+    fn test_skill_wire_format() {
+        let expires_at = DateTime::parse_from_rfc3339("2024-06-30T00:00:00Z").unwrap().with_timezone(&Utc);
+        let worker = Worker::new(1, Location::new(0.0, 0.0), true)
+            .with_skill("forklift", Proficiency::Expert)
+            .with_skill_expiring("reach-truck", Proficiency::Competent, expires_at);
+
+        let json = serde_json::to_value(&worker).unwrap();
+        assert_eq!(json["skills"]["forklift"], "Expert");
+        assert_eq!(json["skills"]["reach-truck"]["proficiency"], "Competent");
+        assert_eq!(serde_json::from_value::<Worker>(json).unwrap(), worker);
+    }
+
+    #[test]
+    fn test_worker_wait_minutes() {
+        let now = DateTime::parse_from_rfc3339("2024-01-15T10:00:00Z").unwrap().with_timezone(&Utc);
+        let free = Worker::new(1, Location::new(0.0, 0.0), true);
+        assert_eq!(free.wait_minutes(now), 0.0);
+
+        let busy = free.clone().with_busy_until(now + chrono::Duration::minutes(5));
+        assert!((busy.wait_minutes(now) - 5.0).abs() < 1e-9);
+
+        let finished = free.with_busy_until(now - chrono::Duration::minutes(5));
+        assert_eq!(finished.wait_minutes(now), 0.0);
+    }
+
+    #[test]
+    fn test_projected_location() {
+        let located_at = DateTime::parse_from_rfc3339("2024-01-15T10:00:00Z").unwrap().with_timezone(&Utc);
+        let still = Worker::new(1, Location::new(2.0, 3.0), true);
+        assert_eq!(still.projected_location_at(located_at), Location::new(2.0, 3.0));
+
+        let walking = still.with_velocity(Velocity::new(1.5, -0.5), located_at);
+        let later = located_at + chrono::Duration::minutes(4);
+        assert_eq!(walking.projected_location_at(later), Location::new(8.0, 1.0));
+        // No extrapolation backwards in time
+        let earlier = located_at - chrono::Duration::minutes(4);
+        assert_eq!(walking.projected_location_at(earlier), Location::new(2.0, 3.0));
+    }
+}
@@ -0,0 +1,389 @@
+use crate::types::{Location, Task, TaskId, Worker, WorkerId};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+/// Planner input that would produce meaningless assignments
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum InputError {
+    #[error("Task ID {0} appears more than once")]
+    DuplicateTaskId(TaskId),
+
+    #[error("Worker ID {0} appears more than once")]
+    DuplicateWorkerId(WorkerId),
+
+    #[error("Task {task_id} has a non-finite location ({x}, {y})")]
+    NonFiniteTaskLocation { task_id: TaskId, x: f64, y: f64 },
+
+    #[error("Task {task_id} has a non-finite dropoff location ({x}, {y})")]
+    NonFiniteDropoffLocation { task_id: TaskId, x: f64, y: f64 },
+
+    #[error("Worker {worker_id} has a non-finite location ({x}, {y})")]
+    NonFiniteWorkerLocation { worker_id: WorkerId, x: f64, y: f64 },
+
+    #[error("Worker {worker_id} has a non-finite load {load}")]
+    NonFiniteWorkerLoad { worker_id: WorkerId, load: f64 },
+
+    #[error("Worker {worker_id} has a non-finite velocity ({dx}, {dy})")]
+    NonFiniteWorkerVelocity { worker_id: WorkerId, dx: f64, dy: f64 },
+
+    #[error("Task {task_id} has an invalid estimated duration {duration}; expected a finite number of minutes, at least 0")]
+    InvalidTaskDuration { task_id: TaskId, duration: f64 },
+
+    #[error("Task {task_id} has an invalid size {size}; expected a finite number, at least 0")]
+    InvalidTaskSize { task_id: TaskId, size: f64 },
+
+    #[error("Task {task_id} has a service window closing at {closes} before it opens at {opens}")]
+    ReversedServiceWindow { task_id: TaskId, opens: DateTime<Utc>, closes: DateTime<Utc> },
+
+    #[error("Worker {worker_id} has an invalid capacity {capacity}; expected a finite number, at least 0")]
+    InvalidWorkerCapacity { worker_id: WorkerId, capacity: f64 },
+
+    #[error("Worker {worker_id} has an invalid travel speed {speed}; expected a finite number above 0")]
+    InvalidTravelSpeed { worker_id: WorkerId, speed: f64 },
+
+    #[error("Tasks depend on each other in a cycle: {}", format_cycle(.0))]
+    DependencyCycle(Vec<TaskId>),
+
+    #[error("Task {task_id} requires skill '{skill}', which no worker has")]
+    UnknownSkill { task_id: TaskId, skill: String },
+}
+
+fn format_cycle(cycle: &[TaskId]) -> String {
+    let mut ids: Vec<String> = cycle.iter().map(TaskId::to_string).collect();
+    ids.extend(cycle.first().map(TaskId::to_string)); // close the loop
+    ids.join(" -> ")
+}
+
+fn is_finite(location: &Location) -> bool {
+    location.x.is_finite() && location.y.is_finite()
+}
+
+fn is_finite_non_negative(value: f64) -> bool {
+    value.is_finite() && value >= 0.0
+}
+
+/// Order tasks so every task comes after the tasks it depends on
+///
+/// Among tasks whose prerequisites are all placed, higher priority goes first,
+/// then input order. Dependencies on IDs outside `tasks` don't affect the
+/// order. Fails with `InputError::DependencyCycle` naming one cycle, each task
+/// depending on the one after it.
+pub fn dependency_order(tasks: &[Task]) -> Result<Vec<&Task>, InputError> {
+    match sort_by_dependencies(tasks) {
+        (order, None) => Ok(order),
+        (_, Some(cycle)) => Err(InputError::DependencyCycle(cycle)),
+    }
+}
+
+/// Tasks in dependency order, leaving out any in or behind a cycle, plus one such cycle
+pub(crate) fn sort_by_dependencies(tasks: &[Task]) -> (Vec<&Task>, Option<Vec<TaskId>>) {
+    let index: HashMap<TaskId, usize> = tasks.iter().enumerate().map(|(i, task)| (task.id, i)).collect();
+    let prerequisites = |task: &Task| -> HashSet<usize> {
+        task.depends_on.iter().filter_map(|id| index.get(id).copied()).collect()
+    };
+
+    let mut waiting_on: Vec<usize> = tasks.iter().map(|task| prerequisites(task).len()).collect();
+    let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); tasks.len()];
+    for (i, task) in tasks.iter().enumerate() {
+        for prerequisite in prerequisites(task) {
+            dependents[prerequisite].push(i);
+        }
+    }
+
+    let mut ready: Vec<usize> = (0..tasks.len()).filter(|&i| waiting_on[i] == 0).collect();
+    let mut order = Vec::with_capacity(tasks.len());
+    while !ready.is_empty() {
+        let next = (0..ready.len())
+            .max_by_key(|&pos| (tasks[ready[pos]].priority.to_numeric(), std::cmp::Reverse(ready[pos])))
+            .unwrap();
+        let i = ready.remove(next);
+        order.push(&tasks[i]);
+        for &dependent in &dependents[i] {
+            waiting_on[dependent] -= 1;
+            if waiting_on[dependent] == 0 {
+                ready.push(dependent);
+            }
+        }
+    }
+
+    if order.len() == tasks.len() {
+        return (order, None);
+    }
+
+    // Every task left over still waits on another left-over task, so following
+    // prerequisites from any of them must eventually revisit one
+    let mut path = Vec::new();
+    let mut current = (0..tasks.len()).find(|&i| waiting_on[i] > 0).unwrap();
+    while !path.contains(&current) {
+        path.push(current);
+        current = prerequisites(&tasks[current]).into_iter().filter(|&p| waiting_on[p] > 0).min().unwrap();
+    }
+    let start = path.iter().position(|&i| i == current).unwrap();
+    let cycle = path[start..].iter().map(|&i| tasks[i].id).collect();
+    (order, Some(cycle))
+}
+
+/// Reject duplicate IDs, non-finite coordinates, loads or velocities, negative
+/// or non-finite durations, sizes, capacities and travel speeds, reversed
+/// service windows, and dependency cycles
+///
+/// NaN distances compare false against everything, so without this check a
+/// single bad coordinate silently skews which worker wins. Fails with the
+/// first problem [`input_problems`] finds.
+pub fn validate_input(tasks: &[Task], workers: &[Worker]) -> Result<(), InputError> {
+    match input_problems(tasks, workers).into_iter().next() {
+        Some(problem) => Err(problem),
+        None => Ok(()),
+    }
+}
+
+/// Whether planners can give `tasks` and `workers` meaningful assignments
+///
+/// True unless [`input_problems`] finds something other than a dependency
+/// cycle; planners that follow dependencies leave cycles out themselves, and
+/// the others ignore them. Every planning entry point checks this and assigns
+/// nothing when it fails.
+pub(crate) fn is_plannable(tasks: &[Task], workers: &[Worker]) -> bool {
+    input_problems(tasks, workers)
+        .iter()
+        .all(|problem| matches!(problem, InputError::DependencyCycle(_)))
+}
+
+/// Every problem [`validate_input`] checks for, tasks first, then workers
+///
+/// At most one dependency cycle is reported.
+pub fn input_problems(tasks: &[Task], workers: &[Worker]) -> Vec<InputError> {
+    let mut problems = Vec::new();
+    let mut task_ids = HashSet::new();
+    for task in tasks {
+        if !task_ids.insert(task.id) {
+            problems.push(InputError::DuplicateTaskId(task.id));
+        }
+        if !is_finite(&task.location) {
+            problems.push(InputError::NonFiniteTaskLocation {
+                task_id: task.id,
+                x: task.location.x,
+                y: task.location.y,
+            });
+        }
+        if let Some(dropoff) = task.dropoff.as_ref().filter(|dropoff| !is_finite(dropoff)) {
+            problems.push(InputError::NonFiniteDropoffLocation {
+                task_id: task.id,
+                x: dropoff.x,
+                y: dropoff.y,
+            });
+        }
+        if let Some(duration) = task.estimated_duration.filter(|&duration| !is_finite_non_negative(duration)) {
+            problems.push(InputError::InvalidTaskDuration { task_id: task.id, duration });
+        }
+        if !is_finite_non_negative(task.size) {
+            problems.push(InputError::InvalidTaskSize { task_id: task.id, size: task.size });
+        }
+        if let Some((opens, closes)) = task.service_window.filter(|(opens, closes)| opens > closes) {
+            problems.push(InputError::ReversedServiceWindow { task_id: task.id, opens, closes });
+        }
+    }
+
+    problems.extend(dependency_order(tasks).err());
+
+    let mut worker_ids = HashSet::new();
+    for worker in workers {
+        if !worker_ids.insert(worker.id) {
+            problems.push(InputError::DuplicateWorkerId(worker.id));
+        }
+        if !is_finite(&worker.location) {
+            problems.push(InputError::NonFiniteWorkerLocation {
+                worker_id: worker.id,
+                x: worker.location.x,
+                y: worker.location.y,
+            });
+        }
+        if !worker.current_load.is_finite() {
+            problems.push(InputError::NonFiniteWorkerLoad {
+                worker_id: worker.id,
+                load: worker.current_load,
+            });
+        }
+        if let Some(velocity) = worker.velocity.filter(|v| !v.dx.is_finite() || !v.dy.is_finite()) {
+            problems.push(InputError::NonFiniteWorkerVelocity {
+                worker_id: worker.id,
+                dx: velocity.dx,
+                dy: velocity.dy,
+            });
+        }
+        if !is_finite_non_negative(worker.capacity) {
+            problems.push(InputError::InvalidWorkerCapacity {
+                worker_id: worker.id,
+                capacity: worker.capacity,
+            });
+        }
+        if let Some(speed) = worker.travel_speed.filter(|&speed| !(speed.is_finite() && speed > 0.0)) {
+            problems.push(InputError::InvalidTravelSpeed { worker_id: worker.id, speed });
+        }
+    }
+    problems
+}
+
+/// Tasks requiring a skill that none of `workers` has, in task order
+///
+/// Not rejected by [`validate_input`], since such a task is merely
+/// unassignable, but usually a sign of a misspelled skill. A skill held only
+/// as a lapsed qualification still counts as known.
+pub fn unknown_skills(tasks: &[Task], workers: &[Worker]) -> Vec<InputError> {
+    tasks
+        .iter()
+        .filter_map(|task| {
+            let skill = task.required_skill.as_ref()?;
+            let known = workers.iter().any(|worker| worker.skills.contains_key(skill));
+            (!known).then(|| InputError::UnknownSkill {
+                task_id: task.id,
+                skill: skill.clone(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    fn task(id: TaskId, x: f64, y: f64) -> Task {
+        Task::new(id, Location::new(x, y), Priority::Medium)
+    }
+
+    fn worker(id: WorkerId, x: f64, y: f64) -> Worker {
+        Worker::new(id, Location::new(x, y), true)
+    }
+
+    #[test]
+    fn test_valid_input_accepted() {
+        assert_eq!(validate_input(&[task(1, 0.0, 0.0), task(2, 1.0, 1.0)], &[worker(1, 0.0, 0.0)]), Ok(()));
+    }
+
+    #[test]
+    fn test_nan_coordinates_rejected() {
+        let err = validate_input(&[task(1, f64::NAN, 0.0)], &[]).unwrap_err();
+        assert!(matches!(err, InputError::NonFiniteTaskLocation { task_id: 1, .. }));
+
+        let err = validate_input(&[], &[worker(7, 0.0, f64::INFINITY)]).unwrap_err();
+        assert!(matches!(err, InputError::NonFiniteWorkerLocation { worker_id: 7, .. }));
+
+        let dropoff = task(2, 0.0, 0.0).with_dropoff(Location::new(f64::NAN, 1.0));
+        assert!(matches!(
+            validate_input(&[dropoff], &[]),
+            Err(InputError::NonFiniteDropoffLocation { task_id: 2, .. })
+        ));
+
+        let mut loaded = worker(3, 0.0, 0.0);
+        loaded.current_load = f64::NAN;
+        assert!(matches!(
+            validate_input(&[], &[loaded]),
+            Err(InputError::NonFiniteWorkerLoad { worker_id: 3, .. })
+        ));
+
+        let moving = worker(5, 0.0, 0.0).with_velocity(crate::types::Velocity::new(f64::NAN, 0.0), chrono::Utc::now());
+        assert!(matches!(
+            validate_input(&[], &[moving]),
+            Err(InputError::NonFiniteWorkerVelocity { worker_id: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn test_out_of_range_numbers_rejected() {
+        let slow = task(1, 0.0, 0.0).with_duration(-5.0);
+        assert_eq!(
+            validate_input(&[slow], &[]),
+            Err(InputError::InvalidTaskDuration { task_id: 1, duration: -5.0 })
+        );
+
+        let mut huge = task(2, 0.0, 0.0);
+        huge.size = f64::INFINITY;
+        assert!(matches!(validate_input(&[huge], &[]), Err(InputError::InvalidTaskSize { task_id: 2, .. })));
+
+        let opens = chrono::Utc::now();
+        let reversed = task(3, 0.0, 0.0).with_service_window(opens, opens - chrono::Duration::minutes(1));
+        assert!(matches!(
+            validate_input(&[reversed], &[]),
+            Err(InputError::ReversedServiceWindow { task_id: 3, .. })
+        ));
+        let instant = task(4, 0.0, 0.0).with_service_window(opens, opens);
+        assert_eq!(validate_input(&[instant], &[]), Ok(()));
+
+        let mut strong = worker(5, 0.0, 0.0);
+        strong.capacity = f64::NAN;
+        assert!(matches!(
+            validate_input(&[], &[strong]),
+            Err(InputError::InvalidWorkerCapacity { worker_id: 5, .. })
+        ));
+
+        let parked = worker(6, 0.0, 0.0).with_travel_speed(0.0);
+        assert_eq!(
+            validate_input(&[], &[parked]),
+            Err(InputError::InvalidTravelSpeed { worker_id: 6, speed: 0.0 })
+        );
+    }
+
+    #[test]
+    fn test_duplicate_ids_rejected() {
+        assert_eq!(
+            validate_input(&[task(1, 0.0, 0.0), task(1, 5.0, 5.0)], &[]),
+            Err(InputError::DuplicateTaskId(1))
+        );
+        assert_eq!(
+            validate_input(&[], &[worker(4, 0.0, 0.0), worker(4, 1.0, 1.0)]),
+            Err(InputError::DuplicateWorkerId(4))
+        );
+        assert_eq!(InputError::DuplicateWorkerId(4).to_string(), "Worker ID 4 appears more than once");
+    }
+
+    #[test]
+    fn test_input_problems_collects_every_problem() {
+        let tasks = vec![task(1, 0.0, 0.0), task(1, f64::NAN, 0.0), task(2, 0.0, 0.0).with_required_skill("forklfit")];
+        let workers = vec![
+            worker(1, 0.0, 0.0).with_skill("forklift", crate::types::Proficiency::Competent),
+            worker(1, 0.0, 0.0),
+        ];
+
+        let problems = input_problems(&tasks, &workers);
+        assert_eq!(problems.len(), 3);
+        assert_eq!(problems[0], InputError::DuplicateTaskId(1));
+        assert!(matches!(problems[1], InputError::NonFiniteTaskLocation { task_id: 1, .. }));
+        assert_eq!(problems[2], InputError::DuplicateWorkerId(1));
+        assert_eq!(validate_input(&tasks, &workers), Err(InputError::DuplicateTaskId(1)));
+
+        assert_eq!(
+            unknown_skills(&tasks, &workers),
+            [InputError::UnknownSkill { task_id: 2, skill: "forklfit".to_string() }]
+        );
+    }
+
+    #[test]
+    fn test_dependency_cycle_rejected() {
+        let tasks = [
+            task(1, 0.0, 0.0),
+            task(2, 0.0, 0.0).with_depends_on([3]),
+            task(3, 0.0, 0.0).with_depends_on([4]),
+            task(4, 0.0, 0.0).with_depends_on([2]),
+            task(5, 0.0, 0.0).with_depends_on([4]),
+        ];
+        let err = validate_input(&tasks, &[]).unwrap_err();
+        assert_eq!(err, InputError::DependencyCycle(vec![2, 3, 4]));
+        assert_eq!(err.to_string(), "Tasks depend on each other in a cycle: 2 -> 3 -> 4 -> 2");
+
+        let selfish = task(6, 0.0, 0.0).with_depends_on([6]);
+        assert_eq!(dependency_order(&[selfish]).unwrap_err(), InputError::DependencyCycle(vec![6]));
+    }
+
+    #[test]
+    fn test_dependency_order_puts_prerequisites_first() {
+        let tasks = [
+            Task::new(1, Location::new(0.0, 0.0), Priority::Critical).with_depends_on([3]),
+            Task::new(2, Location::new(0.0, 0.0), Priority::Low),
+            Task::new(3, Location::new(0.0, 0.0), Priority::Medium).with_depends_on([99]),
+        ];
+        let order: Vec<TaskId> = dependency_order(&tasks).unwrap().iter().map(|task| task.id).collect();
+        assert_eq!(order, [3, 1, 2]);
+    }
+}